@@ -32,9 +32,14 @@ where
     C: Send + Sync + 'static,
     C::Api: substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Nonce>,
     C::Api: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>,
+    C::Api: pallet_identity_rpc::IdentityRuntimeApi<Block, AccountId>,
+    C::Api: pallet_identity_rpc::ReputationRuntimeApi<Block, AccountId>,
+    C::Api: pallet_network_factory_rpc::NetworkFactoryRuntimeApi<Block, AccountId>,
     C::Api: BlockBuilder<Block>,
     P: TransactionPool + 'static,
 {
+    use pallet_identity_rpc::{NaviId, NaviIdApiServer};
+    use pallet_network_factory_rpc::{Networks, NetworksApiServer};
     use pallet_transaction_payment_rpc::{TransactionPayment, TransactionPaymentApiServer};
     use substrate_frame_rpc_system::{System, SystemApiServer};
 
@@ -42,7 +47,9 @@ where
     let FullDeps { client, pool } = deps;
 
     module.merge(System::new(client.clone(), pool).into_rpc())?;
-    module.merge(TransactionPayment::new(client).into_rpc())?;
+    module.merge(TransactionPayment::new(client.clone()).into_rpc())?;
+    module.merge(NaviId::new(client.clone()).into_rpc())?;
+    module.merge(Networks::new(client).into_rpc())?;
 
     // Extend this RPC with a custom API by using the following syntax.
     // `YourRpcStruct` should have a reference to a client, which is needed
@@ -6,11 +6,18 @@
 //!
 //! This pallet allows networks to gate resources by requiring users to stake
 //! tokens for access, with support for tiered pricing and pay-per-use models.
+//! Resources can also require a NaviID, credentials or personhood, and
+//! network referenda can change their pricing.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
+extern crate alloc;
+use alloc::{vec, vec::Vec};
+
 pub use pallet::*;
 
+pub mod migrations;
+
 #[cfg(test)]
 mod mock;
 
@@ -19,6 +26,11 @@ mod tests;
 
 use codec::{Decode, Encode, MaxEncodedLen};
 use frame_support::pallet_prelude::*;
+use pallet_identity::{IdentityInspect, ReputationEvent, ReputationHandler};
+use pallet_network_factory::{
+    ConversionRate, NetworkAction, NetworkActionHandler, NetworkCleanup, NetworkInspect,
+    NetworkMembership, NetworkRole,
+};
 use scale_info::TypeInfo;
 use sp_runtime::RuntimeDebug;
 
@@ -33,9 +45,30 @@ pub struct Tier {
     pub duration_blocks: u32,
 }
 
+/// Identity requirements a caller must satisfy to access a resource
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct AccessRequirement<AccountId> {
+    /// Caller must have a registered NaviID
+    pub require_navi_id: bool,
+    /// Caller must hold a valid credential from this issuer
+    pub credential_issuer: Option<AccountId>,
+    /// Caller's personhood score must be at least this
+    pub min_personhood: u32,
+}
+
+impl<AccountId> Default for AccessRequirement<AccountId> {
+    fn default() -> Self {
+        Self {
+            require_navi_id: false,
+            credential_issuer: None,
+            min_personhood: 0,
+        }
+    }
+}
+
 /// Pricing configuration for a resource
 #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
-pub struct Pricing {
+pub struct Pricing<AccountId> {
     /// Base stake amount
     pub base_stake: u128,
     /// Base duration in blocks
@@ -44,17 +77,19 @@ pub struct Pricing {
     pub tiers: BoundedVec<Tier, ConstU32<10>>,
     /// Optional pay-per-use amount
     pub ppu: Option<u128>,
+    /// Identity requirements on top of payment
+    pub requirement: AccessRequirement<AccountId>,
 }
 
 /// Resource information
 #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
-pub struct Resource {
+pub struct Resource<AccountId> {
     /// Content identifier
     pub cid: BoundedVec<u8, ConstU32<256>>,
     /// Resource kind/type
     pub kind: u8,
     /// Pricing configuration
-    pub pricing: Pricing,
+    pub pricing: Pricing<AccountId>,
 }
 
 #[frame_support::pallet]
@@ -62,13 +97,24 @@ pub mod pallet {
     use super::*;
     use frame_system::pallet_prelude::*;
 
+    /// The in-code storage version.
+    const STORAGE_VERSION: StorageVersion = StorageVersion::new(2);
+
     #[pallet::pallet]
+    #[pallet::storage_version(STORAGE_VERSION)]
     pub struct Pallet<T>(_);
 
     #[pallet::config]
     pub trait Config: frame_system::Config + pallet_network_factory::Config {
         /// The overarching runtime event type.
         type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+        /// Source of NaviID and credential state for access requirements
+        type Identity: IdentityInspect<Self::AccountId>;
+        /// Receiver of membership and payment activity for NaviID reputation
+        type Reputation: ReputationHandler<Self::AccountId>;
+        /// Maximum number of resources a network can register
+        #[pallet::constant]
+        type MaxResources: Get<u32>;
     }
 
     /// Membership information for an account in a network
@@ -93,7 +139,7 @@ pub mod pallet {
         u64, // NetworkId
         Blake2_128Concat,
         u64, // ResourceId
-        Resource,
+        Resource<T::AccountId>,
     >;
 
     /// Next resource ID for each network
@@ -111,6 +157,10 @@ pub mod pallet {
         Membership<T>,
     >;
 
+    /// Number of memberships in each network
+    #[pallet::storage]
+    pub type MemberCount<T: Config> = StorageMap<_, Blake2_128Concat, u64, u32, ValueQuery>;
+
     /// Events for the access gate pallet.
     #[pallet::event]
     #[pallet::generate_deposit(pub(super) fn deposit_event)]
@@ -133,6 +183,18 @@ pub mod pallet {
             resource_id: u64,
             amount: u128,
         },
+        /// Access requirements were updated for a resource
+        AccessRequirementSet { network_id: u64, resource_id: u64 },
+        /// Pricing was updated for a resource
+        PricingSet { network_id: u64, resource_id: u64 },
+        /// A member was banned from a network by its owner
+        MemberBanned { network_id: u64, who: T::AccountId },
+        /// The prices of a network's resources were converted to a new coin
+        PricesConverted {
+            network_id: u64,
+            resources: u32,
+            rate: ConversionRate,
+        },
     }
 
     /// Errors for the access gate pallet.
@@ -158,11 +220,30 @@ pub mod pallet {
         ResourceIdOverflow,
         /// Pay-per-use not enabled
         PayPerUseNotEnabled,
+        /// Caller lacks the network role required
+        MissingNetworkRole,
+        /// Network is paused, archived or closed
+        NetworkNotActive,
+        /// Resource requires a registered NaviID
+        IdentityRequired,
+        /// Resource requires a valid credential from its issuer
+        CredentialRequired,
+        /// Caller's personhood score is below the resource's minimum
+        PersonhoodRequired,
+        /// Network admits only allowlisted accounts
+        NotAdmitted,
+        /// A converted price does not fit in a balance
+        PriceOverflow,
+        /// Network has registered the maximum number of resources
+        TooManyResources,
     }
 
     #[pallet::call]
     impl<T: Config> Pallet<T> {
         /// Register a new resource for a network
+        ///
+        /// Only network admins may register resources, up to [`Config::MaxResources`] per
+        /// network.
         #[pallet::call_index(0)]
         #[pallet::weight(Weight::from_parts(10_000, 0))]
         pub fn register_resource(
@@ -174,9 +255,9 @@ pub mod pallet {
             duration_blocks: u32,
             ppu: Option<u128>,
         ) -> DispatchResult {
-            let _who = ensure_signed(origin)?;
+            let who = ensure_signed(origin)?;
 
-            // TODO: Check that caller is network owner
+            Self::ensure_network_role(&who, network_id, NetworkRole::Admin)?;
 
             let bounded_cid = BoundedVec::try_from(cid).map_err(|_| Error::<T>::CidTooLong)?;
 
@@ -185,6 +266,7 @@ pub mod pallet {
                 duration_blocks,
                 tiers: BoundedVec::try_from(vec![]).unwrap(),
                 ppu,
+                requirement: AccessRequirement::default(),
             };
 
             let resource = Resource {
@@ -193,36 +275,38 @@ pub mod pallet {
                 pricing,
             };
 
-            let resource_id = NextResourceId::<T>::get(network_id);
-            let next_id = resource_id
-                .checked_add(1)
-                .ok_or(Error::<T>::ResourceIdOverflow)?;
-
-            Resources::<T>::insert(network_id, resource_id, resource);
-            NextResourceId::<T>::insert(network_id, next_id);
-
-            Self::deposit_event(Event::ResourceRegistered {
-                network_id,
-                resource_id,
-            });
-
-            Ok(())
+            Self::do_register_resource(network_id, resource)
         }
 
         /// Stake for access to a network's resources
+        ///
+        /// The resource must be registered. Only active networks accept new stakes, and
+        /// private networks only from allowlisted accounts.
         #[pallet::call_index(1)]
         #[pallet::weight(Weight::from_parts(10_000, 0))]
         pub fn stake_for_access(
             origin: OriginFor<T>,
             network_id: u64,
-            _resource_id: u64,
+            resource_id: u64,
             tier_idx: Option<u32>,
         ) -> DispatchResult {
             let who = ensure_signed(origin)?;
 
-            // Verify network exists
-            let _network = pallet_network_factory::Networks::<T>::get(network_id)
+            // Verify network exists and accepts stakes
+            let network = pallet_network_factory::Networks::<T>::get(network_id)
                 .ok_or(Error::<T>::NetworkNotFound)?;
+            ensure!(
+                network.state == pallet_network_factory::NetworkState::Active,
+                Error::<T>::NetworkNotActive
+            );
+            ensure!(
+                pallet_network_factory::Pallet::<T>::is_admitted(network_id, &who),
+                Error::<T>::NotAdmitted
+            );
+
+            let resource =
+                Resources::<T>::get(network_id, resource_id).ok_or(Error::<T>::ResourceNotFound)?;
+            Self::ensure_requirement(&who, &resource.pricing.requirement)?;
 
             // TODO: Lock tokens based on network coin type and tier
 
@@ -237,6 +321,9 @@ pub mod pallet {
                 expires_at,
             };
 
+            if !Memberships::<T>::contains_key(network_id, &who) {
+                MemberCount::<T>::mutate(network_id, |count| *count = count.saturating_add(1));
+            }
             Memberships::<T>::insert(network_id, &who, membership);
 
             Self::deposit_event(Event::AccessGranted {
@@ -270,7 +357,8 @@ pub mod pallet {
 
             // TODO: Unlock tokens
 
-            Memberships::<T>::remove(network_id, &account);
+            Self::remove_membership(network_id, &account);
+            T::Reputation::report(network_id, &account, ReputationEvent::MembershipCompleted);
 
             Self::deposit_event(Event::AccessExpired {
                 network_id,
@@ -290,6 +378,12 @@ pub mod pallet {
         ) -> DispatchResult {
             let who = ensure_signed(origin)?;
 
+            let network = pallet_network_factory::Networks::<T>::get(network_id)
+                .ok_or(Error::<T>::NetworkNotFound)?;
+            ensure!(
+                network.state == pallet_network_factory::NetworkState::Active,
+                Error::<T>::NetworkNotActive
+            );
             let resource =
                 Resources::<T>::get(network_id, resource_id).ok_or(Error::<T>::ResourceNotFound)?;
 
@@ -298,8 +392,12 @@ pub mod pallet {
                 .ppu
                 .ok_or(Error::<T>::PayPerUseNotEnabled)?;
 
+            Self::ensure_requirement(&who, &resource.pricing.requirement)?;
+
             // TODO: Transfer tokens
 
+            T::Reputation::report(network_id, &who, ReputationEvent::PayPerUse(ppu_amount));
+
             Self::deposit_event(Event::PayPerUsePaid {
                 network_id,
                 who,
@@ -309,5 +407,335 @@ pub mod pallet {
 
             Ok(())
         }
+
+        /// Set the identity requirements for a resource
+        ///
+        /// Only network admins may change requirements.
+        #[pallet::call_index(4)]
+        #[pallet::weight(Weight::from_parts(10_000, 0))]
+        pub fn set_access_requirement(
+            origin: OriginFor<T>,
+            network_id: u64,
+            resource_id: u64,
+            require_navi_id: bool,
+            credential_issuer: Option<T::AccountId>,
+            min_personhood: u32,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            Self::ensure_network_role(&who, network_id, NetworkRole::Admin)?;
+
+            Resources::<T>::try_mutate(network_id, resource_id, |maybe_resource| {
+                let resource = maybe_resource
+                    .as_mut()
+                    .ok_or(Error::<T>::ResourceNotFound)?;
+                resource.pricing.requirement = AccessRequirement {
+                    require_navi_id,
+                    credential_issuer,
+                    min_personhood,
+                };
+                Ok::<_, DispatchError>(())
+            })?;
+
+            Self::deposit_event(Event::AccessRequirementSet {
+                network_id,
+                resource_id,
+            });
+
+            Ok(())
+        }
+
+        /// Revoke a member's access and record the ban against their reputation
+        ///
+        /// Only network moderators may ban members.
+        #[pallet::call_index(5)]
+        #[pallet::weight(Weight::from_parts(10_000, 0))]
+        pub fn ban_member(
+            origin: OriginFor<T>,
+            network_id: u64,
+            account: T::AccountId,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            Self::ensure_network_role(&who, network_id, NetworkRole::Moderator)?;
+
+            // TODO: Unlock tokens
+
+            ensure!(
+                Memberships::<T>::contains_key(network_id, &account),
+                Error::<T>::MembershipNotFound
+            );
+            Self::remove_membership(network_id, &account);
+            T::Reputation::report(network_id, &account, ReputationEvent::Banned);
+
+            Self::deposit_event(Event::MemberBanned {
+                network_id,
+                who: account,
+            });
+
+            Ok(())
+        }
+
+        /// Set the stake, duration and pay-per-use price of a resource
+        ///
+        /// Only network admins may change pricing.
+        #[pallet::call_index(6)]
+        #[pallet::weight(Weight::from_parts(10_000, 0))]
+        pub fn set_pricing(
+            origin: OriginFor<T>,
+            network_id: u64,
+            resource_id: u64,
+            base_stake: u128,
+            duration_blocks: u32,
+            ppu: Option<u128>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            Self::ensure_network_role(&who, network_id, NetworkRole::Admin)?;
+            Self::do_set_pricing(network_id, resource_id, base_stake, duration_blocks, ppu)
+        }
+    }
+
+    impl<T: Config> Pallet<T> {
+        /// Replace the pricing of a resource, keeping its tiers and requirements
+        fn do_set_pricing(
+            network_id: u64,
+            resource_id: u64,
+            base_stake: u128,
+            duration_blocks: u32,
+            ppu: Option<u128>,
+        ) -> DispatchResult {
+            Resources::<T>::try_mutate(network_id, resource_id, |maybe_resource| {
+                let resource = maybe_resource
+                    .as_mut()
+                    .ok_or(Error::<T>::ResourceNotFound)?;
+                resource.pricing.base_stake = base_stake;
+                resource.pricing.duration_blocks = duration_blocks;
+                resource.pricing.ppu = ppu;
+                Ok::<_, DispatchError>(())
+            })?;
+
+            Self::deposit_event(Event::PricingSet {
+                network_id,
+                resource_id,
+            });
+
+            Ok(())
+        }
+
+        /// Convert the stakes and pay-per-use prices of a network's resources at `rate`
+        fn do_convert_prices(network_id: u64, rate: ConversionRate) -> DispatchResult {
+            let convert = |amount| rate.convert(amount).ok_or(Error::<T>::PriceOverflow);
+            let mut converted = Vec::new();
+            for (resource_id, mut resource) in Resources::<T>::iter_prefix(network_id) {
+                let pricing = &mut resource.pricing;
+                pricing.base_stake = convert(pricing.base_stake)?;
+                for tier in pricing.tiers.iter_mut() {
+                    tier.stake = convert(tier.stake)?;
+                }
+                pricing.ppu = pricing.ppu.map(convert).transpose()?;
+                converted.push((resource_id, resource));
+            }
+
+            let resources = converted.len() as u32;
+            for (resource_id, resource) in converted {
+                Resources::<T>::insert(network_id, resource_id, resource);
+            }
+
+            Self::deposit_event(Event::PricesConverted {
+                network_id,
+                resources,
+                rate,
+            });
+
+            Ok(())
+        }
+
+        /// Store a new resource under the network's next resource ID
+        fn do_register_resource(
+            network_id: u64,
+            resource: Resource<T::AccountId>,
+        ) -> DispatchResult {
+            let resource_id = NextResourceId::<T>::get(network_id);
+            ensure!(
+                resource_id < u64::from(T::MaxResources::get()),
+                Error::<T>::TooManyResources
+            );
+            let next_id = resource_id
+                .checked_add(1)
+                .ok_or(Error::<T>::ResourceIdOverflow)?;
+
+            Resources::<T>::insert(network_id, resource_id, resource);
+            NextResourceId::<T>::insert(network_id, next_id);
+
+            Self::deposit_event(Event::ResourceRegistered {
+                network_id,
+                resource_id,
+            });
+
+            Ok(())
+        }
+
+        /// Remove a membership and update the member count
+        fn remove_membership(network_id: u64, who: &T::AccountId) {
+            Memberships::<T>::remove(network_id, who);
+            MemberCount::<T>::mutate(network_id, |count| *count = count.saturating_sub(1));
+        }
+
+        /// Check that `who` holds `role` in network `network_id`
+        fn ensure_network_role(
+            who: &T::AccountId,
+            network_id: u64,
+            role: NetworkRole,
+        ) -> DispatchResult {
+            type Networks<T> = pallet_network_factory::Pallet<T>;
+            ensure!(
+                Networks::<T>::network_exists(network_id),
+                Error::<T>::NetworkNotFound
+            );
+            ensure!(
+                Networks::<T>::has_role(network_id, who, role),
+                Error::<T>::MissingNetworkRole
+            );
+            Ok(())
+        }
+
+        /// Check that `who` satisfies a resource's identity requirements
+        fn ensure_requirement(
+            who: &T::AccountId,
+            requirement: &AccessRequirement<T::AccountId>,
+        ) -> DispatchResult {
+            if requirement.require_navi_id {
+                ensure!(T::Identity::has_identity(who), Error::<T>::IdentityRequired);
+            }
+            if let Some(issuer) = &requirement.credential_issuer {
+                ensure!(
+                    T::Identity::has_credential(who, issuer),
+                    Error::<T>::CredentialRequired
+                );
+            }
+            ensure!(
+                T::Identity::personhood_score(who) >= requirement.min_personhood,
+                Error::<T>::PersonhoodRequired
+            );
+            Ok(())
+        }
+    }
+
+    impl<T: Config> NetworkCleanup for Pallet<T> {
+        fn cleanup(network_id: u64, limit: u32) -> (u32, bool) {
+            let resources = Resources::<T>::clear_prefix(network_id, limit, None);
+            let mut removed = resources.unique;
+            if resources.maybe_cursor.is_some() {
+                return (removed, false);
+            }
+
+            let memberships =
+                Memberships::<T>::clear_prefix(network_id, limit.saturating_sub(removed), None);
+            removed = removed.saturating_add(memberships.unique);
+            if memberships.maybe_cursor.is_some() {
+                return (removed, false);
+            }
+
+            // The per-network counters go last, once they fit within the limit.
+            if removed.saturating_add(2) > limit {
+                return (removed, false);
+            }
+            NextResourceId::<T>::remove(network_id);
+            MemberCount::<T>::remove(network_id);
+            (removed.saturating_add(2), true)
+        }
+    }
+
+    impl<T: Config> NetworkMembership<T::AccountId> for Pallet<T> {
+        fn is_member(network_id: u64, who: &T::AccountId) -> bool {
+            let now = frame_system::Pallet::<T>::block_number();
+            let mut network_id = Some(network_id);
+            while let Some(id) = network_id {
+                let active = Memberships::<T>::get(id, who)
+                    .is_some_and(|membership| membership.expires_at > now);
+                if active {
+                    return true;
+                }
+                network_id = pallet_network_factory::Pallet::<T>::membership_parent(id);
+            }
+            false
+        }
+
+        /// Counts the members of parents whose members are let in as well. Expired
+        /// memberships are skipped and accounts that are members of several networks count
+        /// once.
+        fn member_count(network_id: u64) -> u32 {
+            let now = frame_system::Pallet::<T>::block_number();
+            let mut members = alloc::collections::BTreeSet::new();
+            let mut network_id = Some(network_id);
+            while let Some(id) = network_id {
+                members.extend(
+                    Memberships::<T>::iter_prefix(id)
+                        .filter(|(_, membership)| membership.expires_at > now)
+                        .map(|(who, _)| who),
+                );
+                network_id = pallet_network_factory::Pallet::<T>::membership_parent(id);
+            }
+            members.len() as u32
+        }
+    }
+
+    impl<T: Config> NetworkActionHandler for Pallet<T> {
+        fn enact(network_id: u64, action: &NetworkAction) -> Option<DispatchResult> {
+            match *action {
+                NetworkAction::SetResourcePricing {
+                    resource_id,
+                    base_stake,
+                    duration_blocks,
+                    ppu,
+                } => Some(Self::do_set_pricing(
+                    network_id,
+                    resource_id,
+                    base_stake,
+                    duration_blocks,
+                    ppu,
+                )),
+                NetworkAction::RegisterResource(ref spec) => {
+                    let tiers = spec
+                        .tiers
+                        .iter()
+                        .map(|tier| Tier {
+                            name: tier.name.clone(),
+                            stake: tier.stake,
+                            duration_blocks: tier.duration_blocks,
+                        })
+                        .collect::<Vec<_>>();
+                    let resource = Resource {
+                        cid: spec.cid.clone(),
+                        kind: spec.kind,
+                        pricing: Pricing {
+                            base_stake: spec.base_stake,
+                            duration_blocks: spec.duration_blocks,
+                            // Both are bounded to ten tiers
+                            tiers: BoundedVec::truncate_from(tiers),
+                            ppu: spec.ppu,
+                            requirement: AccessRequirement::default(),
+                        },
+                    };
+                    Some(Self::do_register_resource(network_id, resource))
+                }
+                NetworkAction::ConvertPrices(rate) => {
+                    Some(Self::do_convert_prices(network_id, rate))
+                }
+                _ => None,
+            }
+        }
+
+        /// Converting prices rewrites every resource of the network.
+        fn weight(action: &NetworkAction) -> Weight {
+            match action {
+                NetworkAction::ConvertPrices(_) => {
+                    let resources = u64::from(T::MaxResources::get());
+                    T::DbWeight::get().reads_writes(resources, resources)
+                }
+                _ => Weight::zero(),
+            }
+        }
     }
 }
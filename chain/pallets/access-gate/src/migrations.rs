@@ -0,0 +1,127 @@
+//! Storage migrations for the access gate pallet.
+
+use crate::{Config, MemberCount, Memberships, Pallet};
+use frame_support::{
+    migrations::VersionedMigration,
+    traits::{Get, UncheckedOnRuntimeUpgrade},
+    weights::Weight,
+};
+
+/// Version 1: count the memberships of each network, so referenda decided by members
+/// measure their quorum against memberships created before the count existed.
+pub mod v1 {
+    use super::*;
+
+    /// Populates [`MemberCount`] from [`Memberships`].
+    pub struct InnerMigrateV0ToV1<T>(core::marker::PhantomData<T>);
+
+    impl<T: Config> UncheckedOnRuntimeUpgrade for InnerMigrateV0ToV1<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let mut reads = 0u64;
+            let mut writes = 0u64;
+            for (network_id, _, _) in Memberships::<T>::iter() {
+                MemberCount::<T>::mutate(network_id, |count| *count = count.saturating_add(1));
+                reads += 2;
+                writes += 1;
+            }
+            T::DbWeight::get().reads_writes(reads, writes)
+        }
+    }
+
+    /// [`InnerMigrateV0ToV1`] gated on the on-chain storage version.
+    pub type MigrateV0ToV1<T> = VersionedMigration<
+        0,
+        1,
+        InnerMigrateV0ToV1<T>,
+        Pallet<T>,
+        <T as frame_system::Config>::DbWeight,
+    >;
+}
+
+/// Version 2: add identity requirements to the pricing of every resource, starting out
+/// with none.
+pub mod v2 {
+    use super::*;
+    use crate::{AccessRequirement, Pricing, Resource, Tier};
+    use codec::{Decode, Encode, MaxEncodedLen};
+    use frame_support::{pallet_prelude::*, storage_alias};
+    use sp_runtime::RuntimeDebug;
+
+    /// Resource pricing before identity requirements were added in version 2.
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub struct OldPricing {
+        /// Base stake amount.
+        pub base_stake: u128,
+        /// Base duration in blocks.
+        pub duration_blocks: u32,
+        /// Optional tiered pricing.
+        pub tiers: BoundedVec<Tier, ConstU32<10>>,
+        /// Optional pay-per-use amount.
+        pub ppu: Option<u128>,
+    }
+
+    /// A resource with the version 0 and 1 pricing layout.
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub struct OldResource {
+        /// Content identifier.
+        pub cid: BoundedVec<u8, ConstU32<256>>,
+        /// Resource kind.
+        pub kind: u8,
+        /// Pricing configuration.
+        pub pricing: OldPricing,
+    }
+
+    /// [`crate::Resources`] with the version 0 and 1 layout.
+    #[storage_alias]
+    pub type Resources<T: Config> =
+        StorageDoubleMap<Pallet<T>, Blake2_128Concat, u64, Blake2_128Concat, u64, OldResource>;
+
+    /// Translates [`OldResource`] into [`Resource`] without identity requirements.
+    pub struct InnerMigrateV1ToV2<T>(core::marker::PhantomData<T>);
+
+    impl<T: Config> UncheckedOnRuntimeUpgrade for InnerMigrateV1ToV2<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let mut translated = 0u64;
+            crate::Resources::<T>::translate::<OldResource, _>(|_, _, old| {
+                translated += 1;
+                Some(Resource {
+                    cid: old.cid,
+                    kind: old.kind,
+                    pricing: Pricing {
+                        base_stake: old.pricing.base_stake,
+                        duration_blocks: old.pricing.duration_blocks,
+                        tiers: old.pricing.tiers,
+                        ppu: old.pricing.ppu,
+                        requirement: AccessRequirement::default(),
+                    },
+                })
+            });
+            T::DbWeight::get().reads_writes(translated, translated)
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade() -> Result<Vec<u8>, sp_runtime::TryRuntimeError> {
+            Ok((Resources::<T>::iter_keys().count() as u64).encode())
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(state: Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+            let count = u64::decode(&mut &state[..])
+                .map_err(|_| sp_runtime::TryRuntimeError::Other("invalid pre-upgrade state"))?;
+            frame_support::ensure!(
+                crate::Resources::<T>::iter().count() as u64 == count,
+                "resources were lost in translation"
+            );
+            Ok(())
+        }
+    }
+
+    /// [`InnerMigrateV1ToV2`] gated on the on-chain storage version.
+    pub type MigrateV1ToV2<T> = VersionedMigration<
+        1,
+        2,
+        InnerMigrateV1ToV2<T>,
+        Pallet<T>,
+        <T as frame_system::Config>::DbWeight,
+    >;
+}
@@ -1,5 +1,9 @@
 use crate as pallet_access_gate;
-use frame_support::derive_impl;
+use frame_support::{
+    derive_impl, parameter_types,
+    traits::{AsEnsureOriginWithArg, ConstU32, ConstU64},
+};
+use frame_system::{EnsureRoot, EnsureSigned};
 use sp_runtime::BuildStorage;
 
 type Block = frame_system::mocking::MockBlock<Test>;
@@ -7,6 +11,9 @@ type Block = frame_system::mocking::MockBlock<Test>;
 frame_support::construct_runtime!(
     pub enum Test {
         System: frame_system,
+        Balances: pallet_balances,
+        Assets: pallet_assets,
+        Identity: pallet_identity,
         NetworkFactory: pallet_network_factory,
         AccessGate: pallet_access_gate,
     }
@@ -15,19 +22,99 @@ frame_support::construct_runtime!(
 #[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
 impl frame_system::Config for Test {
     type Block = Block;
+    type AccountData = pallet_balances::AccountData<u64>;
+}
+
+#[derive_impl(pallet_balances::config_preludes::TestDefaultConfig)]
+impl pallet_balances::Config for Test {
+    type AccountStore = System;
+}
+
+#[derive_impl(pallet_assets::config_preludes::TestDefaultConfig)]
+impl pallet_assets::Config for Test {
+    type Currency = Balances;
+    type CreateOrigin = AsEnsureOriginWithArg<EnsureSigned<u64>>;
+    type ForceOrigin = EnsureRoot<u64>;
+    type MetadataDepositBase = ConstU64<0>;
+    type MetadataDepositPerByte = ConstU64<0>;
+    type Freezer = ();
+    type Holder = ();
+}
+
+parameter_types! {
+    pub const NameFee: u64 = 10;
+    pub const NameRenewalPeriod: u64 = 1_000;
+    pub const SubIdentityDeposit: u64 = 5;
+    pub const VouchStake: u64 = 20;
+    pub const VouchDuration: u64 = 100;
+    pub const ReputationHalfLife: u64 = 100;
+    pub const ReputationVolumeUnit: u128 = 10;
+}
+
+impl pallet_identity::Config for Test {
+    type RuntimeEvent = RuntimeEvent;
+    type RuntimeHoldReason = RuntimeHoldReason;
+    type Currency = Balances;
+    type NameFeeHandler = ();
+    type NameFee = NameFee;
+    type NameRenewalPeriod = NameRenewalPeriod;
+    type MaxRenewalPeriods = ConstU32<5>;
+    type MaxSubnames = ConstU32<2>;
+    type RegistrarOrigin = EnsureRoot<u64>;
+    type MaxRegistrars = ConstU32<2>;
+    type SubIdentityDeposit = SubIdentityDeposit;
+    type MaxSubIdentities = ConstU32<2>;
+    type MaxAuthKeys = ConstU32<2>;
+    type VouchStake = VouchStake;
+    type VouchDuration = VouchDuration;
+    type MaxVouches = ConstU32<2>;
+    type PersonhoodThreshold = ConstU32<1>;
+    type DuplicateOrigin = EnsureRoot<u64>;
+    type VouchSlashHandler = ();
+    type ReputationHalfLife = ReputationHalfLife;
+    type ReputationVolumeUnit = ReputationVolumeUnit;
+    type MaxCommitments = ConstU32<2>;
 }
 
 impl pallet_network_factory::Config for Test {
     type RuntimeEvent = RuntimeEvent;
+    type Assets = Assets;
+    type DefaultAssetMinBalance = ConstU64<1>;
+    type MaxVestingSchedules = ConstU32<2>;
+    type RuntimeHoldReason = RuntimeHoldReason;
+    type Currency = Balances;
+    type NetworkDeposit = ConstU64<10>;
+    type MaxNetworksPerAccount = ConstU32<3>;
+    type GovernanceOrigin = EnsureRoot<u64>;
+    type Cleanup = AccessGate;
+    type MaxCouncilMembers = ConstU32<3>;
+    type MaxSpendProposals = ConstU32<3>;
+    type Members = AccessGate;
+    type ActionHandler = AccessGate;
+    type MaxReferenda = ConstU32<2>;
+    type VoteLockingPeriod = ConstU64<10>;
+    type MaxChildren = ConstU32<2>;
+    type MaxDepth = ConstU32<3>;
+    type TemplateDeposit = ConstU64<5>;
+    type MaxTemplateResources = ConstU32<2>;
 }
 
 impl pallet_access_gate::Config for Test {
     type RuntimeEvent = RuntimeEvent;
+    type Identity = Identity;
+    type Reputation = Identity;
+    type MaxResources = ConstU32<4>;
 }
 
 pub fn new_test_ext() -> sp_io::TestExternalities {
-    frame_system::GenesisConfig::<Test>::default()
+    let mut t = frame_system::GenesisConfig::<Test>::default()
         .build_storage()
-        .unwrap()
-        .into()
+        .unwrap();
+    pallet_balances::GenesisConfig::<Test> {
+        balances: vec![(1, 1_000), (2, 1_000), (3, 1_000)],
+        ..Default::default()
+    }
+    .assimilate_storage(&mut t)
+    .unwrap();
+    t.into()
 }
@@ -1,5 +1,27 @@
-use crate::{mock::*, Error, Event};
+use crate::{migrations, mock::*, AccessRequirement, Error, Event};
 use frame_support::{assert_noop, assert_ok};
+use frame_support::{
+    traits::{GetStorageVersion, Hooks, OnRuntimeUpgrade, StorageVersion},
+    weights::Weight,
+};
+use pallet_network_factory::{
+    AdmissionMode, AssetAdmin, AssetConfig, ChildOwner, CoinKind, ConversionRate, Conviction,
+    Electorate, GovernanceConfig, NetworkAction, NetworkCleanup, NetworkMembership, NetworkRole,
+    NetworkState, ResourceSpec, ResourceTier, TreasuryPolicy, VotingScheme,
+};
+use sp_runtime::{traits::Dispatchable, Perbill};
+
+fn register_resource(network_id: u64) {
+    assert_ok!(AccessGate::register_resource(
+        RuntimeOrigin::signed(1),
+        network_id,
+        vec![1],
+        1,
+        0,
+        100,
+        None
+    ));
+}
 
 #[test]
 fn register_resource_works() {
@@ -56,6 +78,12 @@ fn stake_for_access_works() {
             None  // ppu
         ));
 
+        // Resources that were never registered cannot be staked for
+        assert_noop!(
+            AccessGate::stake_for_access(RuntimeOrigin::signed(2), 0, 1, None),
+            Error::<Test>::ResourceNotFound
+        );
+
         // Stake for access
         assert_ok!(AccessGate::stake_for_access(
             RuntimeOrigin::signed(2),
@@ -83,6 +111,7 @@ fn unstake_if_expired_works() {
             true,
             vec![1, 2, 3]
         ));
+        register_resource(0);
 
         // Stake for access
         assert_ok!(AccessGate::stake_for_access(
@@ -211,3 +240,1037 @@ fn register_resource_basic_works() {
         assert_eq!(resource.pricing.ppu, Some(25));
     });
 }
+
+#[test]
+fn set_access_requirement_only_owner() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+
+        assert_ok!(NetworkFactory::create_network(
+            RuntimeOrigin::signed(1),
+            true,
+            vec![1, 2, 3]
+        ));
+        assert_ok!(AccessGate::register_resource(
+            RuntimeOrigin::signed(1),
+            0,
+            vec![1, 2, 3],
+            1,
+            1000,
+            100,
+            None
+        ));
+
+        assert_noop!(
+            AccessGate::set_access_requirement(RuntimeOrigin::signed(2), 0, 0, true, None, 0),
+            Error::<Test>::MissingNetworkRole
+        );
+
+        assert_ok!(AccessGate::set_access_requirement(
+            RuntimeOrigin::signed(1),
+            0,
+            0,
+            true,
+            Some(5),
+            0
+        ));
+        let resource = crate::Resources::<Test>::get(0, 0).unwrap();
+        assert!(resource.pricing.requirement.require_navi_id);
+        assert_eq!(resource.pricing.requirement.credential_issuer, Some(5));
+
+        System::assert_last_event(
+            Event::AccessRequirementSet {
+                network_id: 0,
+                resource_id: 0,
+            }
+            .into(),
+        );
+    });
+}
+
+#[test]
+fn stake_requires_navi_id_when_configured() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+
+        assert_ok!(NetworkFactory::create_network(
+            RuntimeOrigin::signed(1),
+            true,
+            vec![1, 2, 3]
+        ));
+        assert_ok!(AccessGate::register_resource(
+            RuntimeOrigin::signed(1),
+            0,
+            vec![1, 2, 3],
+            1,
+            1000,
+            100,
+            Some(10)
+        ));
+        assert_ok!(AccessGate::set_access_requirement(
+            RuntimeOrigin::signed(1),
+            0,
+            0,
+            true,
+            None,
+            0
+        ));
+
+        // No NaviID yet
+        assert_noop!(
+            AccessGate::stake_for_access(RuntimeOrigin::signed(2), 0, 0, None),
+            Error::<Test>::IdentityRequired
+        );
+        assert_noop!(
+            AccessGate::pay_per_use(RuntimeOrigin::signed(2), 0, 0),
+            Error::<Test>::IdentityRequired
+        );
+
+        assert_ok!(Identity::register_identity(RuntimeOrigin::signed(2)));
+        assert_ok!(AccessGate::stake_for_access(
+            RuntimeOrigin::signed(2),
+            0,
+            0,
+            None
+        ));
+        assert_ok!(AccessGate::pay_per_use(RuntimeOrigin::signed(2), 0, 0));
+    });
+}
+
+#[test]
+fn stake_requires_valid_credential_when_configured() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+
+        assert_ok!(NetworkFactory::create_network(
+            RuntimeOrigin::signed(1),
+            true,
+            vec![1, 2, 3]
+        ));
+        assert_ok!(AccessGate::register_resource(
+            RuntimeOrigin::signed(1),
+            0,
+            vec![1, 2, 3],
+            1,
+            1000,
+            100,
+            None
+        ));
+        // Account 5 is the credential issuer
+        assert_ok!(AccessGate::set_access_requirement(
+            RuntimeOrigin::signed(1),
+            0,
+            0,
+            false,
+            Some(5),
+            0
+        ));
+
+        assert_ok!(Identity::register_identity(RuntimeOrigin::signed(2)));
+        assert_ok!(Identity::register_identity(RuntimeOrigin::signed(5)));
+
+        assert_noop!(
+            AccessGate::stake_for_access(RuntimeOrigin::signed(2), 0, 0, None),
+            Error::<Test>::CredentialRequired
+        );
+
+        assert_ok!(Identity::issue_credential(
+            RuntimeOrigin::signed(5),
+            2,
+            Default::default(),
+            None
+        ));
+        assert_ok!(AccessGate::stake_for_access(
+            RuntimeOrigin::signed(2),
+            0,
+            0,
+            None
+        ));
+
+        // A revoked credential no longer grants access
+        assert_ok!(Identity::revoke_credential(RuntimeOrigin::signed(5), 2));
+        assert_noop!(
+            AccessGate::stake_for_access(RuntimeOrigin::signed(2), 0, 0, None),
+            Error::<Test>::CredentialRequired
+        );
+    });
+}
+
+#[test]
+fn stake_requires_personhood_when_configured() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+
+        assert_ok!(NetworkFactory::create_network(
+            RuntimeOrigin::signed(1),
+            true,
+            vec![1, 2, 3]
+        ));
+        assert_ok!(AccessGate::register_resource(
+            RuntimeOrigin::signed(1),
+            0,
+            vec![1, 2, 3],
+            1,
+            1000,
+            100,
+            None
+        ));
+        assert_ok!(AccessGate::set_access_requirement(
+            RuntimeOrigin::signed(1),
+            0,
+            0,
+            false,
+            None,
+            1
+        ));
+
+        assert_ok!(Identity::register_identity(RuntimeOrigin::signed(2)));
+        assert_noop!(
+            AccessGate::stake_for_access(RuntimeOrigin::signed(2), 0, 0, None),
+            Error::<Test>::PersonhoodRequired
+        );
+
+        // A positive judgement from registrar 3 makes account 2 a person
+        assert_ok!(Identity::add_registrar(
+            RuntimeOrigin::root(),
+            3,
+            pallet_identity::FIELD_DISPLAY
+        ));
+        assert_ok!(Identity::request_judgement(RuntimeOrigin::signed(2), 0, 0));
+        assert_ok!(Identity::provide_judgement(
+            RuntimeOrigin::signed(3),
+            0,
+            2,
+            pallet_identity::Judgement::Reasonable,
+            Identity::fields_hash(&Default::default(), pallet_identity::FIELD_DISPLAY)
+        ));
+        assert_ok!(AccessGate::stake_for_access(
+            RuntimeOrigin::signed(2),
+            0,
+            0,
+            None
+        ));
+    });
+}
+
+#[test]
+fn ban_member_only_owner_and_records_reputation() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+
+        assert_ok!(NetworkFactory::create_network(
+            RuntimeOrigin::signed(1),
+            true,
+            vec![1, 2, 3]
+        ));
+        register_resource(0);
+        assert_ok!(Identity::register_identity(RuntimeOrigin::signed(2)));
+        assert_ok!(AccessGate::stake_for_access(
+            RuntimeOrigin::signed(2),
+            0,
+            0,
+            None
+        ));
+
+        assert_noop!(
+            AccessGate::ban_member(RuntimeOrigin::signed(3), 0, 2),
+            Error::<Test>::MissingNetworkRole
+        );
+        assert_ok!(AccessGate::ban_member(RuntimeOrigin::signed(1), 0, 2));
+        System::assert_last_event(
+            Event::MemberBanned {
+                network_id: 0,
+                who: 2,
+            }
+            .into(),
+        );
+        assert!(crate::Memberships::<Test>::get(0, 2).is_none());
+        assert_noop!(
+            AccessGate::ban_member(RuntimeOrigin::signed(1), 0, 2),
+            Error::<Test>::MembershipNotFound
+        );
+
+        let reputation = Identity::reputation(&2, 0).unwrap();
+        assert_eq!(reputation.bans, 1);
+        assert_eq!(reputation.memberships_completed, 0);
+    });
+}
+
+#[test]
+fn completed_membership_records_reputation() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+
+        assert_ok!(NetworkFactory::create_network(
+            RuntimeOrigin::signed(1),
+            true,
+            vec![1, 2, 3]
+        ));
+        register_resource(0);
+        assert_ok!(Identity::register_identity(RuntimeOrigin::signed(2)));
+        assert_ok!(AccessGate::stake_for_access(
+            RuntimeOrigin::signed(2),
+            0,
+            0,
+            None
+        ));
+
+        System::set_block_number(200);
+        assert_ok!(AccessGate::unstake_if_expired(
+            RuntimeOrigin::signed(3),
+            0,
+            2
+        ));
+
+        let reputation = Identity::reputation(&2, 0).unwrap();
+        assert_eq!(reputation.memberships_completed, 1);
+    });
+}
+
+#[test]
+fn network_roles_gate_administration() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+
+        assert_ok!(NetworkFactory::create_network(
+            RuntimeOrigin::signed(1),
+            true,
+            vec![1, 2, 3]
+        ));
+        assert_ok!(NetworkFactory::grant_role(
+            RuntimeOrigin::signed(1),
+            0,
+            3,
+            NetworkRole::Admin
+        ));
+        assert_ok!(NetworkFactory::grant_role(
+            RuntimeOrigin::signed(3),
+            0,
+            4,
+            NetworkRole::Moderator
+        ));
+
+        // Moderators cannot register resources, admins can
+        assert_noop!(
+            AccessGate::register_resource(RuntimeOrigin::signed(4), 0, vec![1], 1, 0, 100, None),
+            Error::<Test>::MissingNetworkRole
+        );
+        assert_ok!(AccessGate::register_resource(
+            RuntimeOrigin::signed(3),
+            0,
+            vec![1],
+            1,
+            0,
+            100,
+            None
+        ));
+        assert_ok!(AccessGate::set_access_requirement(
+            RuntimeOrigin::signed(3),
+            0,
+            0,
+            false,
+            None,
+            0
+        ));
+        assert_noop!(
+            AccessGate::register_resource(RuntimeOrigin::signed(3), 9, vec![1], 1, 0, 100, None),
+            Error::<Test>::NetworkNotFound
+        );
+
+        // Moderators can ban members
+        assert_ok!(AccessGate::stake_for_access(
+            RuntimeOrigin::signed(2),
+            0,
+            0,
+            None
+        ));
+        assert_ok!(AccessGate::ban_member(RuntimeOrigin::signed(4), 0, 2));
+    });
+}
+
+#[test]
+fn stakes_and_payments_require_an_active_network() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+
+        assert_ok!(NetworkFactory::create_network(
+            RuntimeOrigin::signed(1),
+            true,
+            vec![1, 2, 3]
+        ));
+        register_resource(0);
+        assert_ok!(AccessGate::register_resource(
+            RuntimeOrigin::signed(1),
+            0,
+            vec![2],
+            1,
+            0,
+            100,
+            Some(5)
+        ));
+        assert_ok!(NetworkFactory::set_network_state(
+            RuntimeOrigin::signed(1),
+            0,
+            NetworkState::Paused
+        ));
+        assert_noop!(
+            AccessGate::stake_for_access(RuntimeOrigin::signed(2), 0, 0, None),
+            Error::<Test>::NetworkNotActive
+        );
+        assert_noop!(
+            AccessGate::pay_per_use(RuntimeOrigin::signed(2), 0, 1),
+            Error::<Test>::NetworkNotActive
+        );
+
+        assert_ok!(NetworkFactory::set_network_state(
+            RuntimeOrigin::signed(1),
+            0,
+            NetworkState::Active
+        ));
+        assert_ok!(AccessGate::stake_for_access(
+            RuntimeOrigin::signed(2),
+            0,
+            0,
+            None
+        ));
+        assert_ok!(AccessGate::pay_per_use(RuntimeOrigin::signed(2), 0, 1));
+    });
+}
+
+#[test]
+fn resources_per_network_are_bounded() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+
+        assert_ok!(NetworkFactory::create_network(
+            RuntimeOrigin::signed(1),
+            true,
+            vec![1, 2, 3]
+        ));
+        for _ in 0..4 {
+            register_resource(0);
+        }
+        assert_noop!(
+            AccessGate::register_resource(RuntimeOrigin::signed(1), 0, vec![1], 1, 0, 100, None),
+            Error::<Test>::TooManyResources
+        );
+    });
+}
+
+#[test]
+fn cleanup_stays_within_the_limit() {
+    let mut ext = new_test_ext();
+    ext.execute_with(|| {
+        System::set_block_number(1);
+
+        assert_ok!(NetworkFactory::create_network(
+            RuntimeOrigin::signed(1),
+            true,
+            vec![1, 2, 3]
+        ));
+        register_resource(0);
+        register_resource(0);
+        assert_ok!(AccessGate::stake_for_access(
+            RuntimeOrigin::signed(2),
+            0,
+            0,
+            None
+        ));
+    });
+    // Removal limits only apply to committed storage
+    ext.commit_all().unwrap();
+
+    ext.execute_with(|| {
+        assert_eq!(AccessGate::cleanup(0, 1), (1, false));
+        assert_eq!(crate::Resources::<Test>::iter_prefix(0).count(), 1);
+    });
+    ext.commit_all().unwrap();
+
+    ext.execute_with(|| {
+        // The last resource and the membership fill the limit, so the counters wait
+        assert_eq!(AccessGate::cleanup(0, 2), (2, false));
+        assert_eq!(crate::Resources::<Test>::iter_prefix(0).count(), 0);
+        assert!(crate::Memberships::<Test>::get(0, 2).is_none());
+        assert_eq!(crate::NextResourceId::<Test>::get(0), 2);
+        assert_eq!(crate::MemberCount::<Test>::get(0), 1);
+    });
+    ext.commit_all().unwrap();
+
+    ext.execute_with(|| {
+        assert_eq!(AccessGate::cleanup(0, 2), (2, true));
+        assert_eq!(crate::NextResourceId::<Test>::get(0), 0);
+        assert_eq!(crate::MemberCount::<Test>::get(0), 0);
+    });
+}
+
+#[test]
+fn closed_network_resources_and_memberships_are_removed() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+
+        assert_ok!(NetworkFactory::create_network(
+            RuntimeOrigin::signed(1),
+            true,
+            vec![1, 2, 3]
+        ));
+        assert_ok!(AccessGate::register_resource(
+            RuntimeOrigin::signed(1),
+            0,
+            vec![1],
+            1,
+            0,
+            100,
+            None
+        ));
+        assert_ok!(AccessGate::stake_for_access(
+            RuntimeOrigin::signed(2),
+            0,
+            0,
+            None
+        ));
+
+        assert_ok!(NetworkFactory::close_network(
+            RuntimeOrigin::signed(1),
+            0,
+            TreasuryPolicy::Burn
+        ));
+        assert_noop!(
+            AccessGate::stake_for_access(RuntimeOrigin::signed(3), 0, 0, None),
+            Error::<Test>::NetworkNotActive
+        );
+        assert_noop!(
+            AccessGate::register_resource(RuntimeOrigin::signed(1), 0, vec![1], 1, 0, 100, None),
+            Error::<Test>::MissingNetworkRole
+        );
+
+        NetworkFactory::on_idle(2, Weight::MAX);
+
+        assert!(crate::Resources::<Test>::get(0, 0).is_none());
+        assert!(crate::Memberships::<Test>::get(0, 2).is_none());
+        assert_eq!(crate::NextResourceId::<Test>::get(0), 0);
+    });
+}
+
+#[test]
+fn admins_set_pricing() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+
+        assert_ok!(NetworkFactory::create_network(
+            RuntimeOrigin::signed(1),
+            true,
+            vec![1, 2, 3]
+        ));
+        assert_ok!(AccessGate::register_resource(
+            RuntimeOrigin::signed(1),
+            0,
+            vec![1],
+            1,
+            1000,
+            100,
+            None
+        ));
+
+        assert_noop!(
+            AccessGate::set_pricing(RuntimeOrigin::signed(2), 0, 0, 500, 50, Some(5)),
+            Error::<Test>::MissingNetworkRole
+        );
+        assert_noop!(
+            AccessGate::set_pricing(RuntimeOrigin::signed(1), 0, 1, 500, 50, Some(5)),
+            Error::<Test>::ResourceNotFound
+        );
+        assert_ok!(AccessGate::set_pricing(
+            RuntimeOrigin::signed(1),
+            0,
+            0,
+            500,
+            50,
+            Some(5)
+        ));
+        System::assert_last_event(
+            Event::PricingSet {
+                network_id: 0,
+                resource_id: 0,
+            }
+            .into(),
+        );
+
+        let pricing = crate::Resources::<Test>::get(0, 0).unwrap().pricing;
+        assert_eq!(pricing.base_stake, 500);
+        assert_eq!(pricing.duration_blocks, 50);
+        assert_eq!(pricing.ppu, Some(5));
+    });
+}
+
+#[test]
+fn memberships_are_counted_for_referenda() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+
+        assert_ok!(NetworkFactory::create_network(
+            RuntimeOrigin::signed(1),
+            true,
+            vec![1, 2, 3]
+        ));
+        register_resource(0);
+        for who in [2, 3, 2] {
+            assert_ok!(AccessGate::stake_for_access(
+                RuntimeOrigin::signed(who),
+                0,
+                0,
+                None
+            ));
+        }
+        assert_eq!(AccessGate::member_count(0), 2);
+        assert!(AccessGate::is_member(0, &2));
+        assert!(!AccessGate::is_member(0, &4));
+
+        assert_ok!(AccessGate::ban_member(RuntimeOrigin::signed(1), 0, 3));
+        assert_eq!(AccessGate::member_count(0), 1);
+        assert!(!AccessGate::is_member(0, &3));
+
+        // Expired memberships neither vote nor count
+        System::set_block_number(101);
+        assert!(!AccessGate::is_member(0, &2));
+        assert_eq!(AccessGate::member_count(0), 0);
+    });
+}
+
+#[test]
+fn private_networks_only_take_allowlisted_stakes() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+
+        assert_ok!(NetworkFactory::create_network(
+            RuntimeOrigin::signed(1),
+            true,
+            vec![1, 2, 3]
+        ));
+        register_resource(0);
+        assert_ok!(NetworkFactory::set_admission_mode(
+            RuntimeOrigin::signed(1),
+            0,
+            AdmissionMode::RequestToJoin
+        ));
+
+        assert_noop!(
+            AccessGate::stake_for_access(RuntimeOrigin::signed(2), 0, 0, None),
+            Error::<Test>::NotAdmitted
+        );
+        assert_ok!(NetworkFactory::request_to_join(RuntimeOrigin::signed(2), 0));
+        assert_ok!(NetworkFactory::add_to_allowlist(
+            RuntimeOrigin::signed(1),
+            0,
+            2
+        ));
+        assert_ok!(AccessGate::stake_for_access(
+            RuntimeOrigin::signed(2),
+            0,
+            0,
+            None
+        ));
+        assert!(AccessGate::is_member(0, &2));
+    });
+}
+
+#[test]
+fn parent_members_join_inheriting_children() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+
+        assert_ok!(NetworkFactory::create_network(
+            RuntimeOrigin::signed(1),
+            true,
+            vec![1, 2, 3]
+        ));
+        for inherit_membership in [true, false] {
+            assert_ok!(NetworkFactory::create_child_network(
+                RuntimeOrigin::signed(1),
+                0,
+                vec![4],
+                ChildOwner::ParentTreasury,
+                inherit_membership
+            ));
+        }
+        register_resource(0);
+        register_resource(1);
+        assert_ok!(AccessGate::stake_for_access(
+            RuntimeOrigin::signed(2),
+            0,
+            0,
+            None
+        ));
+        assert_ok!(AccessGate::stake_for_access(
+            RuntimeOrigin::signed(3),
+            1,
+            0,
+            None
+        ));
+
+        // A parent pass grants access to children that inherit membership
+        assert!(AccessGate::is_member(1, &2));
+        assert!(AccessGate::is_member(1, &3));
+        assert!(!AccessGate::is_member(2, &2));
+        assert!(!AccessGate::is_member(0, &3));
+        assert_eq!(AccessGate::member_count(1), 2);
+        assert_eq!(AccessGate::member_count(2), 0);
+
+        // Members of both the parent and the child count once
+        assert_ok!(AccessGate::stake_for_access(
+            RuntimeOrigin::signed(2),
+            1,
+            0,
+            None
+        ));
+        assert_eq!(AccessGate::member_count(1), 2);
+    });
+}
+
+#[test]
+fn referendum_quorum_uses_the_electorate_at_submission() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+
+        assert_ok!(NetworkFactory::create_network(
+            RuntimeOrigin::signed(1),
+            true,
+            vec![1, 2, 3]
+        ));
+        register_resource(0);
+        assert_ok!(AccessGate::stake_for_access(
+            RuntimeOrigin::signed(2),
+            0,
+            0,
+            None
+        ));
+        assert_ok!(NetworkFactory::set_governance(
+            RuntimeOrigin::signed(1),
+            0,
+            Some(GovernanceConfig {
+                electorate: Electorate::Members,
+                scheme: VotingScheme::SimpleMajority,
+                quorum: Perbill::from_percent(100),
+                voting_period: 10,
+            })
+        ));
+        assert_ok!(NetworkFactory::submit_referendum(
+            RuntimeOrigin::signed(2),
+            0,
+            NetworkAction::SetResourcePricing {
+                resource_id: 0,
+                base_stake: 500,
+                duration_blocks: 50,
+                ppu: None,
+            }
+        ));
+        assert_ok!(NetworkFactory::vote(
+            RuntimeOrigin::signed(2),
+            0,
+            0,
+            true,
+            0,
+            Conviction::None
+        ));
+
+        // Stakes made during voting do not raise the quorum
+        for who in [1, 3] {
+            assert_ok!(AccessGate::stake_for_access(
+                RuntimeOrigin::signed(who),
+                0,
+                0,
+                None
+            ));
+        }
+        assert_eq!(AccessGate::member_count(0), 3);
+
+        System::set_block_number(11);
+        assert_ok!(NetworkFactory::close_referendum(
+            RuntimeOrigin::signed(3),
+            0,
+            0
+        ));
+        System::assert_has_event(
+            pallet_network_factory::Event::ReferendumPassed {
+                network_id: 0,
+                index: 0,
+                result: Ok(()),
+            }
+            .into(),
+        );
+    });
+}
+
+#[test]
+fn member_referendum_changes_pricing() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+
+        assert_ok!(NetworkFactory::create_network(
+            RuntimeOrigin::signed(1),
+            true,
+            vec![1, 2, 3]
+        ));
+        assert_ok!(AccessGate::register_resource(
+            RuntimeOrigin::signed(1),
+            0,
+            vec![1],
+            1,
+            1000,
+            100,
+            None
+        ));
+        assert_ok!(AccessGate::stake_for_access(
+            RuntimeOrigin::signed(2),
+            0,
+            0,
+            None
+        ));
+        assert_ok!(NetworkFactory::set_governance(
+            RuntimeOrigin::signed(1),
+            0,
+            Some(GovernanceConfig {
+                electorate: Electorate::Members,
+                scheme: VotingScheme::SimpleMajority,
+                quorum: Perbill::from_percent(50),
+                voting_period: 10,
+            })
+        ));
+
+        assert_ok!(NetworkFactory::submit_referendum(
+            RuntimeOrigin::signed(2),
+            0,
+            NetworkAction::SetResourcePricing {
+                resource_id: 0,
+                base_stake: 500,
+                duration_blocks: 50,
+                ppu: Some(5),
+            }
+        ));
+        assert_ok!(NetworkFactory::vote(
+            RuntimeOrigin::signed(2),
+            0,
+            0,
+            true,
+            0,
+            Conviction::None
+        ));
+
+        System::set_block_number(11);
+        assert_ok!(NetworkFactory::close_referendum(
+            RuntimeOrigin::signed(3),
+            0,
+            0
+        ));
+        System::assert_has_event(
+            pallet_network_factory::Event::ReferendumPassed {
+                network_id: 0,
+                index: 0,
+                result: Ok(()),
+            }
+            .into(),
+        );
+        assert_eq!(
+            crate::Resources::<Test>::get(0, 0)
+                .unwrap()
+                .pricing
+                .base_stake,
+            500
+        );
+    });
+}
+
+#[test]
+fn templates_register_resources() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+
+        let resource = ResourceSpec {
+            cid: b"QmResource".to_vec().try_into().unwrap(),
+            kind: 1,
+            base_stake: 100,
+            duration_blocks: 50,
+            ppu: Some(2),
+            tiers: vec![ResourceTier {
+                name: b"Gold".to_vec().try_into().unwrap(),
+                stake: 500,
+                duration_blocks: 200,
+            }]
+            .try_into()
+            .unwrap(),
+        };
+        // No service basket enacts weights in the mock, so this template fails last
+        let weights = vec![(b"ID".to_vec().try_into().unwrap(), 400_000)]
+            .try_into()
+            .unwrap();
+        for basket_weights in [weights, Default::default()] {
+            assert_ok!(NetworkFactory::create_template(
+                RuntimeOrigin::signed(1),
+                None,
+                None,
+                None,
+                vec![resource.clone(); 2],
+                basket_weights
+            ));
+        }
+
+        // Provisioning is all or nothing
+        let call = |template_id| {
+            RuntimeCall::NetworkFactory(pallet_network_factory::Call::create_from_template {
+                template_id,
+                metadata_uri: vec![],
+            })
+        };
+        assert_eq!(
+            call(0)
+                .dispatch(RuntimeOrigin::signed(2))
+                .map_err(|e| e.error),
+            Err(pallet_network_factory::Error::<Test>::UnsupportedAction.into())
+        );
+        assert!(NetworkFactory::networks(0).is_none());
+        assert!(crate::Resources::<Test>::get(0, 0).is_none());
+
+        assert_ok!(call(1).dispatch(RuntimeOrigin::signed(2)));
+        assert_eq!(NetworkFactory::networks(0).unwrap().owner, 2);
+        assert!(crate::Resources::<Test>::get(0, 0).is_some());
+        let registered = crate::Resources::<Test>::get(0, 1).unwrap();
+        assert_eq!(registered.pricing.base_stake, 100);
+        assert_eq!(registered.pricing.ppu, Some(2));
+        assert_eq!(registered.pricing.tiers[0].stake, 500);
+        assert_eq!(crate::NextResourceId::<Test>::get(0), 2);
+    });
+}
+
+#[test]
+fn coin_migrations_convert_prices() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(NetworkFactory::create_network(
+            RuntimeOrigin::signed(1),
+            true,
+            vec![]
+        ));
+        assert_ok!(AccessGate::register_resource(
+            RuntimeOrigin::signed(1),
+            0,
+            vec![1],
+            1,
+            1000,
+            100,
+            Some(3)
+        ));
+        assert_ok!(AccessGate::register_resource(
+            RuntimeOrigin::signed(1),
+            0,
+            vec![2],
+            1,
+            u128::MAX,
+            100,
+            None
+        ));
+        assert_ok!(AccessGate::stake_for_access(
+            RuntimeOrigin::signed(2),
+            0,
+            0,
+            None
+        ));
+
+        let migrate = |from, to| {
+            RuntimeCall::NetworkFactory(pallet_network_factory::Call::migrate_coin {
+                network_id: 0,
+                to: CoinKind::MintAsset,
+                rate: ConversionRate { from, to },
+                asset: Some((
+                    AssetConfig {
+                        name: b"Guild".to_vec().try_into().unwrap(),
+                        symbol: b"GLD".to_vec().try_into().unwrap(),
+                        decimals: 12,
+                        min_balance: 1,
+                        admin: AssetAdmin::Treasury,
+                    },
+                    Default::default(),
+                )),
+            })
+            .dispatch(RuntimeOrigin::signed(1))
+            .map_err(|e| e.error)
+        };
+
+        // Migrations are all or nothing
+        assert_eq!(migrate(1, 2), Err(Error::<Test>::PriceOverflow.into()));
+        assert_eq!(
+            NetworkFactory::networks(0).unwrap().coin_kind,
+            CoinKind::UseNavi
+        );
+        assert_eq!(
+            crate::Resources::<Test>::get(0, 0)
+                .unwrap()
+                .pricing
+                .base_stake,
+            1000
+        );
+
+        assert_ok!(AccessGate::set_pricing(
+            RuntimeOrigin::signed(1),
+            0,
+            1,
+            10,
+            100,
+            None
+        ));
+        assert_ok!(migrate(2, 5));
+        System::assert_has_event(
+            Event::PricesConverted {
+                network_id: 0,
+                resources: 2,
+                rate: ConversionRate { from: 2, to: 5 },
+            }
+            .into(),
+        );
+        assert_eq!(
+            NetworkFactory::networks(0).unwrap().coin_kind,
+            CoinKind::MintAsset
+        );
+        let pricing = crate::Resources::<Test>::get(0, 0).unwrap().pricing;
+        assert_eq!(pricing.base_stake, 2500);
+        // Converted prices round down
+        assert_eq!(pricing.ppu, Some(7));
+        assert_eq!(
+            crate::Resources::<Test>::get(0, 1)
+                .unwrap()
+                .pricing
+                .base_stake,
+            25
+        );
+        // Memberships carry over
+        assert!(AccessGate::is_member(0, &2));
+    });
+}
+
+#[test]
+fn migrations_add_requirements_to_resources() {
+    new_test_ext().execute_with(|| {
+        let old_resource = migrations::v2::OldResource {
+            cid: b"QmOld".to_vec().try_into().unwrap(),
+            kind: 1,
+            pricing: migrations::v2::OldPricing {
+                base_stake: 100,
+                duration_blocks: 50,
+                tiers: Default::default(),
+                ppu: Some(2),
+            },
+        };
+        migrations::v2::Resources::<Test>::insert(0, 0, old_resource.clone());
+        migrations::v2::Resources::<Test>::insert(3, 1, old_resource);
+        StorageVersion::new(1).put::<AccessGate>();
+
+        migrations::v2::MigrateV1ToV2::<Test>::on_runtime_upgrade();
+
+        assert_eq!(AccessGate::on_chain_storage_version(), 2);
+        for (network_id, resource_id) in [(0, 0), (3, 1)] {
+            let resource = crate::Resources::<Test>::get(network_id, resource_id).unwrap();
+            assert_eq!(resource.cid.to_vec(), b"QmOld".to_vec());
+            assert_eq!(resource.pricing.base_stake, 100);
+            assert_eq!(resource.pricing.ppu, Some(2));
+            assert_eq!(resource.pricing.requirement, AccessRequirement::default());
+        }
+    });
+}
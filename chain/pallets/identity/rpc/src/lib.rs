@@ -0,0 +1,186 @@
+//! RPC methods for the NaviID identity pallet.
+
+use std::{
+    sync::Arc,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use jsonrpsee::{
+    core::RpcResult,
+    proc_macros::rpc,
+    types::error::{ErrorObject, ErrorObjectOwned},
+};
+use navi_id_verifier::{disclosure::Disclosure, Challenge};
+use pallet_identity::ReputationView;
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::{crypto::AccountId32, Bytes};
+use sp_runtime::traits::Block as BlockT;
+
+pub use pallet_identity_runtime_api::{
+    IdentityApi as IdentityRuntimeApi, ReputationApi as ReputationRuntimeApi,
+};
+
+/// NaviID RPC methods.
+#[rpc(client, server)]
+pub trait NaviIdApi<BlockHash> {
+    /// Verify a signed Sign-In with NaviID challenge.
+    ///
+    /// The signature is checked against the NaviID's authentication keys at block `at`, or the
+    /// best block if omitted, and the challenge must be issued for `domain` and not expired.
+    /// Returns the verified challenge so the caller can consume its nonce.
+    #[method(name = "naviId_verifySignIn")]
+    fn verify_sign_in(
+        &self,
+        message: String,
+        signature: Bytes,
+        domain: String,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Challenge>;
+
+    /// Verify revealed private attributes against the NaviID's commitments at block `at`, or
+    /// the best block if omitted.
+    #[method(name = "naviId_verifyDisclosure")]
+    fn verify_disclosure(&self, disclosure: Disclosure, at: Option<BlockHash>) -> RpcResult<()>;
+
+    /// The reputation of `account` in network `network_id`.
+    #[method(name = "naviId_reputation")]
+    fn reputation(
+        &self,
+        account: AccountId32,
+        network_id: u64,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Option<ReputationView>>;
+
+    /// The reputation of `account` in every network it has activity in.
+    #[method(name = "naviId_reputations")]
+    fn reputations(
+        &self,
+        account: AccountId32,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Vec<(u64, ReputationView)>>;
+}
+
+/// Error codes returned by the NaviID RPC.
+pub enum Error {
+    /// Calling into the runtime failed.
+    RuntimeError,
+    /// The challenge, signature or disclosure was rejected.
+    VerificationFailed,
+}
+
+impl From<Error> for i32 {
+    fn from(e: Error) -> i32 {
+        match e {
+            Error::RuntimeError => 1,
+            Error::VerificationFailed => 2,
+        }
+    }
+}
+
+/// Provides the NaviID RPC methods.
+pub struct NaviId<C, Block> {
+    client: Arc<C>,
+    _marker: std::marker::PhantomData<Block>,
+}
+
+impl<C, Block> NaviId<C, Block> {
+    /// Create a new instance of the NaviID RPC.
+    pub fn new(client: Arc<C>) -> Self {
+        Self {
+            client,
+            _marker: Default::default(),
+        }
+    }
+}
+
+impl<C, Block> NaviIdApiServer<<Block as BlockT>::Hash> for NaviId<C, Block>
+where
+    Block: BlockT,
+    C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+    C::Api: IdentityRuntimeApi<Block, AccountId32>,
+    C::Api: ReputationRuntimeApi<Block, AccountId32>,
+{
+    fn verify_sign_in(
+        &self,
+        message: String,
+        signature: Bytes,
+        domain: String,
+        at: Option<Block::Hash>,
+    ) -> RpcResult<Challenge> {
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+        let challenge = Challenge::parse(&message).map_err(verification_error)?;
+        let keys = self
+            .client
+            .runtime_api()
+            .auth_keys(at, challenge.account.clone())
+            .map_err(|e| runtime_error("Unable to query authentication keys.", e))?;
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_secs())
+            .unwrap_or_default();
+
+        challenge
+            .verify(&signature, &keys, &domain, now)
+            .map_err(verification_error)?;
+        Ok(challenge)
+    }
+
+    fn verify_disclosure(&self, disclosure: Disclosure, at: Option<Block::Hash>) -> RpcResult<()> {
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+        let commitments = self
+            .client
+            .runtime_api()
+            .commitments(at, disclosure.account.clone())
+            .map_err(|e| runtime_error("Unable to query commitments.", e))?;
+
+        disclosure.verify(&commitments).map_err(|e| {
+            ErrorObject::owned(
+                Error::VerificationFailed.into(),
+                "Disclosure verification failed.",
+                Some(e.to_string()),
+            )
+        })
+    }
+
+    fn reputation(
+        &self,
+        account: AccountId32,
+        network_id: u64,
+        at: Option<Block::Hash>,
+    ) -> RpcResult<Option<ReputationView>> {
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+        self.client
+            .runtime_api()
+            .reputation(at, account, network_id)
+            .map_err(|e| runtime_error("Unable to query reputation.", e))
+    }
+
+    fn reputations(
+        &self,
+        account: AccountId32,
+        at: Option<Block::Hash>,
+    ) -> RpcResult<Vec<(u64, ReputationView)>> {
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+        self.client
+            .runtime_api()
+            .reputations(at, account)
+            .map_err(|e| runtime_error("Unable to query reputation.", e))
+    }
+}
+
+fn runtime_error(message: &str, e: sp_api::ApiError) -> ErrorObjectOwned {
+    ErrorObject::owned(Error::RuntimeError.into(), message, Some(e.to_string()))
+}
+
+fn verification_error(e: navi_id_verifier::Error) -> ErrorObjectOwned {
+    ErrorObject::owned(
+        Error::VerificationFailed.into(),
+        "Sign-in verification failed.",
+        Some(e.to_string()),
+    )
+}
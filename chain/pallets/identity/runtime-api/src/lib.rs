@@ -0,0 +1,55 @@
+//! Runtime API definitions for the NaviID identity pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+use alloc::vec::Vec;
+
+use codec::Codec;
+use pallet_identity::{AuthKey, ReputationView};
+
+sp_api::decl_runtime_apis! {
+    /// Query NaviID state.
+    pub trait IdentityApi<AccountId>
+    where
+        AccountId: Codec,
+    {
+        /// The NaviID `who` acts for: itself if registered, or the parent of a sub-identity.
+        fn identity_of(who: AccountId) -> Option<AccountId>;
+
+        /// The keys currently allowed to sign login challenges for the NaviID `who`.
+        fn auth_keys(who: AccountId) -> Vec<AuthKey>;
+
+        /// The personhood score of `who` from vouches and registrar judgements.
+        fn personhood_score(who: AccountId) -> u32;
+
+        /// The private attribute commitments of `who`, as `(key, commitment)` pairs.
+        fn commitments(who: AccountId) -> Vec<(Vec<u8>, [u8; 32])>;
+    }
+
+    /// Resolve `.navi` names to accounts and back.
+    pub trait NameResolverApi<AccountId>
+    where
+        AccountId: Codec,
+    {
+        /// Resolve a full name such as `alice.navi` or `bot.alice.navi`.
+        ///
+        /// Returns `None` for unknown or expired names.
+        fn resolve(name: Vec<u8>) -> Option<AccountId>;
+
+        /// The primary name of `who`, if it still resolves to them.
+        fn primary_name(who: AccountId) -> Option<Vec<u8>>;
+    }
+
+    /// Query NaviID reputation derived from network activity.
+    pub trait ReputationApi<AccountId>
+    where
+        AccountId: Codec,
+    {
+        /// The reputation of `who` in network `network_id`, with the score decayed to this block.
+        fn reputation(who: AccountId, network_id: u64) -> Option<ReputationView>;
+
+        /// The reputation of `who` in every network it has activity in.
+        fn reputations(who: AccountId) -> Vec<(u64, ReputationView)>;
+    }
+}
@@ -6,21 +6,344 @@
 //!
 //! This pallet provides the foundation for NaviID, allowing users to register
 //! and manage their decentralized identities on the NaviNet chain.
+//!
+//! Besides the identity itself, a NaviID can hold credentials, `.navi` names,
+//! registrar judgements, sub-identities, authentication keys, vouches and a
+//! per-network reputation record. Other pallets read this state through the
+//! [`IdentityInspect`] trait.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
+extern crate alloc;
+use alloc::vec::Vec;
+
 pub use pallet::*;
 
+use codec::{Decode, DecodeWithMemTracking, Encode, MaxEncodedLen};
+use frame_support::{traits::ConstU32, BoundedVec};
+use scale_info::TypeInfo;
+use sp_runtime::{
+    traits::{BlakeTwo256, Hash as _},
+    RuntimeDebug,
+};
+
 #[cfg(test)]
 mod mock;
 
 #[cfg(test)]
 mod tests;
 
+/// Read-only access to NaviID state for other pallets.
+pub trait IdentityInspect<AccountId> {
+    /// Whether `who` has a registered NaviID.
+    fn has_identity(who: &AccountId) -> bool;
+
+    /// Whether `who` holds a credential from `issuer` that is neither revoked nor expired.
+    fn has_credential(who: &AccountId, issuer: &AccountId) -> bool;
+
+    /// The personhood score of `who`, zero for unknown or duplicate identities.
+    fn personhood_score(who: &AccountId) -> u32;
+}
+
+/// Network activity that affects the reputation of a NaviID.
+#[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum ReputationEvent {
+    /// A membership ran its full term without being revoked.
+    MembershipCompleted,
+    /// A pay-per-use payment of the given amount was made.
+    PayPerUse(u128),
+    /// Stake was slashed for misbehaviour.
+    Slashed,
+    /// The network owner banned the account.
+    Banned,
+}
+
+/// Hook for pallets to report network activity that feeds NaviID reputation.
+pub trait ReputationHandler<AccountId> {
+    /// Record `event` for `who` in network `network_id`.
+    fn report(network_id: u64, who: &AccountId, event: ReputationEvent);
+}
+
+impl<AccountId> ReputationHandler<AccountId> for () {
+    fn report(_network_id: u64, _who: &AccountId, _event: ReputationEvent) {}
+}
+
+/// Score points for a membership that ran its full term.
+pub const MEMBERSHIP_COMPLETED_POINTS: i64 = 10;
+/// Score points for being slashed.
+pub const SLASHED_POINTS: i64 = -50;
+/// Score points for being banned by a network owner.
+pub const BANNED_POINTS: i64 = -100;
+
+/// The suffix of every top-level name.
+pub const NAME_SUFFIX: &[u8] = b".navi";
+
+/// Minimum length of a single name label.
+pub const MIN_LABEL_LENGTH: usize = 3;
+
+/// Maximum length of a single name label.
+pub const MAX_LABEL_LENGTH: usize = 32;
+
+/// Maximum length of a full name: `label.label.navi`.
+pub const MAX_NAME_LENGTH: u32 = 70;
+
+/// A full `.navi` name, such as `alice.navi` or `bot.alice.navi`.
+pub type Name = BoundedVec<u8, ConstU32<MAX_NAME_LENGTH>>;
+
+/// Whether `label` is a valid name label: lowercase ASCII letters, digits and
+/// inner hyphens, between [`MIN_LABEL_LENGTH`] and [`MAX_LABEL_LENGTH`] bytes.
+pub fn is_valid_label(label: &[u8]) -> bool {
+    (MIN_LABEL_LENGTH..=MAX_LABEL_LENGTH).contains(&label.len())
+        && label
+            .iter()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || *c == b'-')
+        && label.first() != Some(&b'-')
+        && label.last() != Some(&b'-')
+}
+
+/// A bitmask of [`IdentityFields`] entries, used to scope registrar judgements.
+pub type FieldMask = u8;
+
+/// The display name field.
+pub const FIELD_DISPLAY: FieldMask = 1 << 0;
+/// The email hash field.
+pub const FIELD_EMAIL: FieldMask = 1 << 1;
+/// The web domain field.
+pub const FIELD_WEB: FieldMask = 1 << 2;
+/// The social handles field.
+pub const FIELD_SOCIAL: FieldMask = 1 << 3;
+
+/// A social network handle, such as `("github", "alice")`.
+pub type SocialHandle = (BoundedVec<u8, ConstU32<16>>, BoundedVec<u8, ConstU32<64>>);
+
+/// Public profile fields of a NaviID that registrars can verify.
+#[derive(
+    Clone,
+    Default,
+    Encode,
+    Decode,
+    DecodeWithMemTracking,
+    Eq,
+    PartialEq,
+    RuntimeDebug,
+    TypeInfo,
+    MaxEncodedLen,
+)]
+pub struct IdentityFields {
+    /// Display name.
+    pub display: Option<BoundedVec<u8, ConstU32<64>>>,
+    /// Hash of the email address, so the address itself stays off-chain.
+    pub email_hash: Option<[u8; 32]>,
+    /// Web domain.
+    pub web: Option<BoundedVec<u8, ConstU32<128>>>,
+    /// Social network handles.
+    pub social: BoundedVec<SocialHandle, ConstU32<8>>,
+}
+
+impl IdentityFields {
+    /// The mask of fields that differ between `self` and `other`.
+    pub fn changed_fields(&self, other: &Self) -> FieldMask {
+        let mut changed = 0;
+        if self.display != other.display {
+            changed |= FIELD_DISPLAY;
+        }
+        if self.email_hash != other.email_hash {
+            changed |= FIELD_EMAIL;
+        }
+        if self.web != other.web {
+            changed |= FIELD_WEB;
+        }
+        if self.social != other.social {
+            changed |= FIELD_SOCIAL;
+        }
+        changed
+    }
+
+    /// A copy with every field outside `mask` cleared.
+    pub fn masked(&self, mask: FieldMask) -> Self {
+        Self {
+            display: self.display.clone().filter(|_| mask & FIELD_DISPLAY != 0),
+            email_hash: self.email_hash.filter(|_| mask & FIELD_EMAIL != 0),
+            web: self.web.clone().filter(|_| mask & FIELD_WEB != 0),
+            social: if mask & FIELD_SOCIAL != 0 {
+                self.social.clone()
+            } else {
+                Default::default()
+            },
+        }
+    }
+}
+
+/// A registrar's assessment of an identity's fields.
+#[derive(
+    Clone,
+    Copy,
+    Encode,
+    Decode,
+    DecodeWithMemTracking,
+    Eq,
+    PartialEq,
+    RuntimeDebug,
+    TypeInfo,
+    MaxEncodedLen,
+)]
+pub enum Judgement<Balance> {
+    /// The registrar has no opinion.
+    Unknown,
+    /// A judgement was requested and the fee is held until the registrar responds.
+    FeePaid(Balance),
+    /// The fields look reasonable but were not checked in depth.
+    Reasonable,
+    /// The registrar verified the fields.
+    KnownGood,
+    /// The fields were correct once but are now out of date.
+    OutOfDate,
+    /// The fields are of low quality or imprecise.
+    LowQuality,
+    /// The fields are wrong. This judgement cannot be replaced by a new request.
+    Erroneous,
+}
+
+impl<Balance> Judgement<Balance> {
+    /// Whether this judgement is still waiting for the registrar.
+    pub fn is_pending(&self) -> bool {
+        matches!(self, Judgement::FeePaid(_))
+    }
+
+    /// Whether this judgement survives changes to the fields it covers.
+    pub fn is_sticky(&self) -> bool {
+        matches!(self, Judgement::FeePaid(_) | Judgement::Erroneous)
+    }
+
+    /// Whether this judgement verifies the identity.
+    pub fn is_positive(&self) -> bool {
+        matches!(self, Judgement::Reasonable | Judgement::KnownGood)
+    }
+}
+
+/// A registrar that judges identity fields.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct RegistrarInfo<AccountId, Balance> {
+    /// The account that provides judgements.
+    pub account: AccountId,
+    /// The fee charged for a judgement.
+    pub fee: Balance,
+    /// The fields this registrar verifies.
+    pub fields: FieldMask,
+}
+
+/// A bitmask of permissions granted to a sub-identity.
+///
+/// The bits are defined by the apps that check them with [`Pallet::sub_has_permissions`];
+/// this pallet stores them without interpreting them.
+pub type SubPermissions = u32;
+
+/// A sub-account attached to a NaviID.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct SubIdentityInfo<Balance> {
+    /// Human-readable label, such as `ci-bot`.
+    pub label: BoundedVec<u8, ConstU32<32>>,
+    /// Permissions granted by the parent.
+    pub permissions: SubPermissions,
+    /// The deposit held from the parent for this sub-identity.
+    pub deposit: Balance,
+}
+
+/// Reputation of a NaviID within one network.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct Reputation<BlockNumber> {
+    /// Memberships that ran their full term.
+    pub memberships_completed: u32,
+    /// Total pay-per-use volume.
+    pub ppu_volume: u128,
+    /// Number of slashes.
+    pub slashes: u32,
+    /// Number of bans.
+    pub bans: u32,
+    /// The score as of `updated_at`, before decay.
+    pub score: i64,
+    /// The block the score was last updated at.
+    pub updated_at: BlockNumber,
+}
+
+/// Reputation of a NaviID within one network, with the score decayed to the current block.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct ReputationView {
+    /// Memberships that ran their full term.
+    pub memberships_completed: u32,
+    /// Total pay-per-use volume.
+    pub ppu_volume: u128,
+    /// Number of slashes.
+    pub slashes: u32,
+    /// Number of bans.
+    pub bans: u32,
+    /// The current score.
+    pub score: i64,
+}
+
+/// The key of a private attribute, such as `email` or `legal_name`.
+pub type AttributeKey = BoundedVec<u8, ConstU32<32>>;
+
+/// The commitment to a private attribute value.
+///
+/// This is the BLAKE2-256 hash of the SCALE-encoded `(key, value, salt)` tuple. The salt must be
+/// 32 random bytes kept off-chain, so that the value cannot be guessed from the commitment.
+pub fn attribute_commitment(key: &[u8], value: &[u8], salt: &[u8; 32]) -> [u8; 32] {
+    BlakeTwo256::hash_of(&(key, value, salt)).into()
+}
+
+/// A public key that can sign login challenges for a NaviID.
+#[derive(
+    Clone,
+    Copy,
+    Encode,
+    Decode,
+    DecodeWithMemTracking,
+    Eq,
+    PartialEq,
+    RuntimeDebug,
+    TypeInfo,
+    MaxEncodedLen,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+pub enum AuthKey {
+    /// An sr25519 public key.
+    Sr25519([u8; 32]),
+    /// An ed25519 public key.
+    Ed25519([u8; 32]),
+}
+
 #[frame_support::pallet]
 pub mod pallet {
-    use frame_support::pallet_prelude::*;
+    use super::*;
+    use frame_support::{
+        pallet_prelude::*,
+        traits::{
+            fungible::{self, Balanced, BalancedHold, Credit, MutateHold},
+            tokens::{Fortitude, Precision, Preservation, Restriction},
+            Imbalance, OnUnbalanced,
+        },
+    };
     use frame_system::pallet_prelude::*;
+    use sp_runtime::traits::{Hash, Saturating, UniqueSaturatedInto, Zero};
+
+    /// Balance type of the configured currency.
+    pub type BalanceOf<T> = <<T as Config>::Currency as fungible::Inspect<
+        <T as frame_system::Config>::AccountId,
+    >>::Balance;
+
+    /// A judgement as stored by this pallet.
+    pub type JudgementOf<T> = Judgement<BalanceOf<T>>;
+
+    /// A sub-identity as stored by this pallet.
+    pub type SubIdentityInfoOf<T> = SubIdentityInfo<BalanceOf<T>>;
+
+    /// A registrar as stored by this pallet.
+    pub type RegistrarInfoOf<T> =
+        RegistrarInfo<<T as frame_system::Config>::AccountId, BalanceOf<T>>;
 
     #[pallet::pallet]
     pub struct Pallet<T>(_);
@@ -29,6 +352,324 @@ pub mod pallet {
     pub trait Config: frame_system::Config {
         /// The overarching runtime event type.
         type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+        /// The overarching hold reason.
+        type RuntimeHoldReason: From<HoldReason>;
+
+        /// The currency used to pay name and judgement fees.
+        type Currency: fungible::Mutate<Self::AccountId>
+            + fungible::Balanced<Self::AccountId>
+            + fungible::MutateHold<Self::AccountId, Reason = Self::RuntimeHoldReason>
+            + fungible::BalancedHold<Self::AccountId>;
+
+        /// Handler for name fees. Dropping them burns the fee.
+        type NameFeeHandler: OnUnbalanced<Credit<Self::AccountId, Self::Currency>>;
+
+        /// The fee charged for each renewal period of a top-level name.
+        #[pallet::constant]
+        type NameFee: Get<BalanceOf<Self>>;
+
+        /// The length of one renewal period in blocks, usually a year.
+        #[pallet::constant]
+        type NameRenewalPeriod: Get<BlockNumberFor<Self>>;
+
+        /// The maximum number of renewal periods that can be paid for at once.
+        #[pallet::constant]
+        type MaxRenewalPeriods: Get<u32>;
+
+        /// The maximum number of subnames under a single name.
+        #[pallet::constant]
+        type MaxSubnames: Get<u32>;
+
+        /// The origin that can add and remove registrars.
+        type RegistrarOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+        /// The maximum number of registrars.
+        #[pallet::constant]
+        type MaxRegistrars: Get<u32>;
+
+        /// The deposit held from the parent for each sub-identity.
+        #[pallet::constant]
+        type SubIdentityDeposit: Get<BalanceOf<Self>>;
+
+        /// The maximum number of sub-identities per NaviID.
+        #[pallet::constant]
+        type MaxSubIdentities: Get<u32>;
+
+        /// The maximum number of authentication keys per NaviID.
+        #[pallet::constant]
+        type MaxAuthKeys: Get<u32>;
+
+        /// The stake held from a voucher for each vouch.
+        #[pallet::constant]
+        type VouchStake: Get<BalanceOf<Self>>;
+
+        /// The number of blocks a vouch counts towards personhood.
+        #[pallet::constant]
+        type VouchDuration: Get<BlockNumberFor<Self>>;
+
+        /// The maximum number of vouches an identity can receive.
+        #[pallet::constant]
+        type MaxVouches: Get<u32>;
+
+        /// The personhood score at which an identity counts as a person and may vouch.
+        ///
+        /// A positive registrar judgement on its own reaches this score.
+        #[pallet::constant]
+        type PersonhoodThreshold: Get<u32>;
+
+        /// The origin that can declare an identity a duplicate.
+        type DuplicateOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+        /// Handler for stakes slashed from vouchers of duplicates. Dropping them burns the stake.
+        type VouchSlashHandler: OnUnbalanced<Credit<Self::AccountId, Self::Currency>>;
+
+        /// The number of blocks over which a reputation score halves.
+        #[pallet::constant]
+        type ReputationHalfLife: Get<BlockNumberFor<Self>>;
+
+        /// The pay-per-use volume that earns one reputation point.
+        #[pallet::constant]
+        type ReputationVolumeUnit: Get<u128>;
+
+        /// The maximum number of private attribute commitments per NaviID.
+        #[pallet::constant]
+        type MaxCommitments: Get<u32>;
+    }
+
+    /// Reasons for this pallet placing a hold on funds.
+    #[pallet::composite_enum]
+    pub enum HoldReason {
+        /// The fee for a requested judgement, paid to the registrar once it responds.
+        JudgementFee,
+        /// The deposit backing a sub-identity.
+        SubIdentityDeposit,
+        /// The stake backing a vouch for another identity.
+        VouchStake,
+    }
+
+    /// A registered NaviID.
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    #[scale_info(skip_type_params(T))]
+    pub struct IdentityInfo<T: Config> {
+        /// The block at which the identity was registered.
+        pub registered_at: BlockNumberFor<T>,
+        /// Public profile fields.
+        pub fields: IdentityFields,
+    }
+
+    /// A credential issued by one NaviID to another.
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    #[scale_info(skip_type_params(T))]
+    pub struct Credential<T: Config> {
+        /// Hash of the off-chain claim the issuer attests to.
+        pub claim_hash: T::Hash,
+        /// The block at which the credential was issued.
+        pub issued_at: BlockNumberFor<T>,
+        /// The block after which the credential is no longer valid, if any.
+        pub expires_at: Option<BlockNumberFor<T>>,
+        /// Whether the issuer has revoked the credential.
+        pub revoked: bool,
+    }
+
+    /// A registered `.navi` name.
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    #[scale_info(skip_type_params(T))]
+    pub struct NameRecord<T: Config> {
+        /// The account the name resolves to.
+        ///
+        /// For top-level names this is also the account that controls the name.
+        pub owner: T::AccountId,
+        /// The parent name for subnames, which are controlled by the parent owner.
+        pub parent: Option<Name>,
+        /// The block at which a top-level name expires. Subnames follow their parent.
+        pub expires_at: Option<BlockNumberFor<T>>,
+    }
+
+    /// A vouch from one NaviID for the personhood of another.
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    #[scale_info(skip_type_params(T))]
+    pub struct Vouch<T: Config> {
+        /// The stake held from the voucher.
+        pub stake: BalanceOf<T>,
+        /// The block after which the vouch no longer counts.
+        pub expires_at: BlockNumberFor<T>,
+    }
+
+    /// Registered identities by account.
+    #[pallet::storage]
+    pub type Identities<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, IdentityInfo<T>>;
+
+    /// Credentials by subject and issuer.
+    #[pallet::storage]
+    pub type Credentials<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId, // Subject
+        Blake2_128Concat,
+        T::AccountId, // Issuer
+        Credential<T>,
+    >;
+
+    /// Registered names, top-level and subnames.
+    #[pallet::storage]
+    pub type Names<T: Config> = StorageMap<_, Blake2_128Concat, Name, NameRecord<T>>;
+
+    /// Subnames registered under each top-level name.
+    #[pallet::storage]
+    pub type Subnames<T: Config> =
+        StorageMap<_, Blake2_128Concat, Name, BoundedVec<Name, T::MaxSubnames>, ValueQuery>;
+
+    /// The primary name chosen by each account, used for reverse lookup.
+    #[pallet::storage]
+    pub type PrimaryNames<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, Name>;
+
+    /// Registrars by index. Removed registrars leave an empty slot so indices stay stable.
+    #[pallet::storage]
+    pub type Registrars<T: Config> =
+        StorageValue<_, BoundedVec<Option<RegistrarInfoOf<T>>, T::MaxRegistrars>, ValueQuery>;
+
+    /// Sub-identities attached to each NaviID.
+    #[pallet::storage]
+    pub type SubsOf<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        BoundedVec<T::AccountId, T::MaxSubIdentities>,
+        ValueQuery,
+    >;
+
+    /// The parent NaviID and details of each sub-identity.
+    #[pallet::storage]
+    pub type SuperOf<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, (T::AccountId, SubIdentityInfoOf<T>)>;
+
+    /// Keys allowed to sign login challenges for each NaviID.
+    #[pallet::storage]
+    pub type AuthKeys<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        BoundedVec<AuthKey, T::MaxAuthKeys>,
+        ValueQuery,
+    >;
+
+    /// Vouches by vouchee and voucher.
+    #[pallet::storage]
+    pub type Vouches<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId, // Vouchee
+        Blake2_128Concat,
+        T::AccountId, // Voucher
+        Vouch<T>,
+    >;
+
+    /// The number of vouches each identity has received, expired or not.
+    ///
+    /// Expired vouches keep counting until removed with `unvouch` or `prune_vouch`.
+    #[pallet::storage]
+    pub type VouchCount<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, u32, ValueQuery>;
+
+    /// Identities proven to be duplicates, with the block they were reported at.
+    #[pallet::storage]
+    pub type Duplicates<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, BlockNumberFor<T>>;
+
+    /// Reputation by NaviID and network.
+    #[pallet::storage]
+    pub type Reputations<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        Twox64Concat,
+        u64, // NetworkId
+        Reputation<BlockNumberFor<T>>,
+    >;
+
+    /// Private attribute commitments of each NaviID.
+    #[pallet::storage]
+    pub type Commitments<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        BoundedVec<(AttributeKey, [u8; 32]), T::MaxCommitments>,
+        ValueQuery,
+    >;
+
+    /// Judgements by identity and registrar index.
+    #[pallet::storage]
+    pub type Judgements<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        Twox64Concat,
+        u32, // RegistrarIndex
+        JudgementOf<T>,
+    >;
+
+    /// A NaviID registered at genesis as `(account, top-level name, authentication keys)`.
+    pub type GenesisIdentityOf<T> = (
+        <T as frame_system::Config>::AccountId,
+        Option<Vec<u8>>,
+        Vec<AuthKey>,
+    );
+
+    /// NaviIDs registered at genesis, for development chains.
+    #[pallet::genesis_config]
+    #[derive(frame_support::DefaultNoBound)]
+    pub struct GenesisConfig<T: Config> {
+        /// Identities to register.
+        ///
+        /// Names are registered for the maximum number of renewal periods and set as the
+        /// account's primary name.
+        pub identities: Vec<GenesisIdentityOf<T>>,
+    }
+
+    #[pallet::genesis_build]
+    impl<T: Config> BuildGenesisConfig for GenesisConfig<T> {
+        fn build(&self) {
+            for (who, name, keys) in &self.identities {
+                assert!(
+                    !Identities::<T>::contains_key(who),
+                    "duplicate identity in genesis"
+                );
+                Identities::<T>::insert(
+                    who,
+                    IdentityInfo {
+                        registered_at: Zero::zero(),
+                        fields: IdentityFields::default(),
+                    },
+                );
+
+                if let Some(name) = name {
+                    let name = Pallet::<T>::parse_top_level_name(name.clone())
+                        .expect("invalid name in genesis");
+                    assert!(
+                        !Names::<T>::contains_key(&name),
+                        "duplicate name in genesis"
+                    );
+                    let expires_at = Pallet::<T>::renewal_duration(T::MaxRenewalPeriods::get());
+                    Names::<T>::insert(
+                        &name,
+                        NameRecord {
+                            owner: who.clone(),
+                            parent: None,
+                            expires_at: Some(expires_at),
+                        },
+                    );
+                    PrimaryNames::<T>::insert(who, name);
+                }
+
+                let keys: BoundedVec<AuthKey, T::MaxAuthKeys> = keys
+                    .clone()
+                    .try_into()
+                    .expect("too many authentication keys in genesis");
+                if !keys.is_empty() {
+                    AuthKeys::<T>::insert(who, keys);
+                }
+            }
+        }
     }
 
     /// Events for the identity pallet.
@@ -40,6 +681,204 @@ pub mod pallet {
             /// The account that registered the identity.
             who: T::AccountId,
         },
+        /// A credential was issued.
+        CredentialIssued {
+            /// The account the credential was issued to.
+            subject: T::AccountId,
+            /// The account that issued the credential.
+            issuer: T::AccountId,
+        },
+        /// A credential was revoked by its issuer.
+        CredentialRevoked {
+            /// The account the credential was issued to.
+            subject: T::AccountId,
+            /// The account that issued the credential.
+            issuer: T::AccountId,
+        },
+        /// A top-level name was registered.
+        NameRegistered {
+            /// The registered name.
+            name: Name,
+            /// The account that owns the name.
+            owner: T::AccountId,
+            /// The block at which the name expires.
+            expires_at: BlockNumberFor<T>,
+        },
+        /// A top-level name was renewed.
+        NameRenewed {
+            /// The renewed name.
+            name: Name,
+            /// The new expiry block.
+            expires_at: BlockNumberFor<T>,
+        },
+        /// A subname was created or repointed.
+        SubnameSet {
+            /// The full subname.
+            name: Name,
+            /// The account the subname resolves to.
+            target: T::AccountId,
+        },
+        /// A subname was removed.
+        SubnameRemoved {
+            /// The full subname.
+            name: Name,
+        },
+        /// An account set its primary name.
+        PrimaryNameSet {
+            /// The account.
+            who: T::AccountId,
+            /// The primary name.
+            name: Name,
+        },
+        /// An identity updated its fields.
+        IdentityFieldsSet {
+            /// The identity.
+            who: T::AccountId,
+        },
+        /// A registrar was added.
+        RegistrarAdded {
+            /// The registrar index.
+            index: u32,
+            /// The registrar account.
+            account: T::AccountId,
+        },
+        /// A registrar was removed.
+        RegistrarRemoved {
+            /// The registrar index.
+            index: u32,
+        },
+        /// A registrar changed its fee.
+        RegistrarFeeSet {
+            /// The registrar index.
+            index: u32,
+            /// The new fee.
+            fee: BalanceOf<T>,
+        },
+        /// A judgement was requested.
+        JudgementRequested {
+            /// The identity to be judged.
+            who: T::AccountId,
+            /// The registrar index.
+            registrar: u32,
+        },
+        /// A pending judgement request was cancelled and its fee released.
+        JudgementCancelled {
+            /// The identity.
+            who: T::AccountId,
+            /// The registrar index.
+            registrar: u32,
+        },
+        /// A registrar judged an identity.
+        JudgementGiven {
+            /// The judged identity.
+            who: T::AccountId,
+            /// The registrar index.
+            registrar: u32,
+            /// The judgement.
+            judgement: JudgementOf<T>,
+        },
+        /// A judgement was dropped because a field it covers changed.
+        JudgementInvalidated {
+            /// The identity.
+            who: T::AccountId,
+            /// The registrar index.
+            registrar: u32,
+        },
+        /// A sub-identity was attached to a NaviID.
+        SubIdentityAdded {
+            /// The parent NaviID.
+            parent: T::AccountId,
+            /// The sub-account.
+            sub: T::AccountId,
+        },
+        /// A sub-identity's label or permissions changed.
+        SubIdentityUpdated {
+            /// The parent NaviID.
+            parent: T::AccountId,
+            /// The sub-account.
+            sub: T::AccountId,
+        },
+        /// A sub-identity was detached and its deposit released.
+        SubIdentityRemoved {
+            /// The parent NaviID.
+            parent: T::AccountId,
+            /// The sub-account.
+            sub: T::AccountId,
+        },
+        /// An authentication key was added to a NaviID.
+        AuthKeyAdded {
+            /// The NaviID.
+            who: T::AccountId,
+            /// The added key.
+            key: AuthKey,
+        },
+        /// An authentication key was removed from a NaviID.
+        AuthKeyRemoved {
+            /// The NaviID.
+            who: T::AccountId,
+            /// The removed key.
+            key: AuthKey,
+        },
+        /// A NaviID vouched for the personhood of another.
+        Vouched {
+            /// The vouching NaviID.
+            voucher: T::AccountId,
+            /// The NaviID vouched for.
+            vouchee: T::AccountId,
+            /// The block after which the vouch no longer counts.
+            expires_at: BlockNumberFor<T>,
+        },
+        /// A vouch was withdrawn and its stake released.
+        Unvouched {
+            /// The vouching NaviID.
+            voucher: T::AccountId,
+            /// The NaviID vouched for.
+            vouchee: T::AccountId,
+        },
+        /// A NaviID was declared a duplicate.
+        DuplicateReported {
+            /// The duplicate NaviID.
+            who: T::AccountId,
+        },
+        /// A NaviID's reputation in a network changed.
+        ReputationUpdated {
+            /// The NaviID.
+            who: T::AccountId,
+            /// The network.
+            network_id: u64,
+            /// The new score.
+            score: i64,
+        },
+        /// A private attribute commitment was set or replaced.
+        CommitmentSet {
+            /// The NaviID.
+            who: T::AccountId,
+            /// The attribute key.
+            key: AttributeKey,
+        },
+        /// A private attribute commitment was removed.
+        CommitmentCleared {
+            /// The NaviID.
+            who: T::AccountId,
+            /// The attribute key.
+            key: AttributeKey,
+        },
+        /// A voucher's stake was slashed for vouching for a duplicate.
+        VoucherSlashed {
+            /// The vouching NaviID.
+            voucher: T::AccountId,
+            /// The duplicate NaviID.
+            vouchee: T::AccountId,
+            /// The slashed amount.
+            amount: BalanceOf<T>,
+        },
+        /// An expired vouch was removed and its stake released.
+        VouchPruned {
+            /// The vouching NaviID.
+            voucher: T::AccountId,
+            /// The NaviID vouched for.
+            vouchee: T::AccountId,
+        },
     }
 
     /// Errors for the identity pallet.
@@ -47,20 +886,1196 @@ pub mod pallet {
     pub enum Error<T> {
         /// Placeholder error.
         PlaceholderError,
+        /// The account already has a registered identity.
+        AlreadyRegistered,
+        /// The account has no registered identity.
+        IdentityNotFound,
+        /// The credential subject has no registered identity.
+        SubjectNotRegistered,
+        /// No credential from this issuer exists for the subject.
+        CredentialNotFound,
+        /// The credential has already been revoked.
+        CredentialAlreadyRevoked,
+        /// The expiry block is not in the future.
+        InvalidExpiry,
+        /// The name is malformed or uses a disallowed character.
+        InvalidName,
+        /// The name is registered and has not expired.
+        NameTaken,
+        /// The name is not registered.
+        NameNotFound,
+        /// The name has expired.
+        NameExpired,
+        /// The caller does not control the name.
+        NotNameOwner,
+        /// The number of renewal periods is zero or above the maximum.
+        InvalidPeriods,
+        /// The parent name already has the maximum number of subnames.
+        TooManySubnames,
+        /// The name does not resolve to the caller.
+        NameNotResolvedToCaller,
+        /// The maximum number of registrars has been reached.
+        TooManyRegistrars,
+        /// No registrar exists at this index.
+        RegistrarNotFound,
+        /// The caller is not the registrar at this index.
+        NotRegistrar,
+        /// The registrar's fee is above the maximum the caller accepts.
+        FeeTooHigh,
+        /// A judgement from this registrar is already pending.
+        JudgementPending,
+        /// The existing judgement is sticky and cannot be re-requested.
+        StickyJudgement,
+        /// No judgement from this registrar is pending.
+        JudgementNotPending,
+        /// `FeePaid` cannot be given as a judgement.
+        InvalidJudgement,
+        /// The identity fields changed since the registrar inspected them.
+        FieldsHashMismatch,
+        /// The NaviID already has the maximum number of sub-identities.
+        TooManySubIdentities,
+        /// The account is already a sub-identity.
+        AlreadySubIdentity,
+        /// The account is not a sub-identity of the caller.
+        NotSubIdentity,
+        /// The sub-identity label is too long.
+        LabelTooLong,
+        /// The NaviID already has the maximum number of authentication keys.
+        TooManyAuthKeys,
+        /// The key is already an authentication key of the NaviID.
+        AuthKeyExists,
+        /// The key is not an authentication key of the NaviID.
+        AuthKeyNotFound,
+        /// A NaviID cannot vouch for itself.
+        CannotVouchForSelf,
+        /// The caller's personhood score is below the threshold.
+        NotAPerson,
+        /// The caller already vouches for this NaviID.
+        AlreadyVouched,
+        /// The NaviID already has the maximum number of vouches.
+        TooManyVouches,
+        /// The caller does not vouch for this NaviID.
+        VouchNotFound,
+        /// The NaviID has been declared a duplicate.
+        DuplicateIdentity,
+        /// The attribute key is empty or too long.
+        InvalidAttributeKey,
+        /// The NaviID already has the maximum number of commitments.
+        TooManyCommitments,
+        /// No commitment exists for this attribute key.
+        CommitmentNotFound,
+        /// The vouch has not expired yet.
+        VouchNotExpired,
+        /// The identity has not requested a judgement from this registrar.
+        JudgementNotRequested,
     }
 
     #[pallet::call]
     impl<T: Config> Pallet<T> {
-        /// Register a new identity (placeholder implementation).
+        /// Register a new identity for the caller.
         #[pallet::call_index(0)]
         #[pallet::weight(Weight::from_parts(10_000, 0))]
         pub fn register_identity(origin: OriginFor<T>) -> DispatchResult {
             let who = ensure_signed(origin)?;
 
+            ensure!(
+                !Identities::<T>::contains_key(&who),
+                Error::<T>::AlreadyRegistered
+            );
+            ensure!(
+                !SuperOf::<T>::contains_key(&who),
+                Error::<T>::AlreadySubIdentity
+            );
+
+            let info = IdentityInfo {
+                registered_at: frame_system::Pallet::<T>::block_number(),
+                fields: IdentityFields::default(),
+            };
+            Identities::<T>::insert(&who, info);
+
             // Emit event
             Self::deposit_event(Event::IdentityRegistered { who });
 
             Ok(())
         }
+
+        /// Issue a credential to `subject`, replacing any earlier credential from the caller.
+        ///
+        /// Both the issuer and the subject must have a registered identity.
+        #[pallet::call_index(1)]
+        #[pallet::weight(Weight::from_parts(10_000, 0))]
+        pub fn issue_credential(
+            origin: OriginFor<T>,
+            subject: T::AccountId,
+            claim_hash: T::Hash,
+            expires_at: Option<BlockNumberFor<T>>,
+        ) -> DispatchResult {
+            let issuer = ensure_signed(origin)?;
+
+            ensure!(
+                Identities::<T>::contains_key(&issuer),
+                Error::<T>::IdentityNotFound
+            );
+            ensure!(
+                Identities::<T>::contains_key(&subject),
+                Error::<T>::SubjectNotRegistered
+            );
+
+            let now = frame_system::Pallet::<T>::block_number();
+            if let Some(expiry) = expires_at {
+                ensure!(expiry > now, Error::<T>::InvalidExpiry);
+            }
+
+            let credential = Credential {
+                claim_hash,
+                issued_at: now,
+                expires_at,
+                revoked: false,
+            };
+            Credentials::<T>::insert(&subject, &issuer, credential);
+
+            Self::deposit_event(Event::CredentialIssued { subject, issuer });
+
+            Ok(())
+        }
+
+        /// Revoke a credential the caller previously issued to `subject`.
+        ///
+        /// The revoked credential is kept in storage so verifiers can see the revocation.
+        #[pallet::call_index(2)]
+        #[pallet::weight(Weight::from_parts(10_000, 0))]
+        pub fn revoke_credential(origin: OriginFor<T>, subject: T::AccountId) -> DispatchResult {
+            let issuer = ensure_signed(origin)?;
+
+            Credentials::<T>::try_mutate(&subject, &issuer, |maybe_credential| {
+                let credential = maybe_credential
+                    .as_mut()
+                    .ok_or(Error::<T>::CredentialNotFound)?;
+                ensure!(!credential.revoked, Error::<T>::CredentialAlreadyRevoked);
+                credential.revoked = true;
+                Ok::<_, DispatchError>(())
+            })?;
+
+            Self::deposit_event(Event::CredentialRevoked { subject, issuer });
+
+            Ok(())
+        }
+
+        /// Register a top-level name such as `alice.navi` for `periods` renewal periods.
+        ///
+        /// The caller must have a registered identity and pays [`Config::NameFee`] per
+        /// period. Expired names can be registered again, which removes their old subnames.
+        #[pallet::call_index(3)]
+        #[pallet::weight(Weight::from_parts(10_000, 0))]
+        pub fn register_name(origin: OriginFor<T>, name: Vec<u8>, periods: u32) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            ensure!(
+                Identities::<T>::contains_key(&who),
+                Error::<T>::IdentityNotFound
+            );
+            let name = Self::parse_top_level_name(name)?;
+
+            let now = frame_system::Pallet::<T>::block_number();
+            if let Some(existing) = Names::<T>::get(&name) {
+                ensure!(
+                    existing.expires_at.is_some_and(|expiry| expiry <= now),
+                    Error::<T>::NameTaken
+                );
+                for subname in Subnames::<T>::take(&name) {
+                    Names::<T>::remove(subname);
+                }
+            }
+
+            Self::charge_name_fee(&who, periods)?;
+            let expires_at = now.saturating_add(Self::renewal_duration(periods));
+
+            Names::<T>::insert(
+                &name,
+                NameRecord {
+                    owner: who.clone(),
+                    parent: None,
+                    expires_at: Some(expires_at),
+                },
+            );
+
+            Self::deposit_event(Event::NameRegistered {
+                name,
+                owner: who,
+                expires_at,
+            });
+
+            Ok(())
+        }
+
+        /// Extend a top-level name that has not yet expired by `periods` renewal periods.
+        ///
+        /// Anyone may pay to renew a name.
+        #[pallet::call_index(4)]
+        #[pallet::weight(Weight::from_parts(10_000, 0))]
+        pub fn renew_name(origin: OriginFor<T>, name: Vec<u8>, periods: u32) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let name = Self::parse_top_level_name(name)?;
+            let now = frame_system::Pallet::<T>::block_number();
+
+            let expires_at = Names::<T>::try_mutate(&name, |maybe_record| {
+                let record = maybe_record.as_mut().ok_or(Error::<T>::NameNotFound)?;
+                let current = record.expires_at.ok_or(Error::<T>::InvalidName)?;
+                ensure!(current > now, Error::<T>::NameExpired);
+
+                Self::charge_name_fee(&who, periods)?;
+                let expires_at = current.saturating_add(Self::renewal_duration(periods));
+                record.expires_at = Some(expires_at);
+                Ok::<_, DispatchError>(expires_at)
+            })?;
+
+            Self::deposit_event(Event::NameRenewed { name, expires_at });
+
+            Ok(())
+        }
+
+        /// Point `label.parent` at `target`, creating the subname if needed.
+        ///
+        /// Only the owner of an unexpired parent name may manage its subnames.
+        #[pallet::call_index(5)]
+        #[pallet::weight(Weight::from_parts(10_000, 0))]
+        pub fn set_subname(
+            origin: OriginFor<T>,
+            parent: Vec<u8>,
+            label: Vec<u8>,
+            target: T::AccountId,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let parent = Self::ensure_name_owner(&who, parent)?;
+            let name = Self::subname_of(&parent, &label)?;
+
+            if !Names::<T>::contains_key(&name) {
+                Subnames::<T>::try_mutate(&parent, |subnames| {
+                    subnames
+                        .try_push(name.clone())
+                        .map_err(|_| Error::<T>::TooManySubnames)
+                })?;
+            }
+            Names::<T>::insert(
+                &name,
+                NameRecord {
+                    owner: target.clone(),
+                    parent: Some(parent),
+                    expires_at: None,
+                },
+            );
+
+            Self::deposit_event(Event::SubnameSet { name, target });
+
+            Ok(())
+        }
+
+        /// Remove the subname `label.parent`.
+        #[pallet::call_index(6)]
+        #[pallet::weight(Weight::from_parts(10_000, 0))]
+        pub fn remove_subname(
+            origin: OriginFor<T>,
+            parent: Vec<u8>,
+            label: Vec<u8>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let parent = Self::ensure_name_owner(&who, parent)?;
+            let name = Self::subname_of(&parent, &label)?;
+
+            ensure!(Names::<T>::contains_key(&name), Error::<T>::NameNotFound);
+            Names::<T>::remove(&name);
+            Subnames::<T>::mutate(&parent, |subnames| subnames.retain(|n| n != &name));
+
+            Self::deposit_event(Event::SubnameRemoved { name });
+
+            Ok(())
+        }
+
+        /// Choose the name returned by reverse lookup for the caller.
+        ///
+        /// The name must currently resolve to the caller.
+        #[pallet::call_index(7)]
+        #[pallet::weight(Weight::from_parts(10_000, 0))]
+        pub fn set_primary_name(origin: OriginFor<T>, name: Vec<u8>) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            ensure!(
+                Self::resolve_name(&name).as_ref() == Some(&who),
+                Error::<T>::NameNotResolvedToCaller
+            );
+            let name: Name = name.try_into().map_err(|_| Error::<T>::InvalidName)?;
+            PrimaryNames::<T>::insert(&who, &name);
+
+            Self::deposit_event(Event::PrimaryNameSet { who, name });
+
+            Ok(())
+        }
+
+        /// Replace the caller's identity fields.
+        ///
+        /// Judgements from registrars covering a changed field are removed, except for
+        /// pending requests, which the registrar still has to answer, and `Erroneous`
+        /// judgements, which are sticky.
+        #[pallet::call_index(8)]
+        #[pallet::weight(Weight::from_parts(10_000, 0))]
+        pub fn set_identity_fields(origin: OriginFor<T>, fields: IdentityFields) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let changed = Identities::<T>::try_mutate(&who, |maybe_info| {
+                let info = maybe_info.as_mut().ok_or(Error::<T>::IdentityNotFound)?;
+                let changed = info.fields.changed_fields(&fields);
+                info.fields = fields;
+                Ok::<_, DispatchError>(changed)
+            })?;
+
+            if changed != 0 {
+                let registrars = Registrars::<T>::get();
+                let invalidated: Vec<u32> = Judgements::<T>::iter_prefix(&who)
+                    .filter(|(index, judgement)| {
+                        !judgement.is_sticky()
+                            && registrars
+                                .get(*index as usize)
+                                .cloned()
+                                .flatten()
+                                .map_or(true, |registrar| registrar.fields & changed != 0)
+                    })
+                    .map(|(index, _)| index)
+                    .collect();
+                for registrar in invalidated {
+                    Judgements::<T>::remove(&who, registrar);
+                    Self::deposit_event(Event::JudgementInvalidated {
+                        who: who.clone(),
+                        registrar,
+                    });
+                }
+            }
+
+            Self::deposit_event(Event::IdentityFieldsSet { who });
+
+            Ok(())
+        }
+
+        /// Add a registrar that verifies `fields`.
+        ///
+        /// Origin must be [`Config::RegistrarOrigin`].
+        #[pallet::call_index(9)]
+        #[pallet::weight(Weight::from_parts(10_000, 0))]
+        pub fn add_registrar(
+            origin: OriginFor<T>,
+            account: T::AccountId,
+            fields: FieldMask,
+        ) -> DispatchResult {
+            T::RegistrarOrigin::ensure_origin(origin)?;
+
+            let index = Registrars::<T>::try_mutate(|registrars| {
+                let index = registrars.len() as u32;
+                registrars
+                    .try_push(Some(RegistrarInfo {
+                        account: account.clone(),
+                        fee: Zero::zero(),
+                        fields,
+                    }))
+                    .map_err(|_| Error::<T>::TooManyRegistrars)?;
+                Ok::<_, DispatchError>(index)
+            })?;
+
+            Self::deposit_event(Event::RegistrarAdded { index, account });
+
+            Ok(())
+        }
+
+        /// Remove the registrar at `index`.
+        ///
+        /// Origin must be [`Config::RegistrarOrigin`]. Existing judgements are kept.
+        #[pallet::call_index(10)]
+        #[pallet::weight(Weight::from_parts(10_000, 0))]
+        pub fn remove_registrar(origin: OriginFor<T>, index: u32) -> DispatchResult {
+            T::RegistrarOrigin::ensure_origin(origin)?;
+
+            Registrars::<T>::try_mutate(|registrars| {
+                let slot = registrars
+                    .get_mut(index as usize)
+                    .filter(|slot| slot.is_some())
+                    .ok_or(Error::<T>::RegistrarNotFound)?;
+                *slot = None;
+                Ok::<_, DispatchError>(())
+            })?;
+
+            Self::deposit_event(Event::RegistrarRemoved { index });
+
+            Ok(())
+        }
+
+        /// Set the fee the caller charges as the registrar at `index`.
+        #[pallet::call_index(11)]
+        #[pallet::weight(Weight::from_parts(10_000, 0))]
+        pub fn set_registrar_fee(
+            origin: OriginFor<T>,
+            index: u32,
+            fee: BalanceOf<T>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            Registrars::<T>::try_mutate(|registrars| {
+                let registrar = registrars
+                    .get_mut(index as usize)
+                    .and_then(Option::as_mut)
+                    .ok_or(Error::<T>::RegistrarNotFound)?;
+                ensure!(registrar.account == who, Error::<T>::NotRegistrar);
+                registrar.fee = fee;
+                Ok::<_, DispatchError>(())
+            })?;
+
+            Self::deposit_event(Event::RegistrarFeeSet { index, fee });
+
+            Ok(())
+        }
+
+        /// Ask the registrar at `index` to judge the caller's identity.
+        ///
+        /// The registrar's fee, which must not exceed `max_fee`, is held until the registrar
+        /// responds or the request is cancelled.
+        #[pallet::call_index(12)]
+        #[pallet::weight(Weight::from_parts(10_000, 0))]
+        pub fn request_judgement(
+            origin: OriginFor<T>,
+            index: u32,
+            max_fee: BalanceOf<T>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            ensure!(
+                Identities::<T>::contains_key(&who),
+                Error::<T>::IdentityNotFound
+            );
+            let registrar = Self::registrar(index)?;
+            ensure!(registrar.fee <= max_fee, Error::<T>::FeeTooHigh);
+
+            match Judgements::<T>::get(&who, index) {
+                Some(Judgement::FeePaid(_)) => return Err(Error::<T>::JudgementPending.into()),
+                Some(Judgement::Erroneous) => return Err(Error::<T>::StickyJudgement.into()),
+                _ => {}
+            }
+
+            if !registrar.fee.is_zero() {
+                T::Currency::hold(&HoldReason::JudgementFee.into(), &who, registrar.fee)?;
+            }
+            Judgements::<T>::insert(&who, index, Judgement::FeePaid(registrar.fee));
+
+            Self::deposit_event(Event::JudgementRequested {
+                who,
+                registrar: index,
+            });
+
+            Ok(())
+        }
+
+        /// Cancel a pending judgement request and release its fee.
+        #[pallet::call_index(13)]
+        #[pallet::weight(Weight::from_parts(10_000, 0))]
+        pub fn cancel_judgement_request(origin: OriginFor<T>, index: u32) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let Some(Judgement::FeePaid(fee)) = Judgements::<T>::get(&who, index) else {
+                return Err(Error::<T>::JudgementNotPending.into());
+            };
+
+            T::Currency::release(
+                &HoldReason::JudgementFee.into(),
+                &who,
+                fee,
+                Precision::BestEffort,
+            )?;
+            Judgements::<T>::remove(&who, index);
+
+            Self::deposit_event(Event::JudgementCancelled {
+                who,
+                registrar: index,
+            });
+
+            Ok(())
+        }
+
+        /// Judge the identity of `target` as the registrar at `index`.
+        ///
+        /// `fields_hash` must match [`Pallet::fields_hash`] for the fields the registrar
+        /// covers, so a judgement cannot apply to fields changed after inspection. `target`
+        /// must have requested a judgement from the registrar, or hold one to be revised.
+        /// Any fee held for a pending request is paid to the registrar.
+        #[pallet::call_index(14)]
+        #[pallet::weight(Weight::from_parts(10_000, 0))]
+        pub fn provide_judgement(
+            origin: OriginFor<T>,
+            index: u32,
+            target: T::AccountId,
+            judgement: JudgementOf<T>,
+            fields_hash: T::Hash,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let registrar = Self::registrar(index)?;
+            ensure!(registrar.account == who, Error::<T>::NotRegistrar);
+            ensure!(!judgement.is_pending(), Error::<T>::InvalidJudgement);
+
+            let info = Identities::<T>::get(&target).ok_or(Error::<T>::IdentityNotFound)?;
+            ensure!(
+                Self::fields_hash(&info.fields, registrar.fields) == fields_hash,
+                Error::<T>::FieldsHashMismatch
+            );
+
+            let current =
+                Judgements::<T>::get(&target, index).ok_or(Error::<T>::JudgementNotRequested)?;
+            match current {
+                Judgement::FeePaid(fee) if !fee.is_zero() => {
+                    T::Currency::transfer_on_hold(
+                        &HoldReason::JudgementFee.into(),
+                        &target,
+                        &who,
+                        fee,
+                        Precision::BestEffort,
+                        Restriction::Free,
+                        Fortitude::Polite,
+                    )?;
+                }
+                _ => {}
+            }
+            Judgements::<T>::insert(&target, index, judgement);
+
+            Self::deposit_event(Event::JudgementGiven {
+                who: target,
+                registrar: index,
+                judgement,
+            });
+
+            Ok(())
+        }
+
+        /// Attach `sub` to the caller's NaviID with a label and permissions.
+        ///
+        /// [`Config::SubIdentityDeposit`] is held from the caller. The sub-account must not
+        /// have its own NaviID or belong to another parent.
+        #[pallet::call_index(15)]
+        #[pallet::weight(Weight::from_parts(10_000, 0))]
+        pub fn add_sub_identity(
+            origin: OriginFor<T>,
+            sub: T::AccountId,
+            label: Vec<u8>,
+            permissions: SubPermissions,
+        ) -> DispatchResult {
+            let parent = ensure_signed(origin)?;
+
+            ensure!(
+                Identities::<T>::contains_key(&parent),
+                Error::<T>::IdentityNotFound
+            );
+            ensure!(
+                !Identities::<T>::contains_key(&sub),
+                Error::<T>::AlreadyRegistered
+            );
+            ensure!(
+                !SuperOf::<T>::contains_key(&sub),
+                Error::<T>::AlreadySubIdentity
+            );
+            let label = label.try_into().map_err(|_| Error::<T>::LabelTooLong)?;
+
+            SubsOf::<T>::try_mutate(&parent, |subs| {
+                subs.try_push(sub.clone())
+                    .map_err(|_| Error::<T>::TooManySubIdentities)
+            })?;
+
+            let deposit = T::SubIdentityDeposit::get();
+            T::Currency::hold(&HoldReason::SubIdentityDeposit.into(), &parent, deposit)?;
+            let info = SubIdentityInfo {
+                label,
+                permissions,
+                deposit,
+            };
+            SuperOf::<T>::insert(&sub, (parent.clone(), info));
+
+            Self::deposit_event(Event::SubIdentityAdded { parent, sub });
+
+            Ok(())
+        }
+
+        /// Change the label and permissions of one of the caller's sub-identities.
+        #[pallet::call_index(16)]
+        #[pallet::weight(Weight::from_parts(10_000, 0))]
+        pub fn set_sub_identity(
+            origin: OriginFor<T>,
+            sub: T::AccountId,
+            label: Vec<u8>,
+            permissions: SubPermissions,
+        ) -> DispatchResult {
+            let parent = ensure_signed(origin)?;
+
+            let label: BoundedVec<u8, ConstU32<32>> =
+                label.try_into().map_err(|_| Error::<T>::LabelTooLong)?;
+            SuperOf::<T>::try_mutate(&sub, |maybe_sub| match maybe_sub {
+                Some((owner, info)) if *owner == parent => {
+                    info.label = label;
+                    info.permissions = permissions;
+                    Ok(())
+                }
+                _ => Err(Error::<T>::NotSubIdentity),
+            })?;
+
+            Self::deposit_event(Event::SubIdentityUpdated { parent, sub });
+
+            Ok(())
+        }
+
+        /// Detach one of the caller's sub-identities and release its deposit.
+        #[pallet::call_index(17)]
+        #[pallet::weight(Weight::from_parts(10_000, 0))]
+        pub fn remove_sub_identity(origin: OriginFor<T>, sub: T::AccountId) -> DispatchResult {
+            let parent = ensure_signed(origin)?;
+
+            let (owner, _) = SuperOf::<T>::get(&sub).ok_or(Error::<T>::NotSubIdentity)?;
+            ensure!(owner == parent, Error::<T>::NotSubIdentity);
+
+            Self::do_remove_sub(&sub)
+        }
+
+        /// Detach the caller from its parent NaviID.
+        ///
+        /// The deposit is released back to the parent.
+        #[pallet::call_index(18)]
+        #[pallet::weight(Weight::from_parts(10_000, 0))]
+        pub fn quit_sub_identity(origin: OriginFor<T>) -> DispatchResult {
+            let sub = ensure_signed(origin)?;
+
+            ensure!(SuperOf::<T>::contains_key(&sub), Error::<T>::NotSubIdentity);
+
+            Self::do_remove_sub(&sub)
+        }
+
+        /// Add a key that may sign login challenges for the caller's NaviID.
+        ///
+        /// Signed challenges are checked by the `navi-id-verifier` crate and the
+        /// `naviId_verifySignIn` RPC.
+        #[pallet::call_index(19)]
+        #[pallet::weight(Weight::from_parts(10_000, 0))]
+        pub fn add_auth_key(origin: OriginFor<T>, key: AuthKey) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            ensure!(
+                Identities::<T>::contains_key(&who),
+                Error::<T>::IdentityNotFound
+            );
+
+            AuthKeys::<T>::try_mutate(&who, |keys| {
+                ensure!(!keys.contains(&key), Error::<T>::AuthKeyExists);
+                keys.try_push(key).map_err(|_| Error::<T>::TooManyAuthKeys)
+            })?;
+
+            Self::deposit_event(Event::AuthKeyAdded { who, key });
+
+            Ok(())
+        }
+
+        /// Remove one of the caller's authentication keys.
+        ///
+        /// Challenges signed by the key stop verifying from the next block.
+        #[pallet::call_index(20)]
+        #[pallet::weight(Weight::from_parts(10_000, 0))]
+        pub fn remove_auth_key(origin: OriginFor<T>, key: AuthKey) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            AuthKeys::<T>::try_mutate_exists(&who, |maybe_keys| {
+                let keys = maybe_keys.as_mut().ok_or(Error::<T>::AuthKeyNotFound)?;
+                let index = keys
+                    .iter()
+                    .position(|k| *k == key)
+                    .ok_or(Error::<T>::AuthKeyNotFound)?;
+                keys.remove(index);
+                if keys.is_empty() {
+                    *maybe_keys = None;
+                }
+                Ok::<_, DispatchError>(())
+            })?;
+
+            Self::deposit_event(Event::AuthKeyRemoved { who, key });
+
+            Ok(())
+        }
+
+        /// Vouch for the personhood of `vouchee`, holding [`Config::VouchStake`] from the caller.
+        ///
+        /// The caller must be a person. The stake is slashed if `vouchee` is later declared a
+        /// duplicate while the vouch is active. At most [`Config::MaxVouches`] vouches, including
+        /// expired ones not yet pruned, may exist for `vouchee`.
+        #[pallet::call_index(21)]
+        #[pallet::weight(Weight::from_parts(10_000, 0))]
+        pub fn vouch(origin: OriginFor<T>, vouchee: T::AccountId) -> DispatchResult {
+            let voucher = ensure_signed(origin)?;
+
+            ensure!(voucher != vouchee, Error::<T>::CannotVouchForSelf);
+            ensure!(Self::is_person(&voucher), Error::<T>::NotAPerson);
+            ensure!(
+                Identities::<T>::contains_key(&vouchee),
+                Error::<T>::IdentityNotFound
+            );
+            ensure!(
+                !Duplicates::<T>::contains_key(&vouchee),
+                Error::<T>::DuplicateIdentity
+            );
+            ensure!(
+                !Vouches::<T>::contains_key(&vouchee, &voucher),
+                Error::<T>::AlreadyVouched
+            );
+            VouchCount::<T>::try_mutate(&vouchee, |count| {
+                ensure!(*count < T::MaxVouches::get(), Error::<T>::TooManyVouches);
+                *count += 1;
+                Ok::<_, DispatchError>(())
+            })?;
+
+            let stake = T::VouchStake::get();
+            T::Currency::hold(&HoldReason::VouchStake.into(), &voucher, stake)?;
+            let expires_at =
+                frame_system::Pallet::<T>::block_number().saturating_add(T::VouchDuration::get());
+            Vouches::<T>::insert(&vouchee, &voucher, Vouch { stake, expires_at });
+
+            Self::deposit_event(Event::Vouched {
+                voucher,
+                vouchee,
+                expires_at,
+            });
+
+            Ok(())
+        }
+
+        /// Withdraw a vouch and release its stake.
+        #[pallet::call_index(22)]
+        #[pallet::weight(Weight::from_parts(10_000, 0))]
+        pub fn unvouch(origin: OriginFor<T>, vouchee: T::AccountId) -> DispatchResult {
+            let voucher = ensure_signed(origin)?;
+
+            let vouch = Vouches::<T>::get(&vouchee, &voucher).ok_or(Error::<T>::VouchNotFound)?;
+            Self::remove_vouch(&vouchee, &voucher, vouch)?;
+
+            Self::deposit_event(Event::Unvouched { voucher, vouchee });
+
+            Ok(())
+        }
+
+        /// Declare `who` a duplicate of another identity.
+        ///
+        /// Vouchers with an active vouch for `who` are slashed; expired vouches are released.
+        /// The duplicate's personhood score drops to zero for good.
+        #[pallet::call_index(23)]
+        #[pallet::weight(Weight::from_parts(10_000, 0))]
+        pub fn report_duplicate(origin: OriginFor<T>, who: T::AccountId) -> DispatchResult {
+            T::DuplicateOrigin::ensure_origin(origin)?;
+
+            ensure!(
+                Identities::<T>::contains_key(&who),
+                Error::<T>::IdentityNotFound
+            );
+            ensure!(
+                !Duplicates::<T>::contains_key(&who),
+                Error::<T>::DuplicateIdentity
+            );
+
+            let now = frame_system::Pallet::<T>::block_number();
+            Duplicates::<T>::insert(&who, now);
+            VouchCount::<T>::remove(&who);
+            for (voucher, vouch) in Vouches::<T>::drain_prefix(&who) {
+                let reason = HoldReason::VouchStake.into();
+                if now < vouch.expires_at {
+                    let (credit, _) = T::Currency::slash(&reason, &voucher, vouch.stake);
+                    let amount = credit.peek();
+                    T::VouchSlashHandler::on_unbalanced(credit);
+                    Self::deposit_event(Event::VoucherSlashed {
+                        voucher,
+                        vouchee: who.clone(),
+                        amount,
+                    });
+                } else {
+                    T::Currency::release(&reason, &voucher, vouch.stake, Precision::BestEffort)?;
+                }
+            }
+
+            Self::deposit_event(Event::DuplicateReported { who });
+
+            Ok(())
+        }
+
+        /// Publish a salted hash commitment to a private attribute, replacing any earlier one.
+        ///
+        /// See [`attribute_commitment`] for how the commitment is computed.
+        #[pallet::call_index(24)]
+        #[pallet::weight(Weight::from_parts(10_000, 0))]
+        pub fn set_commitment(
+            origin: OriginFor<T>,
+            key: Vec<u8>,
+            commitment: [u8; 32],
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            ensure!(
+                Identities::<T>::contains_key(&who),
+                Error::<T>::IdentityNotFound
+            );
+            ensure!(!key.is_empty(), Error::<T>::InvalidAttributeKey);
+            let key: AttributeKey = key
+                .try_into()
+                .map_err(|_| Error::<T>::InvalidAttributeKey)?;
+
+            Commitments::<T>::try_mutate(&who, |commitments| {
+                match commitments.iter_mut().find(|(k, _)| *k == key) {
+                    Some((_, existing)) => *existing = commitment,
+                    None => commitments
+                        .try_push((key.clone(), commitment))
+                        .map_err(|_| Error::<T>::TooManyCommitments)?,
+                }
+                Ok::<_, DispatchError>(())
+            })?;
+
+            Self::deposit_event(Event::CommitmentSet { who, key });
+
+            Ok(())
+        }
+
+        /// Remove the commitment to a private attribute.
+        #[pallet::call_index(25)]
+        #[pallet::weight(Weight::from_parts(10_000, 0))]
+        pub fn clear_commitment(origin: OriginFor<T>, key: Vec<u8>) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let key: AttributeKey = key.try_into().map_err(|_| Error::<T>::CommitmentNotFound)?;
+            Commitments::<T>::try_mutate_exists(&who, |maybe_commitments| {
+                let commitments = maybe_commitments
+                    .as_mut()
+                    .ok_or(Error::<T>::CommitmentNotFound)?;
+                let index = commitments
+                    .iter()
+                    .position(|(k, _)| *k == key)
+                    .ok_or(Error::<T>::CommitmentNotFound)?;
+                commitments.remove(index);
+                if commitments.is_empty() {
+                    *maybe_commitments = None;
+                }
+                Ok::<_, DispatchError>(())
+            })?;
+
+            Self::deposit_event(Event::CommitmentCleared { who, key });
+
+            Ok(())
+        }
+
+        /// Remove an expired vouch and release its stake to the voucher.
+        ///
+        /// Anyone can prune expired vouches, so they stop counting towards
+        /// [`Config::MaxVouches`] for the vouchee.
+        #[pallet::call_index(26)]
+        #[pallet::weight(Weight::from_parts(10_000, 0))]
+        pub fn prune_vouch(
+            origin: OriginFor<T>,
+            vouchee: T::AccountId,
+            voucher: T::AccountId,
+        ) -> DispatchResult {
+            ensure_signed(origin)?;
+
+            let vouch = Vouches::<T>::get(&vouchee, &voucher).ok_or(Error::<T>::VouchNotFound)?;
+            ensure!(
+                frame_system::Pallet::<T>::block_number() >= vouch.expires_at,
+                Error::<T>::VouchNotExpired
+            );
+            Self::remove_vouch(&vouchee, &voucher, vouch)?;
+
+            Self::deposit_event(Event::VouchPruned { voucher, vouchee });
+
+            Ok(())
+        }
+    }
+
+    impl<T: Config> Pallet<T> {
+        /// The NaviID `who` acts for: itself if registered, or the parent of a sub-identity.
+        pub fn identity_of(who: &T::AccountId) -> Option<T::AccountId> {
+            if Identities::<T>::contains_key(who) {
+                Some(who.clone())
+            } else {
+                SuperOf::<T>::get(who).map(|(parent, _)| parent)
+            }
+        }
+
+        /// The keys currently allowed to sign login challenges for the NaviID `who`.
+        pub fn auth_keys(who: &T::AccountId) -> Vec<AuthKey> {
+            if !Identities::<T>::contains_key(who) {
+                return Vec::new();
+            }
+            AuthKeys::<T>::get(who).into_inner()
+        }
+
+        /// The personhood score of `who`.
+        ///
+        /// Each active vouch adds one; a positive registrar judgement adds
+        /// [`Config::PersonhoodThreshold`]. Unknown and duplicate identities score zero.
+        pub fn personhood_score(who: &T::AccountId) -> u32 {
+            if !Identities::<T>::contains_key(who) || Duplicates::<T>::contains_key(who) {
+                return 0;
+            }
+            let now = frame_system::Pallet::<T>::block_number();
+            let vouches = Vouches::<T>::iter_prefix_values(who)
+                .filter(|vouch| now < vouch.expires_at)
+                .count() as u32;
+            let judged = Judgements::<T>::iter_prefix_values(who).any(|j| j.is_positive());
+            if judged {
+                vouches.saturating_add(T::PersonhoodThreshold::get())
+            } else {
+                vouches
+            }
+        }
+
+        /// Whether `who` reaches the personhood threshold.
+        pub fn is_person(who: &T::AccountId) -> bool {
+            Self::personhood_score(who) >= T::PersonhoodThreshold::get()
+        }
+
+        /// Remove the vouch of `voucher` for `vouchee` and release its stake.
+        fn remove_vouch(
+            vouchee: &T::AccountId,
+            voucher: &T::AccountId,
+            vouch: Vouch<T>,
+        ) -> DispatchResult {
+            Vouches::<T>::remove(vouchee, voucher);
+            VouchCount::<T>::mutate(vouchee, |count| *count = count.saturating_sub(1));
+            T::Currency::release(
+                &HoldReason::VouchStake.into(),
+                voucher,
+                vouch.stake,
+                Precision::BestEffort,
+            )?;
+            Ok(())
+        }
+
+        /// The private attribute commitments of `who`.
+        pub fn commitments(who: &T::AccountId) -> Vec<(Vec<u8>, [u8; 32])> {
+            Commitments::<T>::get(who)
+                .into_iter()
+                .map(|(key, commitment)| (key.into_inner(), commitment))
+                .collect()
+        }
+
+        /// The reputation of `who` in network `network_id`.
+        pub fn reputation(who: &T::AccountId, network_id: u64) -> Option<ReputationView> {
+            Reputations::<T>::get(who, network_id).map(|record| Self::reputation_view(&record))
+        }
+
+        /// The reputation of `who` in every network it has activity in.
+        pub fn reputations(who: &T::AccountId) -> Vec<(u64, ReputationView)> {
+            Reputations::<T>::iter_prefix(who)
+                .map(|(network_id, record)| (network_id, Self::reputation_view(&record)))
+                .collect()
+        }
+
+        fn reputation_view(record: &Reputation<BlockNumberFor<T>>) -> ReputationView {
+            ReputationView {
+                memberships_completed: record.memberships_completed,
+                ppu_volume: record.ppu_volume,
+                slashes: record.slashes,
+                bans: record.bans,
+                score: Self::decayed_score(record.score, record.updated_at),
+            }
+        }
+
+        /// `score` as of `updated_at`, halved for every half-life elapsed since.
+        fn decayed_score(score: i64, updated_at: BlockNumberFor<T>) -> i64 {
+            let half_life = T::ReputationHalfLife::get();
+            if half_life.is_zero() {
+                return score;
+            }
+            let elapsed = frame_system::Pallet::<T>::block_number().saturating_sub(updated_at);
+            let halvings: u32 = (elapsed / half_life).unique_saturated_into();
+            if halvings >= 63 {
+                0
+            } else {
+                score / (1 << halvings)
+            }
+        }
+
+        /// Whether `who` is a sub-identity granted every permission in `permissions`.
+        pub fn sub_has_permissions(who: &T::AccountId, permissions: SubPermissions) -> bool {
+            SuperOf::<T>::get(who)
+                .is_some_and(|(_, info)| info.permissions & permissions == permissions)
+        }
+
+        /// Detach `sub` from its parent and release the parent's deposit.
+        fn do_remove_sub(sub: &T::AccountId) -> DispatchResult {
+            let (parent, info) = SuperOf::<T>::take(sub).ok_or(Error::<T>::NotSubIdentity)?;
+            SubsOf::<T>::mutate(&parent, |subs| subs.retain(|s| s != sub));
+            T::Currency::release(
+                &HoldReason::SubIdentityDeposit.into(),
+                &parent,
+                info.deposit,
+                Precision::BestEffort,
+            )?;
+
+            Self::deposit_event(Event::SubIdentityRemoved {
+                parent,
+                sub: sub.clone(),
+            });
+
+            Ok(())
+        }
+
+        /// The hash a registrar covering `mask` must sign off on for `fields`.
+        pub fn fields_hash(fields: &IdentityFields, mask: FieldMask) -> T::Hash {
+            T::Hashing::hash_of(&fields.masked(mask))
+        }
+
+        /// The registrar at `index`.
+        fn registrar(index: u32) -> Result<RegistrarInfoOf<T>, DispatchError> {
+            Registrars::<T>::get()
+                .get(index as usize)
+                .cloned()
+                .flatten()
+                .ok_or(Error::<T>::RegistrarNotFound.into())
+        }
+
+        /// Resolve a full name to the account it points to.
+        ///
+        /// Returns `None` for unknown names and names whose (parent) registration has expired.
+        pub fn resolve_name(name: &[u8]) -> Option<T::AccountId> {
+            let name = Name::try_from(name.to_vec()).ok()?;
+            let record = Names::<T>::get(&name)?;
+            let expires_at = match &record.parent {
+                Some(parent) => Names::<T>::get(parent)?.expires_at?,
+                None => record.expires_at?,
+            };
+            (frame_system::Pallet::<T>::block_number() < expires_at).then_some(record.owner)
+        }
+
+        /// Reverse lookup: the primary name of `who`, if it still resolves to them.
+        pub fn primary_name(who: &T::AccountId) -> Option<Vec<u8>> {
+            let name = PrimaryNames::<T>::get(who)?;
+            (Self::resolve_name(&name).as_ref() == Some(who)).then(|| name.into_inner())
+        }
+
+        /// Validate a top-level name of the form `label.navi`.
+        fn parse_top_level_name(name: Vec<u8>) -> Result<Name, DispatchError> {
+            let label = name
+                .strip_suffix(NAME_SUFFIX)
+                .ok_or(Error::<T>::InvalidName)?;
+            ensure!(is_valid_label(label), Error::<T>::InvalidName);
+            name.try_into().map_err(|_| Error::<T>::InvalidName.into())
+        }
+
+        /// Build the full subname `label.parent`.
+        fn subname_of(parent: &Name, label: &[u8]) -> Result<Name, DispatchError> {
+            ensure!(is_valid_label(label), Error::<T>::InvalidName);
+            let mut name = label.to_vec();
+            name.push(b'.');
+            name.extend_from_slice(parent);
+            name.try_into().map_err(|_| Error::<T>::InvalidName.into())
+        }
+
+        /// Ensure `who` owns the unexpired top-level name `name`.
+        fn ensure_name_owner(who: &T::AccountId, name: Vec<u8>) -> Result<Name, DispatchError> {
+            let name = Self::parse_top_level_name(name)?;
+            let record = Names::<T>::get(&name).ok_or(Error::<T>::NameNotFound)?;
+            ensure!(record.owner == *who, Error::<T>::NotNameOwner);
+            let now = frame_system::Pallet::<T>::block_number();
+            ensure!(
+                record.expires_at.is_some_and(|expiry| expiry > now),
+                Error::<T>::NameExpired
+            );
+            Ok(name)
+        }
+
+        /// The number of blocks covered by `periods` renewal periods.
+        fn renewal_duration(periods: u32) -> BlockNumberFor<T> {
+            T::NameRenewalPeriod::get().saturating_mul(periods.into())
+        }
+
+        /// Withdraw the fee for `periods` renewal periods from `who`.
+        fn charge_name_fee(who: &T::AccountId, periods: u32) -> DispatchResult {
+            ensure!(
+                periods > 0 && periods <= T::MaxRenewalPeriods::get(),
+                Error::<T>::InvalidPeriods
+            );
+            let fee = T::NameFee::get().saturating_mul(periods.into());
+            if !fee.is_zero() {
+                let credit = T::Currency::withdraw(
+                    who,
+                    fee,
+                    Precision::Exact,
+                    Preservation::Preserve,
+                    Fortitude::Polite,
+                )?;
+                T::NameFeeHandler::on_unbalanced(credit);
+            }
+            Ok(())
+        }
+    }
+
+    impl<T: Config> IdentityInspect<T::AccountId> for Pallet<T> {
+        fn has_identity(who: &T::AccountId) -> bool {
+            Identities::<T>::contains_key(who)
+        }
+
+        fn has_credential(who: &T::AccountId, issuer: &T::AccountId) -> bool {
+            let now = frame_system::Pallet::<T>::block_number();
+            Credentials::<T>::get(who, issuer).is_some_and(|credential| {
+                !credential.revoked && credential.expires_at.map_or(true, |expiry| now < expiry)
+            })
+        }
+
+        fn personhood_score(who: &T::AccountId) -> u32 {
+            Pallet::<T>::personhood_score(who)
+        }
+    }
+
+    impl<T: Config> ReputationHandler<T::AccountId> for Pallet<T> {
+        /// Activity of a sub-identity counts towards its parent. Accounts without a NaviID are
+        /// ignored.
+        fn report(network_id: u64, who: &T::AccountId, event: ReputationEvent) {
+            let Some(who) = Self::identity_of(who) else {
+                return;
+            };
+            let now = frame_system::Pallet::<T>::block_number();
+            let mut record = Reputations::<T>::get(&who, network_id).unwrap_or(Reputation {
+                memberships_completed: 0,
+                ppu_volume: 0,
+                slashes: 0,
+                bans: 0,
+                score: 0,
+                updated_at: now,
+            });
+
+            let points = match event {
+                ReputationEvent::MembershipCompleted => {
+                    record.memberships_completed.saturating_inc();
+                    MEMBERSHIP_COMPLETED_POINTS
+                }
+                ReputationEvent::PayPerUse(amount) => {
+                    record.ppu_volume.saturating_accrue(amount);
+                    let unit = T::ReputationVolumeUnit::get().max(1);
+                    i64::try_from(amount / unit).unwrap_or(i64::MAX)
+                }
+                ReputationEvent::Slashed => {
+                    record.slashes.saturating_inc();
+                    SLASHED_POINTS
+                }
+                ReputationEvent::Banned => {
+                    record.bans.saturating_inc();
+                    BANNED_POINTS
+                }
+            };
+            record.score =
+                Self::decayed_score(record.score, record.updated_at).saturating_add(points);
+            record.updated_at = now;
+            let score = record.score;
+            Reputations::<T>::insert(&who, network_id, record);
+
+            Self::deposit_event(Event::ReputationUpdated {
+                who,
+                network_id,
+                score,
+            });
+        }
     }
 }
@@ -1,5 +1,6 @@
 use crate as pallet_identity;
-use frame_support::derive_impl;
+use frame_support::{derive_impl, parameter_types, traits::ConstU32};
+use frame_system::EnsureRoot;
 use sp_runtime::BuildStorage;
 
 type Block = frame_system::mocking::MockBlock<Test>;
@@ -7,6 +8,7 @@ type Block = frame_system::mocking::MockBlock<Test>;
 frame_support::construct_runtime!(
     pub enum Test {
         System: frame_system,
+        Balances: pallet_balances,
         Identity: pallet_identity,
     }
 );
@@ -14,15 +16,58 @@ frame_support::construct_runtime!(
 #[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
 impl frame_system::Config for Test {
     type Block = Block;
+    type AccountData = pallet_balances::AccountData<u64>;
+}
+
+#[derive_impl(pallet_balances::config_preludes::TestDefaultConfig)]
+impl pallet_balances::Config for Test {
+    type AccountStore = System;
+}
+
+parameter_types! {
+    pub const NameFee: u64 = 10;
+    pub const NameRenewalPeriod: u64 = 1_000;
+    pub const SubIdentityDeposit: u64 = 5;
+    pub const VouchStake: u64 = 20;
+    pub const VouchDuration: u64 = 100;
+    pub const ReputationHalfLife: u64 = 100;
+    pub const ReputationVolumeUnit: u128 = 10;
 }
 
 impl pallet_identity::Config for Test {
     type RuntimeEvent = RuntimeEvent;
+    type RuntimeHoldReason = RuntimeHoldReason;
+    type Currency = Balances;
+    type NameFeeHandler = ();
+    type NameFee = NameFee;
+    type NameRenewalPeriod = NameRenewalPeriod;
+    type MaxRenewalPeriods = ConstU32<5>;
+    type MaxSubnames = ConstU32<2>;
+    type RegistrarOrigin = EnsureRoot<u64>;
+    type MaxRegistrars = ConstU32<2>;
+    type SubIdentityDeposit = SubIdentityDeposit;
+    type MaxSubIdentities = ConstU32<2>;
+    type MaxAuthKeys = ConstU32<2>;
+    type VouchStake = VouchStake;
+    type VouchDuration = VouchDuration;
+    type MaxVouches = ConstU32<2>;
+    type PersonhoodThreshold = ConstU32<1>;
+    type DuplicateOrigin = EnsureRoot<u64>;
+    type VouchSlashHandler = ();
+    type ReputationHalfLife = ReputationHalfLife;
+    type ReputationVolumeUnit = ReputationVolumeUnit;
+    type MaxCommitments = ConstU32<2>;
 }
 
 pub fn new_test_ext() -> sp_io::TestExternalities {
-    frame_system::GenesisConfig::<Test>::default()
+    let mut t = frame_system::GenesisConfig::<Test>::default()
         .build_storage()
-        .unwrap()
-        .into()
+        .unwrap();
+    pallet_balances::GenesisConfig::<Test> {
+        balances: vec![(1, 1_000), (2, 1_000), (3, 1_000)],
+        ..Default::default()
+    }
+    .assimilate_storage(&mut t)
+    .unwrap();
+    t.into()
 }
@@ -1,5 +1,15 @@
-use crate::{mock::*, Event};
-use frame_support::assert_ok;
+use crate::{
+    attribute_commitment, mock::*, AuthKey, Credentials, Error, Event, IdentityFields,
+    IdentityInspect, Judgement, Judgements, Names, ReputationEvent, ReputationHandler, Subnames,
+    SubsOf, SuperOf, FIELD_DISPLAY, FIELD_EMAIL,
+};
+use frame_support::{assert_noop, assert_ok};
+use sp_core::H256;
+use sp_runtime::BuildStorage;
+
+/// Sub-identity permissions as an app might define them.
+const SIGN_IN: u32 = 1 << 0;
+const ACCESS: u32 = 1 << 1;
 
 #[test]
 fn register_identity_works() {
@@ -14,3 +24,1100 @@ fn register_identity_works() {
         System::assert_last_event(Event::IdentityRegistered { who: 1 }.into());
     });
 }
+
+#[test]
+fn register_identity_twice_fails() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Identity::register_identity(RuntimeOrigin::signed(1)));
+
+        assert_noop!(
+            Identity::register_identity(RuntimeOrigin::signed(1)),
+            Error::<Test>::AlreadyRegistered
+        );
+    });
+}
+
+#[test]
+fn issue_and_revoke_credential_works() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+
+        assert_ok!(Identity::register_identity(RuntimeOrigin::signed(1)));
+        assert_ok!(Identity::register_identity(RuntimeOrigin::signed(2)));
+
+        // Account 1 issues a credential to account 2
+        assert_ok!(Identity::issue_credential(
+            RuntimeOrigin::signed(1),
+            2,
+            H256::repeat_byte(7),
+            None
+        ));
+        assert!(<Identity as IdentityInspect<u64>>::has_credential(&2, &1));
+        System::assert_last_event(
+            Event::CredentialIssued {
+                subject: 2,
+                issuer: 1,
+            }
+            .into(),
+        );
+
+        // Revoking invalidates the credential but keeps the record
+        assert_ok!(Identity::revoke_credential(RuntimeOrigin::signed(1), 2));
+        assert!(!<Identity as IdentityInspect<u64>>::has_credential(&2, &1));
+        assert!(Credentials::<Test>::get(2, 1).unwrap().revoked);
+
+        assert_noop!(
+            Identity::revoke_credential(RuntimeOrigin::signed(1), 2),
+            Error::<Test>::CredentialAlreadyRevoked
+        );
+    });
+}
+
+#[test]
+fn issue_credential_requires_identities() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+
+        // Issuer has no identity
+        assert_noop!(
+            Identity::issue_credential(RuntimeOrigin::signed(1), 2, H256::zero(), None),
+            Error::<Test>::IdentityNotFound
+        );
+
+        // Subject has no identity
+        assert_ok!(Identity::register_identity(RuntimeOrigin::signed(1)));
+        assert_noop!(
+            Identity::issue_credential(RuntimeOrigin::signed(1), 2, H256::zero(), None),
+            Error::<Test>::SubjectNotRegistered
+        );
+    });
+}
+
+#[test]
+fn expired_credential_is_not_valid() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+
+        assert_ok!(Identity::register_identity(RuntimeOrigin::signed(1)));
+        assert_ok!(Identity::register_identity(RuntimeOrigin::signed(2)));
+        assert_ok!(Identity::issue_credential(
+            RuntimeOrigin::signed(1),
+            2,
+            H256::zero(),
+            Some(10)
+        ));
+        assert!(<Identity as IdentityInspect<u64>>::has_credential(&2, &1));
+
+        System::set_block_number(10);
+        assert!(!<Identity as IdentityInspect<u64>>::has_credential(&2, &1));
+    });
+}
+
+#[test]
+fn register_name_charges_fee_and_resolves() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+
+        assert_ok!(Identity::register_identity(RuntimeOrigin::signed(1)));
+        assert_ok!(Identity::register_name(
+            RuntimeOrigin::signed(1),
+            b"alice.navi".to_vec(),
+            2
+        ));
+
+        // Two periods at 10 each
+        assert_eq!(Balances::free_balance(1), 980);
+        assert_eq!(Identity::resolve_name(b"alice.navi"), Some(1));
+        System::assert_last_event(
+            Event::NameRegistered {
+                name: b"alice.navi".to_vec().try_into().unwrap(),
+                owner: 1,
+                expires_at: 2_001,
+            }
+            .into(),
+        );
+
+        // Names stop resolving once expired
+        System::set_block_number(2_001);
+        assert_eq!(Identity::resolve_name(b"alice.navi"), None);
+    });
+}
+
+#[test]
+fn register_name_validates_input() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            Identity::register_name(RuntimeOrigin::signed(1), b"alice.navi".to_vec(), 1),
+            Error::<Test>::IdentityNotFound
+        );
+
+        assert_ok!(Identity::register_identity(RuntimeOrigin::signed(1)));
+        for bad in [
+            b"Alice.navi".to_vec(),
+            b"al.navi".to_vec(),
+            b"-alice.navi".to_vec(),
+            b"alice.com".to_vec(),
+            b"bot.alice.navi".to_vec(),
+        ] {
+            assert_noop!(
+                Identity::register_name(RuntimeOrigin::signed(1), bad, 1),
+                Error::<Test>::InvalidName
+            );
+        }
+        assert_noop!(
+            Identity::register_name(RuntimeOrigin::signed(1), b"alice.navi".to_vec(), 0),
+            Error::<Test>::InvalidPeriods
+        );
+        assert_noop!(
+            Identity::register_name(RuntimeOrigin::signed(1), b"alice.navi".to_vec(), 6),
+            Error::<Test>::InvalidPeriods
+        );
+    });
+}
+
+#[test]
+fn taken_name_can_be_registered_after_expiry() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+
+        assert_ok!(Identity::register_identity(RuntimeOrigin::signed(1)));
+        assert_ok!(Identity::register_identity(RuntimeOrigin::signed(2)));
+        assert_ok!(Identity::register_name(
+            RuntimeOrigin::signed(1),
+            b"alice.navi".to_vec(),
+            1
+        ));
+        assert_ok!(Identity::set_subname(
+            RuntimeOrigin::signed(1),
+            b"alice.navi".to_vec(),
+            b"bot".to_vec(),
+            3
+        ));
+
+        assert_noop!(
+            Identity::register_name(RuntimeOrigin::signed(2), b"alice.navi".to_vec(), 1),
+            Error::<Test>::NameTaken
+        );
+
+        System::set_block_number(1_001);
+        assert_ok!(Identity::register_name(
+            RuntimeOrigin::signed(2),
+            b"alice.navi".to_vec(),
+            1
+        ));
+        assert_eq!(Identity::resolve_name(b"alice.navi"), Some(2));
+
+        // Subnames of the previous owner are gone
+        assert_eq!(Identity::resolve_name(b"bot.alice.navi"), None);
+        assert!(
+            Subnames::<Test>::get(crate::Name::try_from(b"alice.navi".to_vec()).unwrap())
+                .is_empty()
+        );
+    });
+}
+
+#[test]
+fn renew_name_extends_expiry() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+
+        assert_ok!(Identity::register_identity(RuntimeOrigin::signed(1)));
+        assert_ok!(Identity::register_name(
+            RuntimeOrigin::signed(1),
+            b"alice.navi".to_vec(),
+            1
+        ));
+
+        // Anyone can pay for a renewal
+        assert_ok!(Identity::renew_name(
+            RuntimeOrigin::signed(2),
+            b"alice.navi".to_vec(),
+            1
+        ));
+        assert_eq!(Balances::free_balance(2), 990);
+
+        let name = crate::Name::try_from(b"alice.navi".to_vec()).unwrap();
+        assert_eq!(Names::<Test>::get(&name).unwrap().expires_at, Some(2_001));
+
+        System::set_block_number(2_001);
+        assert_noop!(
+            Identity::renew_name(RuntimeOrigin::signed(1), b"alice.navi".to_vec(), 1),
+            Error::<Test>::NameExpired
+        );
+    });
+}
+
+#[test]
+fn subnames_are_controlled_by_parent_owner() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+
+        assert_ok!(Identity::register_identity(RuntimeOrigin::signed(1)));
+        assert_ok!(Identity::register_name(
+            RuntimeOrigin::signed(1),
+            b"alice.navi".to_vec(),
+            1
+        ));
+
+        assert_noop!(
+            Identity::set_subname(
+                RuntimeOrigin::signed(2),
+                b"alice.navi".to_vec(),
+                b"bot".to_vec(),
+                2
+            ),
+            Error::<Test>::NotNameOwner
+        );
+
+        assert_ok!(Identity::set_subname(
+            RuntimeOrigin::signed(1),
+            b"alice.navi".to_vec(),
+            b"bot".to_vec(),
+            2
+        ));
+        assert_eq!(Identity::resolve_name(b"bot.alice.navi"), Some(2));
+
+        assert_ok!(Identity::set_subname(
+            RuntimeOrigin::signed(1),
+            b"alice.navi".to_vec(),
+            b"api".to_vec(),
+            3
+        ));
+        assert_noop!(
+            Identity::set_subname(
+                RuntimeOrigin::signed(1),
+                b"alice.navi".to_vec(),
+                b"web".to_vec(),
+                3
+            ),
+            Error::<Test>::TooManySubnames
+        );
+
+        assert_ok!(Identity::remove_subname(
+            RuntimeOrigin::signed(1),
+            b"alice.navi".to_vec(),
+            b"bot".to_vec()
+        ));
+        assert_eq!(Identity::resolve_name(b"bot.alice.navi"), None);
+
+        // Subnames expire with their parent
+        System::set_block_number(1_001);
+        assert_eq!(Identity::resolve_name(b"api.alice.navi"), None);
+    });
+}
+
+#[test]
+fn primary_name_reverse_lookup_works() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+
+        assert_ok!(Identity::register_identity(RuntimeOrigin::signed(1)));
+        assert_ok!(Identity::register_name(
+            RuntimeOrigin::signed(1),
+            b"alice.navi".to_vec(),
+            1
+        ));
+        assert_ok!(Identity::set_subname(
+            RuntimeOrigin::signed(1),
+            b"alice.navi".to_vec(),
+            b"bot".to_vec(),
+            2
+        ));
+
+        // Account 2 cannot claim a name that resolves to account 1
+        assert_noop!(
+            Identity::set_primary_name(RuntimeOrigin::signed(2), b"alice.navi".to_vec()),
+            Error::<Test>::NameNotResolvedToCaller
+        );
+
+        assert_ok!(Identity::set_primary_name(
+            RuntimeOrigin::signed(1),
+            b"alice.navi".to_vec()
+        ));
+        assert_ok!(Identity::set_primary_name(
+            RuntimeOrigin::signed(2),
+            b"bot.alice.navi".to_vec()
+        ));
+        assert_eq!(Identity::primary_name(&1), Some(b"alice.navi".to_vec()));
+        assert_eq!(Identity::primary_name(&2), Some(b"bot.alice.navi".to_vec()));
+
+        // Repointing the subname invalidates the stale reverse record
+        assert_ok!(Identity::set_subname(
+            RuntimeOrigin::signed(1),
+            b"alice.navi".to_vec(),
+            b"bot".to_vec(),
+            3
+        ));
+        assert_eq!(Identity::primary_name(&2), None);
+    });
+}
+
+fn display_fields(display: &[u8]) -> IdentityFields {
+    IdentityFields {
+        display: Some(display.to_vec().try_into().unwrap()),
+        ..Default::default()
+    }
+}
+
+/// Register identity 1 with a display name and registrar 2 covering the display field.
+fn setup_registrar(fee: u64) {
+    assert_ok!(Identity::register_identity(RuntimeOrigin::signed(1)));
+    assert_ok!(Identity::set_identity_fields(
+        RuntimeOrigin::signed(1),
+        display_fields(b"Alice")
+    ));
+    assert_ok!(Identity::add_registrar(
+        RuntimeOrigin::root(),
+        2,
+        FIELD_DISPLAY
+    ));
+    assert_ok!(Identity::set_registrar_fee(
+        RuntimeOrigin::signed(2),
+        0,
+        fee
+    ));
+}
+
+#[test]
+fn add_registrar_requires_registrar_origin() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            Identity::add_registrar(RuntimeOrigin::signed(1), 2, FIELD_DISPLAY),
+            sp_runtime::DispatchError::BadOrigin
+        );
+
+        assert_ok!(Identity::add_registrar(
+            RuntimeOrigin::root(),
+            2,
+            FIELD_DISPLAY
+        ));
+        assert_noop!(
+            Identity::set_registrar_fee(RuntimeOrigin::signed(3), 0, 5),
+            Error::<Test>::NotRegistrar
+        );
+
+        assert_ok!(Identity::remove_registrar(RuntimeOrigin::root(), 0));
+        assert_noop!(
+            Identity::set_registrar_fee(RuntimeOrigin::signed(2), 0, 5),
+            Error::<Test>::RegistrarNotFound
+        );
+    });
+}
+
+#[test]
+fn judgement_fee_is_held_and_paid_to_registrar() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        setup_registrar(50);
+
+        assert_noop!(
+            Identity::request_judgement(RuntimeOrigin::signed(1), 0, 49),
+            Error::<Test>::FeeTooHigh
+        );
+        assert_ok!(Identity::request_judgement(RuntimeOrigin::signed(1), 0, 50));
+        assert_eq!(Balances::free_balance(1), 950);
+        assert_eq!(Judgements::<Test>::get(1, 0), Some(Judgement::FeePaid(50)));
+        assert_noop!(
+            Identity::request_judgement(RuntimeOrigin::signed(1), 0, 50),
+            Error::<Test>::JudgementPending
+        );
+
+        let hash = Identity::fields_hash(&display_fields(b"Alice"), FIELD_DISPLAY);
+        assert_ok!(Identity::provide_judgement(
+            RuntimeOrigin::signed(2),
+            0,
+            1,
+            Judgement::KnownGood,
+            hash
+        ));
+
+        assert_eq!(Judgements::<Test>::get(1, 0), Some(Judgement::KnownGood));
+        assert_eq!(Balances::free_balance(1), 950);
+        assert_eq!(Balances::free_balance(2), 1_050);
+    });
+}
+
+#[test]
+fn cancel_judgement_request_releases_fee() {
+    new_test_ext().execute_with(|| {
+        setup_registrar(50);
+
+        assert_noop!(
+            Identity::cancel_judgement_request(RuntimeOrigin::signed(1), 0),
+            Error::<Test>::JudgementNotPending
+        );
+        assert_ok!(Identity::request_judgement(RuntimeOrigin::signed(1), 0, 50));
+        assert_ok!(Identity::cancel_judgement_request(
+            RuntimeOrigin::signed(1),
+            0
+        ));
+
+        assert_eq!(Balances::free_balance(1), 1_000);
+        assert!(Judgements::<Test>::get(1, 0).is_none());
+    });
+}
+
+#[test]
+fn provide_judgement_rejects_stale_fields() {
+    new_test_ext().execute_with(|| {
+        setup_registrar(0);
+        let hash = Identity::fields_hash(&display_fields(b"Alice"), FIELD_DISPLAY);
+        assert_noop!(
+            Identity::provide_judgement(RuntimeOrigin::signed(2), 0, 1, Judgement::Erroneous, hash),
+            Error::<Test>::JudgementNotRequested
+        );
+        assert_ok!(Identity::request_judgement(RuntimeOrigin::signed(1), 0, 0));
+
+        let stale = Identity::fields_hash(&display_fields(b"Mallory"), FIELD_DISPLAY);
+        assert_noop!(
+            Identity::provide_judgement(
+                RuntimeOrigin::signed(2),
+                0,
+                1,
+                Judgement::KnownGood,
+                stale
+            ),
+            Error::<Test>::FieldsHashMismatch
+        );
+
+        let hash = Identity::fields_hash(&display_fields(b"Alice"), FIELD_DISPLAY);
+        assert_noop!(
+            Identity::provide_judgement(RuntimeOrigin::signed(3), 0, 1, Judgement::KnownGood, hash),
+            Error::<Test>::NotRegistrar
+        );
+        assert_noop!(
+            Identity::provide_judgement(
+                RuntimeOrigin::signed(2),
+                0,
+                1,
+                Judgement::FeePaid(0),
+                hash
+            ),
+            Error::<Test>::InvalidJudgement
+        );
+    });
+}
+
+#[test]
+fn changing_a_judged_field_invalidates_judgement() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        setup_registrar(0);
+
+        // Registrar 3 only covers the email field
+        assert_ok!(Identity::add_registrar(
+            RuntimeOrigin::root(),
+            3,
+            FIELD_EMAIL
+        ));
+        assert_ok!(Identity::request_judgement(RuntimeOrigin::signed(1), 0, 0));
+        assert_ok!(Identity::request_judgement(RuntimeOrigin::signed(1), 1, 0));
+
+        let hash = Identity::fields_hash(&display_fields(b"Alice"), FIELD_DISPLAY);
+        assert_ok!(Identity::provide_judgement(
+            RuntimeOrigin::signed(2),
+            0,
+            1,
+            Judgement::Reasonable,
+            hash
+        ));
+        let hash = Identity::fields_hash(&display_fields(b"Alice"), FIELD_EMAIL);
+        assert_ok!(Identity::provide_judgement(
+            RuntimeOrigin::signed(3),
+            1,
+            1,
+            Judgement::Reasonable,
+            hash
+        ));
+
+        // Changing the display name only drops the display registrar's judgement
+        assert_ok!(Identity::set_identity_fields(
+            RuntimeOrigin::signed(1),
+            display_fields(b"Alicia")
+        ));
+        assert!(Judgements::<Test>::get(1, 0).is_none());
+        assert_eq!(Judgements::<Test>::get(1, 1), Some(Judgement::Reasonable));
+        System::assert_has_event(
+            Event::JudgementInvalidated {
+                who: 1,
+                registrar: 0,
+            }
+            .into(),
+        );
+    });
+}
+
+#[test]
+fn erroneous_judgement_is_sticky() {
+    new_test_ext().execute_with(|| {
+        setup_registrar(0);
+        assert_ok!(Identity::request_judgement(RuntimeOrigin::signed(1), 0, 0));
+
+        let hash = Identity::fields_hash(&display_fields(b"Alice"), FIELD_DISPLAY);
+        assert_ok!(Identity::provide_judgement(
+            RuntimeOrigin::signed(2),
+            0,
+            1,
+            Judgement::Erroneous,
+            hash
+        ));
+        assert_noop!(
+            Identity::request_judgement(RuntimeOrigin::signed(1), 0, 0),
+            Error::<Test>::StickyJudgement
+        );
+
+        // Editing the fields does not clear it either
+        assert_ok!(Identity::set_identity_fields(
+            RuntimeOrigin::signed(1),
+            display_fields(b"Alicia")
+        ));
+        assert_eq!(Judgements::<Test>::get(1, 0), Some(Judgement::Erroneous));
+    });
+}
+
+#[test]
+fn add_sub_identity_holds_deposit_and_resolves_to_parent() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+
+        assert_noop!(
+            Identity::add_sub_identity(RuntimeOrigin::signed(1), 2, b"bot".to_vec(), 0),
+            Error::<Test>::IdentityNotFound
+        );
+
+        assert_ok!(Identity::register_identity(RuntimeOrigin::signed(1)));
+        assert_ok!(Identity::add_sub_identity(
+            RuntimeOrigin::signed(1),
+            2,
+            b"ci-bot".to_vec(),
+            SIGN_IN
+        ));
+
+        assert_eq!(Balances::free_balance(1), 995);
+        assert_eq!(SubsOf::<Test>::get(1).into_inner(), vec![2]);
+        assert_eq!(Identity::identity_of(&2), Some(1));
+        assert_eq!(Identity::identity_of(&1), Some(1));
+        assert_eq!(Identity::identity_of(&3), None);
+        assert!(Identity::sub_has_permissions(&2, SIGN_IN));
+        assert!(!Identity::sub_has_permissions(&2, ACCESS));
+        System::assert_last_event(Event::SubIdentityAdded { parent: 1, sub: 2 }.into());
+
+        // A sub-identity cannot be attached twice or register its own NaviID
+        assert_noop!(
+            Identity::add_sub_identity(RuntimeOrigin::signed(1), 2, b"bot".to_vec(), 0),
+            Error::<Test>::AlreadySubIdentity
+        );
+        assert_noop!(
+            Identity::register_identity(RuntimeOrigin::signed(2)),
+            Error::<Test>::AlreadySubIdentity
+        );
+    });
+}
+
+#[test]
+fn sub_identities_are_bounded() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Identity::register_identity(RuntimeOrigin::signed(1)));
+        assert_ok!(Identity::add_sub_identity(
+            RuntimeOrigin::signed(1),
+            2,
+            b"a".to_vec(),
+            0
+        ));
+        assert_ok!(Identity::add_sub_identity(
+            RuntimeOrigin::signed(1),
+            3,
+            b"b".to_vec(),
+            0
+        ));
+        assert_noop!(
+            Identity::add_sub_identity(RuntimeOrigin::signed(1), 4, b"c".to_vec(), 0),
+            Error::<Test>::TooManySubIdentities
+        );
+    });
+}
+
+#[test]
+fn set_sub_identity_updates_permissions() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Identity::register_identity(RuntimeOrigin::signed(1)));
+        assert_ok!(Identity::register_identity(RuntimeOrigin::signed(3)));
+        assert_ok!(Identity::add_sub_identity(
+            RuntimeOrigin::signed(1),
+            2,
+            b"bot".to_vec(),
+            0
+        ));
+
+        assert_noop!(
+            Identity::set_sub_identity(RuntimeOrigin::signed(3), 2, b"bot".to_vec(), 0),
+            Error::<Test>::NotSubIdentity
+        );
+        assert_ok!(Identity::set_sub_identity(
+            RuntimeOrigin::signed(1),
+            2,
+            b"api".to_vec(),
+            ACCESS
+        ));
+
+        let (_, info) = SuperOf::<Test>::get(2).unwrap();
+        assert_eq!(info.label.into_inner(), b"api".to_vec());
+        assert!(Identity::sub_has_permissions(&2, ACCESS));
+    });
+}
+
+#[test]
+fn either_party_can_remove_sub_identity() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+
+        assert_ok!(Identity::register_identity(RuntimeOrigin::signed(1)));
+        assert_ok!(Identity::add_sub_identity(
+            RuntimeOrigin::signed(1),
+            2,
+            b"bot".to_vec(),
+            0
+        ));
+        assert_ok!(Identity::add_sub_identity(
+            RuntimeOrigin::signed(1),
+            3,
+            b"phone".to_vec(),
+            0
+        ));
+        assert_eq!(Balances::free_balance(1), 990);
+
+        // Removed by the parent
+        assert_noop!(
+            Identity::remove_sub_identity(RuntimeOrigin::signed(3), 2),
+            Error::<Test>::NotSubIdentity
+        );
+        assert_ok!(Identity::remove_sub_identity(RuntimeOrigin::signed(1), 2));
+        System::assert_last_event(Event::SubIdentityRemoved { parent: 1, sub: 2 }.into());
+
+        // Removed by the sub-identity itself
+        assert_ok!(Identity::quit_sub_identity(RuntimeOrigin::signed(3)));
+        assert_noop!(
+            Identity::quit_sub_identity(RuntimeOrigin::signed(3)),
+            Error::<Test>::NotSubIdentity
+        );
+
+        // Deposits go back to the parent
+        assert_eq!(Balances::free_balance(1), 1_000);
+        assert!(SubsOf::<Test>::get(1).is_empty());
+        assert_eq!(Identity::identity_of(&2), None);
+    });
+}
+
+#[test]
+fn auth_keys_can_be_added_and_removed() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+
+        let key = AuthKey::Sr25519([1; 32]);
+        assert_noop!(
+            Identity::add_auth_key(RuntimeOrigin::signed(1), key),
+            Error::<Test>::IdentityNotFound
+        );
+
+        assert_ok!(Identity::register_identity(RuntimeOrigin::signed(1)));
+        assert_ok!(Identity::add_auth_key(RuntimeOrigin::signed(1), key));
+        System::assert_last_event(Event::AuthKeyAdded { who: 1, key }.into());
+        assert_noop!(
+            Identity::add_auth_key(RuntimeOrigin::signed(1), key),
+            Error::<Test>::AuthKeyExists
+        );
+        assert_eq!(Identity::auth_keys(&1), vec![key]);
+
+        assert_ok!(Identity::remove_auth_key(RuntimeOrigin::signed(1), key));
+        System::assert_last_event(Event::AuthKeyRemoved { who: 1, key }.into());
+        assert_noop!(
+            Identity::remove_auth_key(RuntimeOrigin::signed(1), key),
+            Error::<Test>::AuthKeyNotFound
+        );
+        assert!(Identity::auth_keys(&1).is_empty());
+    });
+}
+
+#[test]
+fn auth_keys_are_bounded() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Identity::register_identity(RuntimeOrigin::signed(1)));
+        assert_ok!(Identity::add_auth_key(
+            RuntimeOrigin::signed(1),
+            AuthKey::Sr25519([1; 32])
+        ));
+        assert_ok!(Identity::add_auth_key(
+            RuntimeOrigin::signed(1),
+            AuthKey::Ed25519([1; 32])
+        ));
+        assert_noop!(
+            Identity::add_auth_key(RuntimeOrigin::signed(1), AuthKey::Sr25519([2; 32])),
+            Error::<Test>::TooManyAuthKeys
+        );
+    });
+}
+
+/// Make identity 1 a person through a positive judgement from registrar 2.
+fn setup_person() {
+    setup_registrar(0);
+    assert_ok!(Identity::request_judgement(RuntimeOrigin::signed(1), 0, 0));
+    let hash = Identity::fields_hash(&display_fields(b"Alice"), FIELD_DISPLAY);
+    assert_ok!(Identity::provide_judgement(
+        RuntimeOrigin::signed(2),
+        0,
+        1,
+        Judgement::Reasonable,
+        hash
+    ));
+}
+
+#[test]
+fn vouching_builds_personhood() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        setup_person();
+        assert_ok!(Identity::register_identity(RuntimeOrigin::signed(2)));
+        assert_ok!(Identity::register_identity(RuntimeOrigin::signed(3)));
+        assert_eq!(Identity::personhood_score(&1), 1);
+
+        // Only persons may vouch
+        assert_noop!(
+            Identity::vouch(RuntimeOrigin::signed(2), 3),
+            Error::<Test>::NotAPerson
+        );
+        assert_noop!(
+            Identity::vouch(RuntimeOrigin::signed(1), 1),
+            Error::<Test>::CannotVouchForSelf
+        );
+
+        assert_ok!(Identity::vouch(RuntimeOrigin::signed(1), 3));
+        System::assert_last_event(
+            Event::Vouched {
+                voucher: 1,
+                vouchee: 3,
+                expires_at: 101,
+            }
+            .into(),
+        );
+        assert_eq!(Balances::free_balance(1), 980);
+        assert_noop!(
+            Identity::vouch(RuntimeOrigin::signed(1), 3),
+            Error::<Test>::AlreadyVouched
+        );
+
+        // Account 3 is now a person and can vouch in turn
+        assert_eq!(Identity::personhood_score(&3), 1);
+        assert_ok!(Identity::vouch(RuntimeOrigin::signed(3), 2));
+        assert_eq!(Identity::personhood_score(&2), 1);
+
+        // Vouches stop counting once expired
+        System::set_block_number(101);
+        assert_eq!(Identity::personhood_score(&3), 0);
+        assert_ok!(Identity::unvouch(RuntimeOrigin::signed(1), 3));
+        assert_eq!(Balances::free_balance(1), 1_000);
+        assert_noop!(
+            Identity::unvouch(RuntimeOrigin::signed(1), 3),
+            Error::<Test>::VouchNotFound
+        );
+    });
+}
+
+#[test]
+fn vouches_are_bounded() {
+    new_test_ext().execute_with(|| {
+        setup_person();
+        assert_ok!(Identity::register_identity(RuntimeOrigin::signed(2)));
+        assert_ok!(Identity::register_identity(RuntimeOrigin::signed(3)));
+        assert_ok!(Identity::register_identity(RuntimeOrigin::signed(4)));
+        assert_ok!(Identity::vouch(RuntimeOrigin::signed(1), 2));
+        assert_ok!(Identity::vouch(RuntimeOrigin::signed(1), 3));
+
+        assert_ok!(Identity::vouch(RuntimeOrigin::signed(1), 4));
+        assert_ok!(Identity::vouch(RuntimeOrigin::signed(2), 4));
+        assert_noop!(
+            Identity::vouch(RuntimeOrigin::signed(3), 4),
+            Error::<Test>::TooManyVouches
+        );
+    });
+}
+
+#[test]
+fn expired_vouches_can_be_pruned() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        setup_person();
+        assert_ok!(Identity::register_identity(RuntimeOrigin::signed(2)));
+        assert_ok!(Identity::register_identity(RuntimeOrigin::signed(3)));
+        assert_ok!(Identity::register_identity(RuntimeOrigin::signed(4)));
+        assert_ok!(Identity::vouch(RuntimeOrigin::signed(1), 2));
+        assert_ok!(Identity::vouch(RuntimeOrigin::signed(2), 4));
+        assert_ok!(Identity::vouch(RuntimeOrigin::signed(1), 4));
+        assert_noop!(
+            Identity::prune_vouch(RuntimeOrigin::signed(3), 4, 1),
+            Error::<Test>::VouchNotExpired
+        );
+
+        // Expired vouches still fill the cap until pruned
+        System::set_block_number(101);
+        assert_ok!(Identity::vouch(RuntimeOrigin::signed(1), 3));
+        assert_ok!(Identity::vouch(RuntimeOrigin::signed(3), 2));
+        assert_noop!(
+            Identity::vouch(RuntimeOrigin::signed(3), 4),
+            Error::<Test>::TooManyVouches
+        );
+
+        // Anyone can prune an expired vouch, releasing the stake to the voucher
+        assert_ok!(Identity::prune_vouch(RuntimeOrigin::signed(3), 4, 1));
+        System::assert_last_event(
+            Event::VouchPruned {
+                voucher: 1,
+                vouchee: 4,
+            }
+            .into(),
+        );
+        assert_eq!(Balances::reserved_balance(1), 40);
+        assert_noop!(
+            Identity::prune_vouch(RuntimeOrigin::signed(3), 4, 1),
+            Error::<Test>::VouchNotFound
+        );
+        assert_ok!(Identity::vouch(RuntimeOrigin::signed(3), 4));
+    });
+}
+
+#[test]
+fn duplicate_report_slashes_active_vouchers() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        setup_person();
+        assert_ok!(Identity::register_identity(RuntimeOrigin::signed(2)));
+        assert_ok!(Identity::register_identity(RuntimeOrigin::signed(3)));
+        assert_ok!(Identity::vouch(RuntimeOrigin::signed(1), 3));
+
+        // Vouches for account 2 are still active when account 1's vouch for 3 expires
+        System::set_block_number(50);
+        assert_ok!(Identity::vouch(RuntimeOrigin::signed(3), 2));
+        assert_ok!(Identity::vouch(RuntimeOrigin::signed(1), 2));
+        System::set_block_number(101);
+
+        assert_noop!(
+            Identity::report_duplicate(RuntimeOrigin::signed(1), 2),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_ok!(Identity::report_duplicate(RuntimeOrigin::root(), 3));
+        System::assert_last_event(Event::DuplicateReported { who: 3 }.into());
+
+        // Account 1's vouch for 3 had expired, so its stake is released
+        assert_eq!(Balances::free_balance(1), 980);
+        assert_eq!(Balances::reserved_balance(1), 20);
+        assert_eq!(Identity::personhood_score(&3), 0);
+        assert_noop!(
+            Identity::vouch(RuntimeOrigin::signed(1), 3),
+            Error::<Test>::DuplicateIdentity
+        );
+
+        // Both active vouchers of account 2 lose their stake
+        assert_ok!(Identity::report_duplicate(RuntimeOrigin::root(), 2));
+        System::assert_has_event(
+            Event::VoucherSlashed {
+                voucher: 1,
+                vouchee: 2,
+                amount: 20,
+            }
+            .into(),
+        );
+        assert_eq!(Balances::reserved_balance(1), 0);
+        assert_eq!(Balances::free_balance(1), 980);
+        assert_eq!(Balances::reserved_balance(3), 0);
+        assert_eq!(Balances::free_balance(3), 980);
+        assert_noop!(
+            Identity::report_duplicate(RuntimeOrigin::root(), 2),
+            Error::<Test>::DuplicateIdentity
+        );
+    });
+}
+
+fn report(network_id: u64, who: u64, event: ReputationEvent) {
+    <Identity as ReputationHandler<u64>>::report(network_id, &who, event);
+}
+
+#[test]
+fn reputation_records_network_activity() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+
+        // Accounts without a NaviID are ignored
+        report(0, 1, ReputationEvent::MembershipCompleted);
+        assert_eq!(Identity::reputation(&1, 0), None);
+
+        assert_ok!(Identity::register_identity(RuntimeOrigin::signed(1)));
+        report(0, 1, ReputationEvent::MembershipCompleted);
+        report(0, 1, ReputationEvent::PayPerUse(55));
+        System::assert_last_event(
+            Event::ReputationUpdated {
+                who: 1,
+                network_id: 0,
+                score: 15,
+            }
+            .into(),
+        );
+        report(1, 1, ReputationEvent::Banned);
+
+        let reputation = Identity::reputation(&1, 0).unwrap();
+        assert_eq!(reputation.memberships_completed, 1);
+        assert_eq!(reputation.ppu_volume, 55);
+        assert_eq!(reputation.score, 15);
+
+        let reputations = Identity::reputations(&1);
+        assert_eq!(reputations.len(), 2);
+        let (_, banned) = reputations.iter().find(|(id, _)| *id == 1).unwrap();
+        assert_eq!(banned.bans, 1);
+        assert_eq!(banned.score, -100);
+    });
+}
+
+#[test]
+fn reputation_decays_over_time() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(Identity::register_identity(RuntimeOrigin::signed(1)));
+        report(0, 1, ReputationEvent::PayPerUse(400));
+        report(0, 1, ReputationEvent::Slashed);
+
+        // Half-life is 100 blocks
+        System::set_block_number(101);
+        assert_eq!(Identity::reputation(&1, 0).unwrap().score, -5);
+        System::set_block_number(201);
+        assert_eq!(Identity::reputation(&1, 0).unwrap().score, -2);
+
+        // New activity builds on the decayed score
+        report(0, 1, ReputationEvent::MembershipCompleted);
+        assert_eq!(Identity::reputation(&1, 0).unwrap().score, 8);
+        assert_eq!(Identity::reputation(&1, 0).unwrap().slashes, 1);
+    });
+}
+
+#[test]
+fn sub_identity_activity_counts_towards_parent() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Identity::register_identity(RuntimeOrigin::signed(1)));
+        assert_ok!(Identity::add_sub_identity(
+            RuntimeOrigin::signed(1),
+            2,
+            b"bot".to_vec(),
+            ACCESS
+        ));
+
+        report(0, 2, ReputationEvent::MembershipCompleted);
+
+        assert_eq!(
+            Identity::reputation(&1, 0).unwrap().memberships_completed,
+            1
+        );
+        assert_eq!(Identity::reputation(&2, 0), None);
+    });
+}
+
+#[test]
+fn commitments_can_be_set_replaced_and_cleared() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+
+        let commitment = attribute_commitment(b"email", b"alice@example.com", &[7; 32]);
+        assert_noop!(
+            Identity::set_commitment(RuntimeOrigin::signed(1), b"email".to_vec(), commitment),
+            Error::<Test>::IdentityNotFound
+        );
+
+        assert_ok!(Identity::register_identity(RuntimeOrigin::signed(1)));
+        assert_noop!(
+            Identity::set_commitment(RuntimeOrigin::signed(1), vec![], commitment),
+            Error::<Test>::InvalidAttributeKey
+        );
+        assert_ok!(Identity::set_commitment(
+            RuntimeOrigin::signed(1),
+            b"email".to_vec(),
+            [0; 32]
+        ));
+        assert_ok!(Identity::set_commitment(
+            RuntimeOrigin::signed(1),
+            b"email".to_vec(),
+            commitment
+        ));
+        System::assert_last_event(
+            Event::CommitmentSet {
+                who: 1,
+                key: b"email".to_vec().try_into().unwrap(),
+            }
+            .into(),
+        );
+        assert_eq!(
+            Identity::commitments(&1),
+            vec![(b"email".to_vec(), commitment)]
+        );
+
+        assert_ok!(Identity::clear_commitment(
+            RuntimeOrigin::signed(1),
+            b"email".to_vec()
+        ));
+        assert_noop!(
+            Identity::clear_commitment(RuntimeOrigin::signed(1), b"email".to_vec()),
+            Error::<Test>::CommitmentNotFound
+        );
+        assert!(Identity::commitments(&1).is_empty());
+    });
+}
+
+#[test]
+fn commitments_are_bounded() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Identity::register_identity(RuntimeOrigin::signed(1)));
+        assert_ok!(Identity::set_commitment(
+            RuntimeOrigin::signed(1),
+            b"email".to_vec(),
+            [1; 32]
+        ));
+        assert_ok!(Identity::set_commitment(
+            RuntimeOrigin::signed(1),
+            b"legal_name".to_vec(),
+            [2; 32]
+        ));
+        assert_noop!(
+            Identity::set_commitment(RuntimeOrigin::signed(1), b"phone".to_vec(), [3; 32]),
+            Error::<Test>::TooManyCommitments
+        );
+    });
+}
+
+#[test]
+fn genesis_registers_identities() {
+    let mut t = frame_system::GenesisConfig::<Test>::default()
+        .build_storage()
+        .unwrap();
+    crate::GenesisConfig::<Test> {
+        identities: vec![
+            (
+                1,
+                Some(b"alice.navi".to_vec()),
+                vec![AuthKey::Sr25519([1; 32])],
+            ),
+            (2, None, vec![]),
+        ],
+    }
+    .assimilate_storage(&mut t)
+    .unwrap();
+
+    sp_io::TestExternalities::from(t).execute_with(|| {
+        System::set_block_number(1);
+
+        assert!(Identity::has_identity(&1));
+        assert!(Identity::has_identity(&2));
+        assert_eq!(Identity::resolve_name(b"alice.navi"), Some(1));
+        assert_eq!(Identity::primary_name(&1), Some(b"alice.navi".to_vec()));
+        assert_eq!(Identity::auth_keys(&1), vec![AuthKey::Sr25519([1; 32])]);
+        assert!(Identity::auth_keys(&2).is_empty());
+
+        // Names are held for the maximum number of renewal periods
+        System::set_block_number(5_000);
+        assert_eq!(Identity::resolve_name(b"alice.navi"), None);
+    });
+}
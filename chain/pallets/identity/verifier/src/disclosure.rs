@@ -0,0 +1,162 @@
+//! Disclosure of private NaviID attributes.
+//!
+//! A NaviID publishes salted commitments to private attributes on chain (see
+//! [`attribute_commitment`]). To prove attributes to a verifier, the user hands over a
+//! [`Disclosure`] with the values and salts, which the verifier checks against the commitments
+//! at a chosen block, for example through the `naviId_verifyDisclosure` RPC. As JSON:
+//!
+//! ```json
+//! {
+//!   "account": "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY",
+//!   "attributes": [
+//!     {
+//!       "key": "email",
+//!       "value": "alice@example.com",
+//!       "salt": "0x5c0d1176a568c1f92944340dbfed9e9c530ebca703c85910e7164cb7d1c9e47b"
+//!     }
+//!   ]
+//! }
+//! ```
+//!
+//! Only the attributes included in the disclosure are revealed.
+
+use core::fmt;
+
+use pallet_identity::attribute_commitment;
+use serde::{Deserialize, Serialize};
+use sp_core::{crypto::AccountId32, H256};
+
+/// Why a disclosure was rejected.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// The disclosure reveals no attributes.
+    Empty,
+    /// The NaviID has no commitment for this attribute.
+    NotCommitted(String),
+    /// The value and salt do not match the commitment for this attribute.
+    Mismatch(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Empty => f.write_str("disclosure reveals no attributes"),
+            Error::NotCommitted(key) => write!(f, "no commitment for attribute `{key}`"),
+            Error::Mismatch(key) => write!(f, "attribute `{key}` does not match its commitment"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// One revealed attribute.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DisclosedAttribute {
+    /// The attribute key, such as `email`.
+    pub key: String,
+    /// The attribute value.
+    pub value: String,
+    /// The salt used in the commitment.
+    pub salt: H256,
+}
+
+impl DisclosedAttribute {
+    /// Reveal `value` for `key`, committed with `salt`.
+    pub fn new(key: impl Into<String>, value: impl Into<String>, salt: [u8; 32]) -> Self {
+        DisclosedAttribute {
+            key: key.into(),
+            value: value.into(),
+            salt: salt.into(),
+        }
+    }
+
+    /// The on-chain commitment this attribute must match.
+    pub fn commitment(&self) -> [u8; 32] {
+        attribute_commitment(
+            self.key.as_bytes(),
+            self.value.as_bytes(),
+            self.salt.as_fixed_bytes(),
+        )
+    }
+}
+
+/// Attributes a NaviID reveals to a verifier.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Disclosure {
+    /// The NaviID the attributes belong to.
+    pub account: AccountId32,
+    /// The revealed attributes.
+    pub attributes: Vec<DisclosedAttribute>,
+}
+
+impl Disclosure {
+    /// Check every revealed attribute against the NaviID's `(key, commitment)` pairs.
+    pub fn verify(&self, commitments: &[(Vec<u8>, [u8; 32])]) -> Result<(), Error> {
+        if self.attributes.is_empty() {
+            return Err(Error::Empty);
+        }
+        for attribute in &self.attributes {
+            let (_, commitment) = commitments
+                .iter()
+                .find(|(key, _)| key.as_slice() == attribute.key.as_bytes())
+                .ok_or_else(|| Error::NotCommitted(attribute.key.clone()))?;
+            if *commitment != attribute.commitment() {
+                return Err(Error::Mismatch(attribute.key.clone()));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn disclosure(value: &str, salt: [u8; 32]) -> Disclosure {
+        Disclosure {
+            account: AccountId32::new([1; 32]),
+            attributes: vec![DisclosedAttribute::new("email", value, salt)],
+        }
+    }
+
+    fn commitments() -> Vec<(Vec<u8>, [u8; 32])> {
+        vec![(
+            b"email".to_vec(),
+            attribute_commitment(b"email", b"alice@example.com", &[7; 32]),
+        )]
+    }
+
+    #[test]
+    fn verifies_committed_attributes() {
+        assert_eq!(
+            disclosure("alice@example.com", [7; 32]).verify(&commitments()),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn rejects_wrong_value_or_salt() {
+        let mismatch = Err(Error::Mismatch("email".into()));
+        assert_eq!(
+            disclosure("bob@example.com", [7; 32]).verify(&commitments()),
+            mismatch
+        );
+        assert_eq!(
+            disclosure("alice@example.com", [8; 32]).verify(&commitments()),
+            mismatch
+        );
+    }
+
+    #[test]
+    fn rejects_uncommitted_or_empty_disclosures() {
+        let mut disclosure = disclosure("alice@example.com", [7; 32]);
+        disclosure.attributes[0].key = "legal_name".into();
+        assert_eq!(
+            disclosure.verify(&commitments()),
+            Err(Error::NotCommitted("legal_name".into()))
+        );
+
+        disclosure.attributes.clear();
+        assert_eq!(disclosure.verify(&commitments()), Err(Error::Empty));
+    }
+}
@@ -0,0 +1,340 @@
+//! Off-chain verification for NaviID.
+//!
+//! ## Sign-In with NaviID
+//!
+//! An app authenticates a NaviID by asking the user to sign a challenge bound to the app's
+//! domain, a one-time nonce and an expiry. The signature must come from one of the
+//! authentication keys registered for the NaviID on chain, as returned by the `auth_keys`
+//! runtime API or checked directly by the `naviId_verifySignIn` RPC.
+//!
+//! The challenge is plain text so wallets can show it to the user before signing:
+//!
+//! ```text
+//! example.com wants you to sign in with your NaviID:
+//! 5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY
+//!
+//! Nonce: k2Jd81mXq0
+//! Issued At: 1700000000
+//! Expiration Time: 1700000300
+//! ```
+//!
+//! Times are Unix timestamps in seconds. Tracking which nonces have been used is up to the app.
+//!
+//! ## Attribute disclosure
+//!
+//! The [`disclosure`] module checks private attributes revealed by a user against the salted
+//! commitments published for their NaviID.
+
+pub mod disclosure;
+
+use core::fmt;
+
+use pallet_identity::AuthKey;
+use serde::{Deserialize, Serialize};
+use sp_core::{
+    crypto::{AccountId32, Ss58Codec},
+    ed25519, sr25519, Pair,
+};
+
+/// The minimum length of a challenge nonce.
+pub const MIN_NONCE_LENGTH: usize = 8;
+
+const HEADER_SUFFIX: &str = " wants you to sign in with your NaviID:";
+const NONCE_PREFIX: &str = "Nonce: ";
+const ISSUED_AT_PREFIX: &str = "Issued At: ";
+const EXPIRATION_TIME_PREFIX: &str = "Expiration Time: ";
+
+/// Why a challenge or signature was rejected.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// The message is not a challenge in canonical form.
+    Malformed,
+    /// The domain is empty or contains whitespace.
+    InvalidDomain,
+    /// The nonce is too short or not alphanumeric.
+    InvalidNonce,
+    /// The account is not a valid SS58 address.
+    InvalidAccount,
+    /// The expiration time is not after the issue time.
+    InvalidValidity,
+    /// The challenge was issued for a different domain.
+    DomainMismatch,
+    /// The challenge is not valid yet.
+    NotYetValid,
+    /// The challenge has expired.
+    Expired,
+    /// The signature does not match any authentication key of the NaviID.
+    BadSignature,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let reason = match self {
+            Error::Malformed => "malformed sign-in challenge",
+            Error::InvalidDomain => "invalid domain",
+            Error::InvalidNonce => "invalid nonce",
+            Error::InvalidAccount => "invalid account",
+            Error::InvalidValidity => "expiration time is not after issue time",
+            Error::DomainMismatch => "challenge was issued for another domain",
+            Error::NotYetValid => "challenge is not valid yet",
+            Error::Expired => "challenge has expired",
+            Error::BadSignature => "signature does not match any authentication key",
+        };
+        f.write_str(reason)
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// A Sign-In with NaviID challenge.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Challenge {
+    /// The domain of the app requesting the sign-in, such as `example.com`.
+    pub domain: String,
+    /// The NaviID signing in.
+    pub account: AccountId32,
+    /// A one-time value chosen by the app.
+    pub nonce: String,
+    /// When the challenge was issued.
+    pub issued_at: u64,
+    /// When the challenge stops being accepted.
+    pub expires_at: u64,
+}
+
+impl Challenge {
+    /// Create a challenge, validating each part.
+    pub fn new(
+        domain: impl Into<String>,
+        account: AccountId32,
+        nonce: impl Into<String>,
+        issued_at: u64,
+        expires_at: u64,
+    ) -> Result<Self, Error> {
+        let challenge = Challenge {
+            domain: domain.into(),
+            account,
+            nonce: nonce.into(),
+            issued_at,
+            expires_at,
+        };
+        challenge.validate()?;
+        Ok(challenge)
+    }
+
+    /// The text the user signs.
+    pub fn message(&self) -> String {
+        format!(
+            "{}{HEADER_SUFFIX}\n{}\n\n{NONCE_PREFIX}{}\n{ISSUED_AT_PREFIX}{}\n{EXPIRATION_TIME_PREFIX}{}",
+            self.domain,
+            self.account.to_ss58check(),
+            self.nonce,
+            self.issued_at,
+            self.expires_at,
+        )
+    }
+
+    /// Parse a challenge from the text the user signed.
+    ///
+    /// Only the canonical form produced by [`Challenge::message`] is accepted, so the parsed
+    /// challenge always describes exactly the bytes that were signed.
+    pub fn parse(message: &str) -> Result<Self, Error> {
+        let lines: Vec<&str> = message.split('\n').collect();
+        let [header, account, "", nonce, issued_at, expires_at] = lines[..] else {
+            return Err(Error::Malformed);
+        };
+
+        let domain = header.strip_suffix(HEADER_SUFFIX).ok_or(Error::Malformed)?;
+        let account = AccountId32::from_ss58check(account).map_err(|_| Error::InvalidAccount)?;
+        let nonce = nonce.strip_prefix(NONCE_PREFIX).ok_or(Error::Malformed)?;
+        let issued_at = parse_time(issued_at, ISSUED_AT_PREFIX)?;
+        let expires_at = parse_time(expires_at, EXPIRATION_TIME_PREFIX)?;
+
+        let challenge = Challenge::new(domain, account, nonce, issued_at, expires_at)?;
+        if challenge.message() != message {
+            return Err(Error::Malformed);
+        }
+        Ok(challenge)
+    }
+
+    /// Check that the challenge was issued for `domain` and is valid at `now`.
+    pub fn ensure_valid(&self, domain: &str, now: u64) -> Result<(), Error> {
+        if self.domain != domain {
+            return Err(Error::DomainMismatch);
+        }
+        if now < self.issued_at {
+            return Err(Error::NotYetValid);
+        }
+        if now >= self.expires_at {
+            return Err(Error::Expired);
+        }
+        Ok(())
+    }
+
+    /// Verify `signature` over this challenge against the NaviID's authentication keys.
+    pub fn verify(
+        &self,
+        signature: &[u8],
+        keys: &[AuthKey],
+        domain: &str,
+        now: u64,
+    ) -> Result<(), Error> {
+        self.ensure_valid(domain, now)?;
+        let message = self.message();
+        if keys
+            .iter()
+            .any(|key| verify_signature(key, message.as_bytes(), signature))
+        {
+            Ok(())
+        } else {
+            Err(Error::BadSignature)
+        }
+    }
+
+    fn validate(&self) -> Result<(), Error> {
+        if self.domain.is_empty() || self.domain.chars().any(char::is_whitespace) {
+            return Err(Error::InvalidDomain);
+        }
+        if self.nonce.len() < MIN_NONCE_LENGTH
+            || !self.nonce.bytes().all(|b| b.is_ascii_alphanumeric())
+        {
+            return Err(Error::InvalidNonce);
+        }
+        if self.expires_at <= self.issued_at {
+            return Err(Error::InvalidValidity);
+        }
+        Ok(())
+    }
+}
+
+/// Parse and verify a signed challenge in one step.
+///
+/// Returns the challenge so the app can check and consume its nonce.
+pub fn verify(
+    message: &str,
+    signature: &[u8],
+    keys: &[AuthKey],
+    domain: &str,
+    now: u64,
+) -> Result<Challenge, Error> {
+    let challenge = Challenge::parse(message)?;
+    challenge.verify(signature, keys, domain, now)?;
+    Ok(challenge)
+}
+
+/// Whether `signature` over `message` was made by `key`.
+///
+/// Browser wallets wrap raw payloads in `<Bytes>` tags before signing, so both forms are accepted.
+pub fn verify_signature(key: &AuthKey, message: &[u8], signature: &[u8]) -> bool {
+    let wrapped = [b"<Bytes>".as_slice(), message, b"</Bytes>"].concat();
+    let payloads = [message, wrapped.as_slice()];
+    match key {
+        AuthKey::Sr25519(public) => {
+            let Ok(signature) = sr25519::Signature::try_from(signature) else {
+                return false;
+            };
+            let public = sr25519::Public::from_raw(*public);
+            payloads
+                .iter()
+                .any(|payload| sr25519::Pair::verify(&signature, payload, &public))
+        }
+        AuthKey::Ed25519(public) => {
+            let Ok(signature) = ed25519::Signature::try_from(signature) else {
+                return false;
+            };
+            let public = ed25519::Public::from_raw(*public);
+            payloads
+                .iter()
+                .any(|payload| ed25519::Pair::verify(&signature, payload, &public))
+        }
+    }
+}
+
+fn parse_time(line: &str, prefix: &str) -> Result<u64, Error> {
+    line.strip_prefix(prefix)
+        .and_then(|time| time.parse().ok())
+        .ok_or(Error::Malformed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DOMAIN: &str = "example.com";
+
+    fn alice() -> sr25519::Pair {
+        sr25519::Pair::from_string("//Alice", None).unwrap()
+    }
+
+    fn challenge(pair: &sr25519::Pair) -> Challenge {
+        Challenge::new(DOMAIN, pair.public().into(), "k2Jd81mXq0", 1_000, 1_300).unwrap()
+    }
+
+    #[test]
+    fn message_round_trips() {
+        let challenge = challenge(&alice());
+        assert_eq!(Challenge::parse(&challenge.message()), Ok(challenge));
+    }
+
+    #[test]
+    fn non_canonical_messages_are_rejected() {
+        let message = challenge(&alice()).message();
+        assert_eq!(
+            Challenge::parse(&format!("{message}\n")),
+            Err(Error::Malformed)
+        );
+        assert_eq!(
+            Challenge::parse(&message.replace("1000", "01000")),
+            Err(Error::Malformed)
+        );
+        assert_eq!(
+            Challenge::parse(&message.replace("k2Jd81mXq0", "short")),
+            Err(Error::InvalidNonce)
+        );
+    }
+
+    #[test]
+    fn verifies_signature_from_auth_key() {
+        let pair = alice();
+        let message = challenge(&pair).message();
+        let signature = pair.sign(message.as_bytes());
+        let keys = [AuthKey::Sr25519(pair.public().0)];
+
+        assert!(verify(&message, &signature.0, &keys, DOMAIN, 1_000).is_ok());
+        assert_eq!(
+            verify(&message, &signature.0, &[], DOMAIN, 1_000),
+            Err(Error::BadSignature)
+        );
+    }
+
+    #[test]
+    fn accepts_wallet_wrapped_signatures() {
+        let pair = alice();
+        let message = challenge(&pair).message();
+        let signature = pair.sign(format!("<Bytes>{message}</Bytes>").as_bytes());
+        let keys = [AuthKey::Sr25519(pair.public().0)];
+
+        assert!(verify(&message, &signature.0, &keys, DOMAIN, 1_000).is_ok());
+    }
+
+    #[test]
+    fn enforces_domain_and_validity_window() {
+        let pair = alice();
+        let message = challenge(&pair).message();
+        let signature = pair.sign(message.as_bytes());
+        let keys = [AuthKey::Sr25519(pair.public().0)];
+
+        assert_eq!(
+            verify(&message, &signature.0, &keys, "evil.com", 1_000),
+            Err(Error::DomainMismatch)
+        );
+        assert_eq!(
+            verify(&message, &signature.0, &keys, DOMAIN, 999),
+            Err(Error::NotYetValid)
+        );
+        assert_eq!(
+            verify(&message, &signature.0, &keys, DOMAIN, 1_300),
+            Err(Error::Expired)
+        );
+    }
+}
@@ -0,0 +1,237 @@
+//! RPC methods for the network factory pallet.
+
+use std::sync::Arc;
+
+use jsonrpsee::{
+    core::RpcResult,
+    proc_macros::rpc,
+    types::error::{ErrorObject, ErrorObjectOwned},
+};
+use pallet_network_factory::NetworkDetails;
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::crypto::AccountId32;
+use sp_runtime::traits::Block as BlockT;
+
+pub use pallet_network_factory_runtime_api::NetworkFactoryApi as NetworkFactoryRuntimeApi;
+
+/// Network factory RPC methods.
+#[rpc(client, server)]
+pub trait NetworksApi<BlockHash> {
+    /// Up to `limit` networks in ID order, starting at `start`.
+    #[method(name = "networkFactory_networks")]
+    fn networks(
+        &self,
+        start: u64,
+        limit: u32,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Vec<NetworkDetails<AccountId32>>>;
+
+    /// Up to `limit` networks `owner` owns, closed ones included, in ID order, starting
+    /// at `start`.
+    #[method(name = "networkFactory_networksByOwner")]
+    fn networks_by_owner(
+        &self,
+        owner: AccountId32,
+        start: u64,
+        limit: u32,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Vec<NetworkDetails<AccountId32>>>;
+
+    /// The network `network_id`.
+    #[method(name = "networkFactory_networkInfo")]
+    fn network_info(
+        &self,
+        network_id: u64,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Option<NetworkDetails<AccountId32>>>;
+
+    /// The network with handle `handle`, matched case-insensitively.
+    #[method(name = "networkFactory_networkByHandle")]
+    fn network_by_handle(
+        &self,
+        handle: String,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Option<NetworkDetails<AccountId32>>>;
+
+    /// The NAVI price of the next step of the network's bonding curve.
+    #[method(name = "networkFactory_curvePrice")]
+    fn curve_price(&self, network_id: u64, at: Option<BlockHash>) -> RpcResult<Option<u128>>;
+
+    /// The NAVI cost of buying `steps` steps from the network's bonding curve.
+    #[method(name = "networkFactory_curveBuyCost")]
+    fn curve_buy_cost(
+        &self,
+        network_id: u64,
+        steps: u32,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Option<u128>>;
+
+    /// The NAVI returned for selling `steps` steps back to the network's bonding curve.
+    #[method(name = "networkFactory_curveSellReturn")]
+    fn curve_sell_return(
+        &self,
+        network_id: u64,
+        steps: u32,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Option<u128>>;
+
+    /// The IDs of the open child networks of network `network_id`, in ascending order.
+    #[method(name = "networkFactory_children")]
+    fn children(&self, network_id: u64, at: Option<BlockHash>) -> RpcResult<Vec<u64>>;
+
+    /// The IDs of the ancestors of network `network_id`, from its parent up to the root.
+    #[method(name = "networkFactory_ancestors")]
+    fn ancestors(&self, network_id: u64, at: Option<BlockHash>) -> RpcResult<Vec<u64>>;
+}
+
+/// Error codes returned by the network factory RPC.
+pub enum Error {
+    /// Calling into the runtime failed.
+    RuntimeError,
+}
+
+impl From<Error> for i32 {
+    fn from(e: Error) -> i32 {
+        match e {
+            Error::RuntimeError => 1,
+        }
+    }
+}
+
+/// Provides the network factory RPC methods.
+pub struct Networks<C, Block> {
+    client: Arc<C>,
+    _marker: std::marker::PhantomData<Block>,
+}
+
+impl<C, Block> Networks<C, Block> {
+    /// Create a new instance of the network factory RPC.
+    pub fn new(client: Arc<C>) -> Self {
+        Self {
+            client,
+            _marker: Default::default(),
+        }
+    }
+}
+
+impl<C, Block> NetworksApiServer<<Block as BlockT>::Hash> for Networks<C, Block>
+where
+    Block: BlockT,
+    C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+    C::Api: NetworkFactoryRuntimeApi<Block, AccountId32>,
+{
+    fn networks(
+        &self,
+        start: u64,
+        limit: u32,
+        at: Option<Block::Hash>,
+    ) -> RpcResult<Vec<NetworkDetails<AccountId32>>> {
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+        self.client
+            .runtime_api()
+            .networks(at, start, limit)
+            .map_err(|e| runtime_error("Unable to query networks.", e))
+    }
+
+    fn networks_by_owner(
+        &self,
+        owner: AccountId32,
+        start: u64,
+        limit: u32,
+        at: Option<Block::Hash>,
+    ) -> RpcResult<Vec<NetworkDetails<AccountId32>>> {
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+        self.client
+            .runtime_api()
+            .networks_by_owner(at, owner, start, limit)
+            .map_err(|e| runtime_error("Unable to query networks.", e))
+    }
+
+    fn network_info(
+        &self,
+        network_id: u64,
+        at: Option<Block::Hash>,
+    ) -> RpcResult<Option<NetworkDetails<AccountId32>>> {
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+        self.client
+            .runtime_api()
+            .network_info(at, network_id)
+            .map_err(|e| runtime_error("Unable to query network.", e))
+    }
+
+    fn network_by_handle(
+        &self,
+        handle: String,
+        at: Option<Block::Hash>,
+    ) -> RpcResult<Option<NetworkDetails<AccountId32>>> {
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+        self.client
+            .runtime_api()
+            .network_by_handle(at, handle.into_bytes())
+            .map_err(|e| runtime_error("Unable to query network.", e))
+    }
+
+    fn curve_price(&self, network_id: u64, at: Option<Block::Hash>) -> RpcResult<Option<u128>> {
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+        self.client
+            .runtime_api()
+            .curve_price(at, network_id)
+            .map_err(|e| runtime_error("Unable to query curve price.", e))
+    }
+
+    fn curve_buy_cost(
+        &self,
+        network_id: u64,
+        steps: u32,
+        at: Option<Block::Hash>,
+    ) -> RpcResult<Option<u128>> {
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+        self.client
+            .runtime_api()
+            .curve_buy_cost(at, network_id, steps)
+            .map_err(|e| runtime_error("Unable to query curve price.", e))
+    }
+
+    fn curve_sell_return(
+        &self,
+        network_id: u64,
+        steps: u32,
+        at: Option<Block::Hash>,
+    ) -> RpcResult<Option<u128>> {
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+        self.client
+            .runtime_api()
+            .curve_sell_return(at, network_id, steps)
+            .map_err(|e| runtime_error("Unable to query curve price.", e))
+    }
+
+    fn children(&self, network_id: u64, at: Option<Block::Hash>) -> RpcResult<Vec<u64>> {
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+        self.client
+            .runtime_api()
+            .children(at, network_id)
+            .map_err(|e| runtime_error("Unable to query network hierarchy.", e))
+    }
+
+    fn ancestors(&self, network_id: u64, at: Option<Block::Hash>) -> RpcResult<Vec<u64>> {
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+        self.client
+            .runtime_api()
+            .ancestors(at, network_id)
+            .map_err(|e| runtime_error("Unable to query network hierarchy.", e))
+    }
+}
+
+fn runtime_error(message: &str, e: sp_api::ApiError) -> ErrorObjectOwned {
+    ErrorObject::owned(Error::RuntimeError.into(), message, Some(e.to_string()))
+}
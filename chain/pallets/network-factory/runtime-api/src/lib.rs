@@ -0,0 +1,53 @@
+//! Runtime API definitions for the network factory pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+use alloc::vec::Vec;
+
+use codec::Codec;
+use pallet_network_factory::NetworkDetails;
+
+sp_api::decl_runtime_apis! {
+    /// Query networks without iterating storage off-chain.
+    pub trait NetworkFactoryApi<AccountId>
+    where
+        AccountId: Codec,
+    {
+        /// Up to `limit` networks in ID order, starting at `start`.
+        ///
+        /// Pages hold at most [`pallet_network_factory::MAX_PAGE_SIZE`] networks.
+        fn networks(start: u64, limit: u32) -> Vec<NetworkDetails<AccountId>>;
+
+        /// Up to `limit` networks `owner` owns, closed ones included, in ID order, starting
+        /// at `start`.
+        ///
+        /// Pages hold at most [`pallet_network_factory::MAX_PAGE_SIZE`] networks.
+        fn networks_by_owner(
+            owner: AccountId,
+            start: u64,
+            limit: u32,
+        ) -> Vec<NetworkDetails<AccountId>>;
+
+        /// The network `network_id`.
+        fn network_info(network_id: u64) -> Option<NetworkDetails<AccountId>>;
+
+        /// The network with handle `handle`, matched case-insensitively.
+        fn network_by_handle(handle: Vec<u8>) -> Option<NetworkDetails<AccountId>>;
+
+        /// The NAVI price of the next step of the network's bonding curve.
+        fn curve_price(network_id: u64) -> Option<u128>;
+
+        /// The NAVI cost of buying `steps` steps from the network's bonding curve.
+        fn curve_buy_cost(network_id: u64, steps: u32) -> Option<u128>;
+
+        /// The NAVI returned for selling `steps` steps back to the network's bonding curve.
+        fn curve_sell_return(network_id: u64, steps: u32) -> Option<u128>;
+
+        /// The IDs of the open child networks of network `network_id`, in ascending order.
+        fn children(network_id: u64) -> Vec<u64>;
+
+        /// The IDs of the ancestors of network `network_id`, from its parent up to the root.
+        fn ancestors(network_id: u64) -> Vec<u64>;
+    }
+}
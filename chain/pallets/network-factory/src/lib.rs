@@ -15,13 +15,28 @@ use alloc::vec::Vec;
 
 pub use pallet::*;
 
-use codec::{Decode, Encode, MaxEncodedLen};
+use codec::{Decode, DecodeWithMemTracking, Encode, MaxEncodedLen};
 use frame_support::pallet_prelude::*;
 use scale_info::TypeInfo;
-use sp_runtime::RuntimeDebug;
+use sp_runtime::{
+    traits::{Bounded, CheckedAdd, CheckedDiv, CheckedSub, One, Saturating},
+    FixedPointNumber, FixedU128, Perbill, RuntimeDebug,
+};
 
 /// The type of coin a network uses.
-#[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[derive(
+    Clone,
+    Copy,
+    Encode,
+    Decode,
+    DecodeWithMemTracking,
+    Eq,
+    PartialEq,
+    RuntimeDebug,
+    TypeInfo,
+    MaxEncodedLen,
+)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
 pub enum CoinKind {
     /// Use the native NAVI token.
     UseNavi,
@@ -29,6 +44,922 @@ pub enum CoinKind {
     MintAsset,
 }
 
+/// The account that administers a network's custom asset.
+#[derive(
+    Clone,
+    Copy,
+    Encode,
+    Decode,
+    DecodeWithMemTracking,
+    Eq,
+    PartialEq,
+    RuntimeDebug,
+    TypeInfo,
+    MaxEncodedLen,
+)]
+pub enum AssetAdmin {
+    /// The network treasury account.
+    Treasury,
+    /// The network owner.
+    Owner,
+}
+
+/// Parameters for a network's custom asset.
+#[derive(
+    Clone,
+    Encode,
+    Decode,
+    DecodeWithMemTracking,
+    Eq,
+    PartialEq,
+    RuntimeDebug,
+    TypeInfo,
+    MaxEncodedLen,
+)]
+pub struct AssetConfig<Balance> {
+    /// Asset name.
+    pub name: BoundedVec<u8, ConstU32<50>>,
+    /// Asset ticker symbol.
+    pub symbol: BoundedVec<u8, ConstU32<50>>,
+    /// Number of decimals.
+    pub decimals: u8,
+    /// The minimum balance an account must hold. Must be non-zero.
+    pub min_balance: Balance,
+    /// Who administers the asset.
+    pub admin: AssetAdmin,
+}
+
+/// A vesting schedule requested at network creation. Vesting starts at the creation block.
+#[derive(Clone, Encode, Decode, DecodeWithMemTracking, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct VestingConfig<AccountId, Balance, BlockNumber> {
+    /// Who receives the vested coins.
+    pub beneficiary: AccountId,
+    /// The total amount released by the schedule.
+    pub amount: Balance,
+    /// Blocks after the start before anything can be claimed.
+    pub cliff: BlockNumber,
+    /// Blocks after the start until everything is released. Must be non-zero and not
+    /// shorter than the cliff.
+    pub duration: BlockNumber,
+}
+
+/// The issuance configuration of a network asset.
+#[derive(Clone, Encode, Decode, DecodeWithMemTracking, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct IssuanceConfig<AccountId, Balance, BlockNumber> {
+    /// The maximum supply, if capped.
+    pub max_supply: Option<Balance>,
+    /// Amount minted to the owner at creation.
+    pub owner_allocation: Balance,
+    /// Amount minted to the treasury at creation.
+    pub treasury_allocation: Balance,
+    /// Vesting schedules, e.g. for team allocations.
+    pub vesting: Vec<VestingConfig<AccountId, Balance, BlockNumber>>,
+    /// Amount minted to the treasury per block.
+    pub inflation_per_block: Balance,
+    /// A bonding curve selling the asset against NAVI, if any.
+    pub curve: Option<CurveConfig<Balance>>,
+}
+
+impl<AccountId, Balance: Default, BlockNumber> Default
+    for IssuanceConfig<AccountId, Balance, BlockNumber>
+{
+    fn default() -> Self {
+        Self {
+            max_supply: None,
+            owner_allocation: Balance::default(),
+            treasury_allocation: Balance::default(),
+            vesting: Vec::new(),
+            inflation_per_block: Balance::default(),
+            curve: None,
+        }
+    }
+}
+
+/// How the price of a bonding curve rises with each step sold.
+#[derive(
+    Clone,
+    Copy,
+    Encode,
+    Decode,
+    DecodeWithMemTracking,
+    Eq,
+    PartialEq,
+    RuntimeDebug,
+    TypeInfo,
+    MaxEncodedLen,
+)]
+pub enum CurveKind {
+    /// Each step costs `increment` NAVI more than the one before.
+    Linear {
+        /// The price increase per step.
+        increment: u128,
+    },
+    /// Each step costs `growth` more than the one before, compounded.
+    Exponential {
+        /// The relative price increase per step.
+        growth: Perbill,
+    },
+}
+
+/// A bonding curve that sells a network asset in fixed steps.
+///
+/// Buyers pay the price of every step they buy, and sellers get back the price of every
+/// step they return, so the reserve always covers selling the whole supply.
+#[derive(
+    Clone,
+    Copy,
+    Encode,
+    Decode,
+    DecodeWithMemTracking,
+    Eq,
+    PartialEq,
+    RuntimeDebug,
+    TypeInfo,
+    MaxEncodedLen,
+)]
+pub struct CurveConfig<Balance> {
+    /// How the price rises.
+    pub kind: CurveKind,
+    /// The NAVI price of the first step. Must be non-zero.
+    pub initial_price: u128,
+    /// The amount of the asset in one step. Must be non-zero.
+    pub step: Balance,
+}
+
+impl<Balance> CurveConfig<Balance> {
+    /// The NAVI cost of the first `supply` steps, or `None` on overflow.
+    pub fn total_cost(&self, supply: u32) -> Option<u128> {
+        let base = u128::from(supply).checked_mul(self.initial_price)?;
+        match self.kind {
+            CurveKind::Linear { increment } => {
+                // The increments of every step: 0 + 1 + ... + (supply - 1).
+                let n = u128::from(supply);
+                let rises = n.checked_mul(n.saturating_sub(1))? / 2;
+                base.checked_add(increment.checked_mul(rises)?)
+            }
+            CurveKind::Exponential { growth } if growth.is_zero() => Some(base),
+            CurveKind::Exponential { growth } => {
+                // initial_price * ((1 + growth)^supply - 1) / growth
+                let growth = FixedU128::from(growth);
+                let factor = FixedU128::one()
+                    .checked_add(&growth)?
+                    .saturating_pow(supply as usize);
+                if factor == FixedU128::max_value() {
+                    return None;
+                }
+                factor
+                    .checked_sub(&FixedU128::one())?
+                    .checked_div(&growth)?
+                    .checked_mul_int(self.initial_price)
+            }
+        }
+    }
+
+    /// The NAVI cost of `steps` steps when `supply` steps are sold, which is also what
+    /// selling them back returns.
+    pub fn cost(&self, supply: u32, steps: u32) -> Option<u128> {
+        let after = self.total_cost(supply.checked_add(steps)?)?;
+        after.checked_sub(self.total_cost(supply)?)
+    }
+}
+
+/// The issuance policy enforced for a network asset.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct IssuancePolicy<Balance, BlockNumber> {
+    /// The maximum supply, if capped.
+    pub max_supply: Option<Balance>,
+    /// Amount minted to the treasury per block.
+    pub inflation_per_block: Balance,
+    /// The block up to which inflation has been minted.
+    pub inflation_minted_at: BlockNumber,
+    /// Amount promised to vesting schedules but not yet minted.
+    pub unvested: Balance,
+}
+
+/// A vesting schedule of a network asset.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct VestingSchedule<Balance, BlockNumber> {
+    /// The total amount released by the schedule.
+    pub total: Balance,
+    /// The amount already claimed.
+    pub claimed: Balance,
+    /// The block vesting started.
+    pub start: BlockNumber,
+    /// Blocks after the start before anything can be claimed.
+    pub cliff: BlockNumber,
+    /// Blocks after the start until everything is released.
+    pub duration: BlockNumber,
+}
+
+impl<Balance, BlockNumber> VestingSchedule<Balance, BlockNumber>
+where
+    Balance: sp_runtime::traits::AtLeast32BitUnsigned + Copy,
+    BlockNumber: sp_runtime::traits::AtLeast32BitUnsigned + Copy,
+{
+    /// The amount released by block `now`, claimed or not.
+    pub fn vested_at(&self, now: BlockNumber) -> Balance {
+        use sp_runtime::{traits::SaturatedConversion, Perquintill};
+
+        let elapsed = now.saturating_sub(self.start);
+        if elapsed < self.cliff {
+            return Balance::zero();
+        }
+        if elapsed >= self.duration {
+            return self.total;
+        }
+        let portion = Perquintill::from_rational(
+            elapsed.saturated_into::<u64>(),
+            self.duration.saturated_into::<u64>(),
+        );
+        portion
+            .mul_floor(self.total.saturated_into::<u128>())
+            .saturated_into()
+    }
+}
+
+/// Why a network asset was minted.
+#[derive(
+    Clone,
+    Copy,
+    Encode,
+    Decode,
+    DecodeWithMemTracking,
+    Eq,
+    PartialEq,
+    RuntimeDebug,
+    TypeInfo,
+    MaxEncodedLen,
+)]
+pub enum MintReason {
+    /// The owner's initial allocation.
+    OwnerAllocation,
+    /// The treasury's initial allocation.
+    TreasuryAllocation,
+    /// A claim from a vesting schedule.
+    Vesting,
+    /// Per-block inflation to the treasury.
+    Inflation,
+    /// A purchase from the bonding curve.
+    BondingCurve,
+}
+
+/// A role in a network's administration.
+#[derive(
+    Clone,
+    Copy,
+    Encode,
+    Decode,
+    DecodeWithMemTracking,
+    Eq,
+    PartialEq,
+    RuntimeDebug,
+    TypeInfo,
+    MaxEncodedLen,
+)]
+pub enum NetworkRole {
+    /// The network owner. Held by exactly one account and moved by ownership transfer.
+    Owner,
+    /// Manages the network on the owner's behalf.
+    Admin,
+    /// Moderates members.
+    Moderator,
+    /// Manages spending on services.
+    BillingManager,
+}
+
+impl NetworkRole {
+    /// Whether holding this role grants the permissions of `required`.
+    ///
+    /// The owner holds every role and admins hold every role but owner.
+    pub fn includes(&self, required: NetworkRole) -> bool {
+        match self {
+            NetworkRole::Owner => true,
+            NetworkRole::Admin => required != NetworkRole::Owner,
+            role => *role == required,
+        }
+    }
+}
+
+/// Network lookups for other pallets.
+pub trait NetworkInspect<AccountId> {
+    /// Whether network `network_id` exists.
+    fn network_exists(network_id: u64) -> bool;
+
+    /// Whether network `network_id` exists and is active.
+    fn is_active(network_id: u64) -> bool;
+
+    /// Whether `who` holds `role`, or a role including it, in network `network_id`.
+    fn has_role(network_id: u64, who: &AccountId, role: NetworkRole) -> bool;
+
+    /// The network whose members are also members of network `network_id`, if any.
+    fn membership_parent(_network_id: u64) -> Option<u64> {
+        None
+    }
+
+    /// Whether `who` may join network `network_id` under its admission mode.
+    fn is_admitted(_network_id: u64, _who: &AccountId) -> bool {
+        true
+    }
+}
+
+/// The public profile of a network.
+#[derive(
+    Clone,
+    Default,
+    Encode,
+    Decode,
+    DecodeWithMemTracking,
+    Eq,
+    PartialEq,
+    RuntimeDebug,
+    TypeInfo,
+    MaxEncodedLen,
+)]
+pub struct NetworkProfile {
+    /// Display name.
+    pub name: BoundedVec<u8, ConstU32<64>>,
+    /// Short ticker-like symbol.
+    pub symbol: BoundedVec<u8, ConstU32<12>>,
+    /// Short description.
+    pub description: BoundedVec<u8, ConstU32<280>>,
+    /// Content ID of the logo.
+    pub logo_cid: BoundedVec<u8, ConstU32<128>>,
+    /// Website URL.
+    pub website: BoundedVec<u8, ConstU32<128>>,
+}
+
+/// The lifecycle state of a network.
+///
+/// Dependent pallets stop accepting new stakes, payments and basket changes while a
+/// network is not active.
+#[derive(
+    Clone,
+    Copy,
+    Default,
+    Encode,
+    Decode,
+    DecodeWithMemTracking,
+    Eq,
+    PartialEq,
+    RuntimeDebug,
+    TypeInfo,
+    MaxEncodedLen,
+)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub enum NetworkState {
+    /// The network operates normally.
+    #[default]
+    Active,
+    /// The network is temporarily suspended.
+    Paused,
+    /// The network is read-only but can be reactivated.
+    Archived,
+    /// The network is shut down for good.
+    Closed,
+}
+
+/// The most networks one page of [`Pallet::networks_page`] or
+/// [`Pallet::networks_by_owner`] returns.
+pub const MAX_PAGE_SIZE: u32 = 100;
+
+/// The fewest characters in a network handle.
+pub const MIN_HANDLE_LENGTH: u32 = 3;
+
+/// The most characters in a network handle.
+pub const MAX_HANDLE_LENGTH: u32 = 32;
+
+/// A unique, URL-safe name of a network.
+pub type Handle = BoundedVec<u8, ConstU32<MAX_HANDLE_LENGTH>>;
+
+/// Whether `handle` is a valid network handle: lowercase ASCII letters, digits and
+/// single hyphens between them, [`MIN_HANDLE_LENGTH`] to [`MAX_HANDLE_LENGTH`] long.
+pub fn is_valid_handle(handle: &[u8]) -> bool {
+    (MIN_HANDLE_LENGTH as usize..=MAX_HANDLE_LENGTH as usize).contains(&handle.len())
+        && handle
+            .iter()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || *c == b'-')
+        && !handle.starts_with(b"-")
+        && !handle.ends_with(b"-")
+        && !handle.windows(2).any(|pair| pair == b"--")
+}
+
+/// A network as reported to clients.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct NetworkDetails<AccountId> {
+    /// The network ID.
+    pub network_id: u64,
+    /// The owner of the network.
+    pub owner: AccountId,
+    /// The type of coin the network uses.
+    pub coin_kind: CoinKind,
+    /// The network asset, if it mints one.
+    pub asset_id: Option<u32>,
+    /// The treasury account of the network.
+    pub treasury: AccountId,
+    /// Metadata URI for the network.
+    pub metadata_uri: Vec<u8>,
+    /// The lifecycle state of the network.
+    pub state: NetworkState,
+    /// The parent network, if this is a child network.
+    pub parent: Option<u64>,
+    /// The handle of the network, if it has one.
+    pub handle: Option<Vec<u8>>,
+}
+
+/// What happens to a network treasury when the network is closed.
+#[derive(
+    Clone,
+    Copy,
+    Encode,
+    Decode,
+    DecodeWithMemTracking,
+    Eq,
+    PartialEq,
+    RuntimeDebug,
+    TypeInfo,
+    MaxEncodedLen,
+)]
+pub enum TreasuryPolicy {
+    /// Transfer the treasury to the network owner.
+    ToOwner,
+    /// Burn the treasury.
+    Burn,
+}
+
+/// An amount paid from a network treasury.
+#[derive(
+    Clone,
+    Copy,
+    Encode,
+    Decode,
+    DecodeWithMemTracking,
+    Eq,
+    PartialEq,
+    RuntimeDebug,
+    TypeInfo,
+    MaxEncodedLen,
+)]
+pub enum SpendAmount<Balance, AssetBalance> {
+    /// An amount of NAVI.
+    Native(Balance),
+    /// An amount of the network asset.
+    Asset(AssetBalance),
+}
+
+/// How much a network treasury can pay out per period.
+#[derive(
+    Clone,
+    Copy,
+    Encode,
+    Decode,
+    DecodeWithMemTracking,
+    Eq,
+    PartialEq,
+    RuntimeDebug,
+    TypeInfo,
+    MaxEncodedLen,
+)]
+pub struct SpendLimit<Balance, AssetBalance, BlockNumber> {
+    /// The length of a period in blocks. Must be non-zero.
+    pub period: BlockNumber,
+    /// The NAVI that can be paid out per period.
+    pub native: Balance,
+    /// The network asset that can be paid out per period.
+    pub asset: AssetBalance,
+}
+
+/// What a network treasury paid out in the current period.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct SpendPeriod<Balance, AssetBalance, BlockNumber> {
+    /// The block the period started.
+    pub started_at: BlockNumber,
+    /// The NAVI paid out.
+    pub native_spent: Balance,
+    /// The network asset paid out.
+    pub asset_spent: AssetBalance,
+}
+
+/// Who votes in a network's referenda.
+#[derive(
+    Clone,
+    Copy,
+    Encode,
+    Decode,
+    DecodeWithMemTracking,
+    Eq,
+    PartialEq,
+    RuntimeDebug,
+    TypeInfo,
+    MaxEncodedLen,
+)]
+pub enum Electorate {
+    /// Holders of the network asset, weighted by the balance they lock.
+    AssetHolders,
+    /// Members of the network, one vote each.
+    Members,
+}
+
+/// How referendum votes are weighted.
+#[derive(
+    Clone,
+    Copy,
+    Encode,
+    Decode,
+    DecodeWithMemTracking,
+    Eq,
+    PartialEq,
+    RuntimeDebug,
+    TypeInfo,
+    MaxEncodedLen,
+)]
+pub enum VotingScheme {
+    /// Every locked unit counts once and is released when voting ends.
+    SimpleMajority,
+    /// Asset holders multiply their votes by locking their balance for longer.
+    Conviction,
+}
+
+/// How long a vote stays locked after its referendum ends, and what that multiplies it by.
+#[derive(
+    Clone,
+    Copy,
+    Default,
+    Encode,
+    Decode,
+    DecodeWithMemTracking,
+    Eq,
+    PartialEq,
+    RuntimeDebug,
+    TypeInfo,
+    MaxEncodedLen,
+)]
+pub enum Conviction {
+    /// 0.1x votes, unlocked when voting ends.
+    #[default]
+    None,
+    /// 1x votes, locked for one period.
+    Locked1x,
+    /// 2x votes, locked for two periods.
+    Locked2x,
+    /// 3x votes, locked for four periods.
+    Locked3x,
+    /// 4x votes, locked for eight periods.
+    Locked4x,
+    /// 5x votes, locked for 16 periods.
+    Locked5x,
+    /// 6x votes, locked for 32 periods.
+    Locked6x,
+}
+
+impl Conviction {
+    /// The votes `balance` is worth with this conviction.
+    pub fn votes(self, balance: u128) -> u128 {
+        match self {
+            Conviction::None => balance / 10,
+            Conviction::Locked1x => balance,
+            Conviction::Locked2x => balance.saturating_mul(2),
+            Conviction::Locked3x => balance.saturating_mul(3),
+            Conviction::Locked4x => balance.saturating_mul(4),
+            Conviction::Locked5x => balance.saturating_mul(5),
+            Conviction::Locked6x => balance.saturating_mul(6),
+        }
+    }
+
+    /// The number of locking periods a vote stays locked after its referendum ends.
+    pub fn lock_periods(self) -> u32 {
+        match self {
+            Conviction::None => 0,
+            Conviction::Locked1x => 1,
+            Conviction::Locked2x => 2,
+            Conviction::Locked3x => 4,
+            Conviction::Locked4x => 8,
+            Conviction::Locked5x => 16,
+            Conviction::Locked6x => 32,
+        }
+    }
+}
+
+/// How a network decides by referendum.
+#[derive(
+    Clone,
+    Copy,
+    Encode,
+    Decode,
+    DecodeWithMemTracking,
+    Eq,
+    PartialEq,
+    RuntimeDebug,
+    TypeInfo,
+    MaxEncodedLen,
+)]
+pub struct GovernanceConfig<BlockNumber> {
+    /// Who votes.
+    pub electorate: Electorate,
+    /// How votes are weighted. Conviction requires the asset holder electorate.
+    pub scheme: VotingScheme,
+    /// The share of the electorate that must vote for a referendum to pass. Asset
+    /// holders are measured against the asset's total issuance.
+    pub quorum: Perbill,
+    /// How many blocks voting lasts. Must be non-zero.
+    pub voting_period: BlockNumber,
+}
+
+/// The running result of a referendum.
+#[derive(
+    Clone, Copy, Default, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen,
+)]
+pub struct Tally {
+    /// Votes in favour.
+    pub ayes: u128,
+    /// Votes against.
+    pub nays: u128,
+    /// The balance locked by voters, or the number of voting members.
+    pub turnout: u128,
+}
+
+/// A change a network referendum makes when it passes.
+#[derive(
+    Clone,
+    Encode,
+    Decode,
+    DecodeWithMemTracking,
+    Eq,
+    PartialEq,
+    RuntimeDebug,
+    TypeInfo,
+    MaxEncodedLen,
+)]
+pub enum NetworkAction {
+    /// Replace the metadata URI.
+    SetMetadataUri(BoundedVec<u8, ConstU32<256>>),
+    /// Replace the profile.
+    SetProfile(NetworkProfile),
+    /// Replace the stake, duration and pay-per-use price of an Access Gate resource.
+    SetResourcePricing {
+        /// The resource ID.
+        resource_id: u64,
+        /// Base stake amount.
+        base_stake: u128,
+        /// Base duration in blocks.
+        duration_blocks: u32,
+        /// Pay-per-use amount, if enabled.
+        ppu: Option<u128>,
+    },
+    /// Replace the service basket weights, in parts per million.
+    SetBasketWeights(BoundedVec<(BoundedVec<u8, ConstU32<32>>, u32), ConstU32<10>>),
+    /// Register an Access Gate resource.
+    RegisterResource(ResourceSpec),
+    /// Convert every price of the network to a new coin.
+    ConvertPrices(ConversionRate),
+}
+
+/// The rate at which prices move from one coin to another: `from` units of the old coin
+/// are worth `to` units of the new one.
+#[derive(
+    Clone,
+    Copy,
+    Encode,
+    Decode,
+    DecodeWithMemTracking,
+    Eq,
+    PartialEq,
+    RuntimeDebug,
+    TypeInfo,
+    MaxEncodedLen,
+)]
+pub struct ConversionRate {
+    /// Units of the old coin. Must be non-zero.
+    pub from: u128,
+    /// Units of the new coin. Must be non-zero.
+    pub to: u128,
+}
+
+impl ConversionRate {
+    /// Convert `amount` of the old coin, rounding down, or `None` on overflow.
+    pub fn convert(&self, amount: u128) -> Option<u128> {
+        sp_runtime::helpers_128bit::multiply_by_rational_with_rounding(
+            amount,
+            self.to,
+            self.from,
+            sp_runtime::Rounding::Down,
+        )
+    }
+}
+
+/// A pricing tier of a resource registered by [`NetworkAction::RegisterResource`].
+#[derive(
+    Clone,
+    Encode,
+    Decode,
+    DecodeWithMemTracking,
+    Eq,
+    PartialEq,
+    RuntimeDebug,
+    TypeInfo,
+    MaxEncodedLen,
+)]
+pub struct ResourceTier {
+    /// Name of the tier.
+    pub name: BoundedVec<u8, ConstU32<64>>,
+    /// Amount to stake for this tier.
+    pub stake: u128,
+    /// Duration in blocks for this tier.
+    pub duration_blocks: u32,
+}
+
+/// An Access Gate resource registered by [`NetworkAction::RegisterResource`].
+#[derive(
+    Clone,
+    Encode,
+    Decode,
+    DecodeWithMemTracking,
+    Eq,
+    PartialEq,
+    RuntimeDebug,
+    TypeInfo,
+    MaxEncodedLen,
+)]
+pub struct ResourceSpec {
+    /// Content identifier.
+    pub cid: BoundedVec<u8, ConstU32<256>>,
+    /// Resource kind.
+    pub kind: u8,
+    /// Base stake amount.
+    pub base_stake: u128,
+    /// Base duration in blocks.
+    pub duration_blocks: u32,
+    /// Pay-per-use amount, if enabled.
+    pub ppu: Option<u128>,
+    /// Tiered pricing.
+    pub tiers: BoundedVec<ResourceTier, ConstU32<10>>,
+}
+
+/// Network membership lookups for referenda.
+pub trait NetworkMembership<AccountId> {
+    /// Whether `who` is a member of network `network_id`.
+    fn is_member(network_id: u64, who: &AccountId) -> bool;
+
+    /// The number of accounts that are members of network `network_id`, by the same
+    /// rules as [`Self::is_member`].
+    fn member_count(network_id: u64) -> u32;
+}
+
+impl<AccountId> NetworkMembership<AccountId> for () {
+    fn is_member(_network_id: u64, _who: &AccountId) -> bool {
+        false
+    }
+
+    fn member_count(_network_id: u64) -> u32 {
+        0
+    }
+}
+
+/// Enacts network actions that change the settings of other pallets.
+pub trait NetworkActionHandler {
+    /// Enact `action` in network `network_id`.
+    ///
+    /// Returns `None` if this handler does not handle the action.
+    fn enact(network_id: u64, action: &NetworkAction) -> Option<DispatchResult>;
+
+    /// The most weight enacting `action` can take beyond the call that enacts it.
+    fn weight(_action: &NetworkAction) -> Weight {
+        Weight::zero()
+    }
+}
+
+impl NetworkActionHandler for () {
+    fn enact(_network_id: u64, _action: &NetworkAction) -> Option<DispatchResult> {
+        None
+    }
+}
+
+macro_rules! impl_network_action_handler_for_tuple {
+    ($($name:ident),+) => {
+        impl<$($name: NetworkActionHandler),+> NetworkActionHandler for ($($name,)+) {
+            fn enact(network_id: u64, action: &NetworkAction) -> Option<DispatchResult> {
+                $(
+                    if let Some(result) = $name::enact(network_id, action) {
+                        return Some(result);
+                    }
+                )+
+                None
+            }
+
+            fn weight(action: &NetworkAction) -> Weight {
+                Weight::zero()$(.saturating_add($name::weight(action)))+
+            }
+        }
+    };
+}
+
+impl_network_action_handler_for_tuple!(A);
+impl_network_action_handler_for_tuple!(A, B);
+impl_network_action_handler_for_tuple!(A, B, C);
+impl_network_action_handler_for_tuple!(A, B, C, D);
+
+/// Who owns a child network.
+#[derive(
+    Clone,
+    Copy,
+    Encode,
+    Decode,
+    DecodeWithMemTracking,
+    Eq,
+    PartialEq,
+    RuntimeDebug,
+    TypeInfo,
+    MaxEncodedLen,
+)]
+pub enum ChildOwner {
+    /// The parent network's treasury. The parent's roles apply to the child.
+    ParentTreasury,
+    /// The parent network admin creating the child.
+    Caller,
+}
+
+/// The parent of a child network.
+#[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct ParentLink {
+    /// The parent network ID.
+    pub parent: u64,
+    /// Whether members of the parent are members of the child.
+    pub inherit_membership: bool,
+}
+
+/// Who can join a network.
+#[derive(
+    Clone,
+    Copy,
+    Default,
+    Encode,
+    Decode,
+    DecodeWithMemTracking,
+    Eq,
+    PartialEq,
+    RuntimeDebug,
+    TypeInfo,
+    MaxEncodedLen,
+)]
+pub enum AdmissionMode {
+    /// Anyone can join.
+    #[default]
+    Open,
+    /// Only allowlisted accounts can join, added by admins or by redeeming an invite.
+    InviteOnly,
+    /// Like invite-only, and accounts can also ask admins to allowlist them.
+    RequestToJoin,
+}
+
+/// An invite code that adds whoever redeems it to a network's allowlist.
+#[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct Invite<BlockNumber> {
+    /// The block from which the invite can no longer be redeemed, if any.
+    pub expires_at: Option<BlockNumber>,
+    /// How many more times the invite can be redeemed.
+    pub uses_left: u32,
+}
+
+/// Removes the storage a pallet keeps for closed networks.
+pub trait NetworkCleanup {
+    /// Remove at most `limit` storage items of closed network `network_id`.
+    ///
+    /// Returns the number of items removed and whether nothing is left.
+    fn cleanup(network_id: u64, limit: u32) -> (u32, bool);
+}
+
+impl NetworkCleanup for () {
+    fn cleanup(_network_id: u64, _limit: u32) -> (u32, bool) {
+        (0, true)
+    }
+}
+
+macro_rules! impl_network_cleanup_for_tuple {
+    ($($name:ident),+) => {
+        impl<$($name: NetworkCleanup),+> NetworkCleanup for ($($name,)+) {
+            fn cleanup(network_id: u64, limit: u32) -> (u32, bool) {
+                let mut removed = 0u32;
+                $(
+                    let (count, done) = $name::cleanup(network_id, limit.saturating_sub(removed));
+                    removed = removed.saturating_add(count);
+                    if !done {
+                        return (removed, false);
+                    }
+                )+
+                (removed, true)
+            }
+        }
+    };
+}
+
+impl_network_cleanup_for_tuple!(A);
+impl_network_cleanup_for_tuple!(A, B);
+impl_network_cleanup_for_tuple!(A, B, C);
+impl_network_cleanup_for_tuple!(A, B, C, D);
+
+pub mod migrations;
+
 #[cfg(test)]
 mod mock;
 
@@ -38,20 +969,154 @@ mod tests;
 #[frame_support::pallet]
 pub mod pallet {
     use super::*;
-    use frame_support::PalletId;
+    use frame_support::{
+        traits::{
+            fungible::{self, MutateHold},
+            fungibles,
+            tokens::{Fortitude, Precision, Preservation},
+        },
+        PalletId,
+    };
     use frame_system::pallet_prelude::*;
-    use sp_runtime::traits::AccountIdConversion;
+    use sp_runtime::traits::{
+        AccountIdConversion, BlakeTwo256, CheckedAdd, CheckedMul, Hash, SaturatedConversion,
+        Saturating, Zero,
+    };
+
+    /// Balance type of network assets.
+    pub type AssetBalanceOf<T> = <<T as Config>::Assets as fungibles::Inspect<
+        <T as frame_system::Config>::AccountId,
+    >>::Balance;
+
+    /// Balance type of the native currency.
+    pub type BalanceOf<T> = <<T as Config>::Currency as fungible::Inspect<
+        <T as frame_system::Config>::AccountId,
+    >>::Balance;
+
+    /// Issuance configuration accepted at network creation.
+    pub type IssuanceConfigOf<T> = IssuanceConfig<
+        <T as frame_system::Config>::AccountId,
+        AssetBalanceOf<T>,
+        BlockNumberFor<T>,
+    >;
+
+    /// An amount paid from a network treasury.
+    pub type SpendAmountOf<T> = SpendAmount<BalanceOf<T>, AssetBalanceOf<T>>;
+
+    /// A treasury spend limit.
+    pub type SpendLimitOf<T> = SpendLimit<BalanceOf<T>, AssetBalanceOf<T>, BlockNumberFor<T>>;
+
+    /// A treasury spend period.
+    pub type SpendPeriodOf<T> = SpendPeriod<BalanceOf<T>, AssetBalanceOf<T>, BlockNumberFor<T>>;
+
+    /// The governance configuration of a network.
+    pub type GovernanceConfigOf<T> = GovernanceConfig<BlockNumberFor<T>>;
+
+    /// An invite to a network.
+    pub type InviteOf<T> = Invite<BlockNumberFor<T>>;
+
+    /// A bonding curve configuration.
+    pub type CurveConfigOf<T> = CurveConfig<AssetBalanceOf<T>>;
+
+    /// The configuration of a network's custom asset.
+    pub type AssetConfigOf<T> = AssetConfig<AssetBalanceOf<T>>;
+
+    /// The in-code storage version.
+    const STORAGE_VERSION: StorageVersion = StorageVersion::new(4);
 
     #[pallet::pallet]
+    #[pallet::storage_version(STORAGE_VERSION)]
     pub struct Pallet<T>(_);
 
     /// The pallet ID for deriving treasury accounts.
     const PALLET_ID: PalletId = PalletId(*b"navifact");
 
+    /// The pallet ID for deriving the account that holds locked votes.
+    const VOTING_PALLET_ID: PalletId = PalletId(*b"nf/votes");
+
+    /// How many taken asset IDs are skipped before asset creation gives up.
+    const MAX_ASSET_ID_PROBES: u32 = 16;
+
+    /// The most storage items removed for closed networks per block.
+    const MAX_CLEANUP_ITEMS: u64 = 512;
+
     #[pallet::config]
     pub trait Config: frame_system::Config {
         /// The overarching runtime event type.
         type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+        /// The assets used for network coins.
+        type Assets: fungibles::Inspect<Self::AccountId, AssetId = u32>
+            + fungibles::Create<Self::AccountId>
+            + fungibles::Mutate<Self::AccountId>
+            + fungibles::metadata::Mutate<Self::AccountId>;
+
+        /// The minimum balance of assets created by `create_network`.
+        #[pallet::constant]
+        type DefaultAssetMinBalance: Get<AssetBalanceOf<Self>>;
+
+        /// The maximum number of vesting schedules per network.
+        #[pallet::constant]
+        type MaxVestingSchedules: Get<u32>;
+
+        /// The overarching hold reason.
+        type RuntimeHoldReason: From<HoldReason>;
+
+        /// The native currency held by network treasuries and used for creation deposits.
+        type Currency: fungible::Mutate<Self::AccountId>
+            + fungible::MutateHold<Self::AccountId, Reason = Self::RuntimeHoldReason>;
+
+        /// The deposit held from the creator of a network until it is closed.
+        #[pallet::constant]
+        type NetworkDeposit: Get<BalanceOf<Self>>;
+
+        /// The maximum number of networks an account can own that are not closed.
+        #[pallet::constant]
+        type MaxNetworksPerAccount: Get<u32>;
+
+        /// Origin that can change the state of any network.
+        type GovernanceOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+        /// Removes the storage dependent pallets keep for closed networks.
+        type Cleanup: NetworkCleanup;
+
+        /// The maximum number of members of a network council.
+        #[pallet::constant]
+        type MaxCouncilMembers: Get<u32>;
+
+        /// The maximum number of open spend proposals per network.
+        #[pallet::constant]
+        type MaxSpendProposals: Get<u32>;
+
+        /// Network membership, for referenda decided by members.
+        type Members: NetworkMembership<Self::AccountId>;
+
+        /// Enacts referenda that change the settings of other pallets.
+        type ActionHandler: NetworkActionHandler;
+
+        /// The maximum number of ongoing referenda per network.
+        #[pallet::constant]
+        type MaxReferenda: Get<u32>;
+
+        /// How long one conviction period locks a vote after its referendum ends.
+        #[pallet::constant]
+        type VoteLockingPeriod: Get<BlockNumberFor<Self>>;
+
+        /// The maximum number of open child networks per network.
+        #[pallet::constant]
+        type MaxChildren: Get<u32>;
+
+        /// The maximum number of levels in a network hierarchy, counting the root.
+        #[pallet::constant]
+        type MaxDepth: Get<u32>;
+
+        /// The deposit held from the creator of a network template until it is removed.
+        #[pallet::constant]
+        type TemplateDeposit: Get<BalanceOf<Self>>;
+
+        /// The maximum number of resources a network template registers.
+        #[pallet::constant]
+        type MaxTemplateResources: Get<u32>;
     }
 
     /// Information about a network.
@@ -68,6 +1133,95 @@ pub mod pallet {
         pub treasury: T::AccountId,
         /// Metadata URI for the network.
         pub metadata_uri: BoundedVec<u8, ConstU32<256>>,
+        /// The lifecycle state of the network.
+        pub state: NetworkState,
+    }
+
+    /// A network council that approves treasury spends.
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    #[scale_info(skip_type_params(T))]
+    pub struct Council<T: Config> {
+        /// The council members.
+        pub members: BoundedVec<T::AccountId, T::MaxCouncilMembers>,
+        /// How many members must approve a spend.
+        pub threshold: u32,
+    }
+
+    /// A proposal to pay from a network treasury.
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    #[scale_info(skip_type_params(T))]
+    pub struct SpendProposal<T: Config> {
+        /// Who proposed the spend.
+        pub proposer: T::AccountId,
+        /// Who receives the payout.
+        pub beneficiary: T::AccountId,
+        /// The payout.
+        pub amount: SpendAmountOf<T>,
+        /// Council members that approved the spend.
+        pub approvals: BoundedVec<T::AccountId, T::MaxCouncilMembers>,
+    }
+
+    /// An ongoing network referendum.
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    #[scale_info(skip_type_params(T))]
+    pub struct Referendum<T: Config> {
+        /// Who submitted the referendum.
+        pub proposer: T::AccountId,
+        /// What the referendum enacts if it passes.
+        pub action: NetworkAction,
+        /// The governance configuration when the referendum was submitted.
+        pub config: GovernanceConfigOf<T>,
+        /// The block voting ends.
+        pub end: BlockNumberFor<T>,
+        /// The votes so far.
+        pub tally: Tally,
+        /// The size of the electorate when the referendum was submitted, which the quorum
+        /// is measured against.
+        pub electorate: u128,
+    }
+
+    /// A vote in a network referendum.
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    #[scale_info(skip_type_params(T))]
+    pub struct VoteRecord<T: Config> {
+        /// Whether the vote is in favour.
+        pub aye: bool,
+        /// The network asset locked by the vote.
+        pub balance: AssetBalanceOf<T>,
+        /// The block from which the vote can be unlocked.
+        pub unlock_at: BlockNumberFor<T>,
+    }
+
+    /// The bonding curve of a network asset.
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    #[scale_info(skip_type_params(T))]
+    pub struct BondingCurve<T: Config> {
+        /// The curve configuration.
+        pub config: CurveConfigOf<T>,
+        /// The number of steps sold and not sold back.
+        pub supply: u32,
+        /// The NAVI in the treasury backing the supply, kept out of treasury spends.
+        pub reserve: BalanceOf<T>,
+    }
+
+    /// A reusable bundle of network settings that `create_from_template` provisions.
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    #[scale_info(skip_type_params(T))]
+    pub struct NetworkTemplate<T: Config> {
+        /// Who published the template and can remove it.
+        pub creator: T::AccountId,
+        /// The deposit held from the creator.
+        pub deposit: BalanceOf<T>,
+        /// The asset minted by networks created from the template, or `None` to use NAVI.
+        pub asset: Option<AssetConfigOf<T>>,
+        /// The profile of networks created from the template.
+        pub profile: Option<NetworkProfile>,
+        /// How networks created from the template decide by referendum.
+        pub governance: Option<GovernanceConfigOf<T>>,
+        /// The Access Gate resources registered for networks created from the template.
+        pub resources: BoundedVec<ResourceSpec, T::MaxTemplateResources>,
+        /// The service basket weights, in parts per million. Empty to leave them unset.
+        pub basket_weights: BoundedVec<(BoundedVec<u8, ConstU32<32>>, u32), ConstU32<10>>,
     }
 
     /// Storage for the next network ID.
@@ -80,105 +1234,3337 @@ pub mod pallet {
     #[pallet::getter(fn networks)]
     pub type Networks<T: Config> = StorageMap<_, Blake2_128Concat, u64, NetworkInfo<T>>;
 
-    /// Events for the network factory pallet.
-    #[pallet::event]
-    #[pallet::generate_deposit(pub(super) fn deposit_event)]
-    pub enum Event<T: Config> {
-        /// A network was created.
-        NetworkCreated {
-            /// The network ID.
-            network_id: u64,
-            /// The owner of the network.
-            owner: T::AccountId,
-            /// Whether the network uses NAVI (true) or a custom asset (false).
-            use_navi: bool,
-            /// The asset ID if using a custom asset (u32 for compatibility with pallet_assets).
-            asset_id: Option<u32>,
-        },
-    }
+    /// The next candidate asset ID for network assets.
+    ///
+    /// IDs already taken by assets created outside the factory are skipped.
+    #[pallet::storage]
+    #[pallet::getter(fn next_asset_id)]
+    pub type NextAssetId<T> = StorageValue<_, u32, ValueQuery>;
 
-    /// Errors for the network factory pallet.
-    #[pallet::error]
-    pub enum Error<T> {
-        /// Network ID overflow.
-        NetworkIdOverflow,
-        /// Metadata URI too long.
-        MetadataUriTooLong,
-        /// Failed to create asset.
-        AssetCreationFailed,
-    }
+    /// The network that minted each network asset.
+    #[pallet::storage]
+    #[pallet::getter(fn asset_network)]
+    pub type AssetToNetwork<T> = StorageMap<_, Blake2_128Concat, u32, u64>;
 
-    #[pallet::call]
-    impl<T: Config> Pallet<T> {
-        /// Create a new network.
-        ///
-        /// # Parameters
-        /// - `use_navi`: If true, use NAVI token; if false, mint a new asset.
-        /// - `metadata_uri`: Metadata URI for the network (max 256 bytes).
-        #[pallet::call_index(0)]
-        #[pallet::weight(Weight::from_parts(10_000, 0))]
-        pub fn create_network(
-            origin: OriginFor<T>,
-            use_navi: bool,
-            metadata_uri: Vec<u8>,
-        ) -> DispatchResult {
-            let who = ensure_signed(origin)?;
+    /// Accounts proposed as new network owners, awaiting acceptance.
+    #[pallet::storage]
+    #[pallet::getter(fn pending_owner)]
+    pub type PendingOwners<T: Config> = StorageMap<_, Blake2_128Concat, u64, T::AccountId>;
 
-            // Get next network ID
-            let network_id = NextNetworkId::<T>::get();
-            let next_id = network_id
-                .checked_add(1)
-                .ok_or(Error::<T>::NetworkIdOverflow)?;
+    /// Roles other than owner, by network and account.
+    #[pallet::storage]
+    #[pallet::getter(fn role)]
+    pub type Roles<T: Config> =
+        StorageDoubleMap<_, Blake2_128Concat, u64, Blake2_128Concat, T::AccountId, NetworkRole>;
 
-            // Convert metadata_uri to BoundedVec
-            let bounded_uri: BoundedVec<u8, ConstU32<256>> = metadata_uri
-                .try_into()
-                .map_err(|_| Error::<T>::MetadataUriTooLong)?;
+    /// Reasons for this pallet placing a hold on funds.
+    #[pallet::composite_enum]
+    pub enum HoldReason {
+        /// The deposit backing a network.
+        NetworkDeposit,
+        /// The deposit backing a network template.
+        TemplateDeposit,
+    }
 
-            // Derive treasury account
-            let treasury = Self::treasury_account(network_id);
+    /// The creation deposit of each network that is not closed, and who paid it.
+    #[pallet::storage]
+    #[pallet::getter(fn network_deposit)]
+    pub type NetworkDeposits<T: Config> =
+        StorageMap<_, Blake2_128Concat, u64, (T::AccountId, BalanceOf<T>)>;
 
-            // Determine coin kind and asset ID
-            let (coin_kind, asset_id) = if use_navi {
-                (CoinKind::UseNavi, None)
-            } else {
-                // Create a new asset
-                let asset_id = network_id as u32;
+    /// How many networks that are not closed each account owns.
+    #[pallet::storage]
+    #[pallet::getter(fn owned_networks)]
+    pub type OwnedNetworks<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, u32, ValueQuery>;
 
-                // Note: In a real implementation, we would call pallet_assets::create here
-                // For now, we'll just record the asset_id
-                (CoinKind::MintAsset, Some(asset_id))
-            };
+    /// Closed networks whose storage is still being removed.
+    #[pallet::storage]
+    pub type PendingCleanup<T> = StorageMap<_, Blake2_128Concat, u64, ()>;
 
-            // Store network info
-            let network_info = NetworkInfo {
+    /// The public profile of each network.
+    #[pallet::storage]
+    #[pallet::getter(fn profile)]
+    pub type Profiles<T> = StorageMap<_, Blake2_128Concat, u64, NetworkProfile>;
+
+    /// How often each network's metadata URI or profile changed.
+    #[pallet::storage]
+    #[pallet::getter(fn metadata_version)]
+    pub type MetadataVersions<T> = StorageMap<_, Blake2_128Concat, u64, u32, ValueQuery>;
+
+    /// The issuance policy of each network asset.
+    #[pallet::storage]
+    #[pallet::getter(fn issuance)]
+    pub type Issuance<T: Config> =
+        StorageMap<_, Blake2_128Concat, u64, IssuancePolicy<AssetBalanceOf<T>, BlockNumberFor<T>>>;
+
+    /// Vesting schedules by network and beneficiary.
+    #[pallet::storage]
+    #[pallet::getter(fn vesting)]
+    pub type Vesting<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        u64,
+        Blake2_128Concat,
+        T::AccountId,
+        VestingSchedule<AssetBalanceOf<T>, BlockNumberFor<T>>,
+    >;
+
+    /// The council of each network that has one.
+    #[pallet::storage]
+    #[pallet::getter(fn council)]
+    pub type Councils<T: Config> = StorageMap<_, Blake2_128Concat, u64, Council<T>>;
+
+    /// The spend limit of each network treasury that has one.
+    #[pallet::storage]
+    #[pallet::getter(fn spend_limit)]
+    pub type SpendLimits<T: Config> = StorageMap<_, Blake2_128Concat, u64, SpendLimitOf<T>>;
+
+    /// What each limited network treasury paid out in the current period.
+    #[pallet::storage]
+    #[pallet::getter(fn spend_period)]
+    pub type SpendPeriods<T: Config> = StorageMap<_, Blake2_128Concat, u64, SpendPeriodOf<T>>;
+
+    /// The next spend proposal ID of each network.
+    #[pallet::storage]
+    pub type NextProposalId<T> = StorageMap<_, Blake2_128Concat, u64, u32, ValueQuery>;
+
+    /// Open spend proposals by network and proposal ID.
+    #[pallet::storage]
+    #[pallet::getter(fn proposal)]
+    pub type Proposals<T: Config> =
+        StorageDoubleMap<_, Blake2_128Concat, u64, Blake2_128Concat, u32, SpendProposal<T>>;
+
+    /// How many spend proposals each network has open.
+    #[pallet::storage]
+    pub type OpenProposals<T> = StorageMap<_, Blake2_128Concat, u64, u32, ValueQuery>;
+
+    /// The governance configuration of each network that decides by referendum.
+    #[pallet::storage]
+    #[pallet::getter(fn governance)]
+    pub type Governance<T: Config> = StorageMap<_, Blake2_128Concat, u64, GovernanceConfigOf<T>>;
+
+    /// The next referendum index of each network.
+    #[pallet::storage]
+    pub type NextReferendumIndex<T> = StorageMap<_, Blake2_128Concat, u64, u32, ValueQuery>;
+
+    /// Ongoing referenda by network and referendum index.
+    #[pallet::storage]
+    #[pallet::getter(fn referendum)]
+    pub type Referenda<T: Config> =
+        StorageDoubleMap<_, Blake2_128Concat, u64, Blake2_128Concat, u32, Referendum<T>>;
+
+    /// How many referenda each network has ongoing.
+    #[pallet::storage]
+    pub type OngoingReferenda<T> = StorageMap<_, Blake2_128Concat, u64, u32, ValueQuery>;
+
+    /// The parent of each child network.
+    #[pallet::storage]
+    #[pallet::getter(fn parent)]
+    pub type Parents<T> = StorageMap<_, Blake2_128Concat, u64, ParentLink>;
+
+    /// The open child networks of each network.
+    #[pallet::storage]
+    pub type Children<T> = StorageDoubleMap<_, Blake2_128Concat, u64, Blake2_128Concat, u64, ()>;
+
+    /// How many open child networks each network has.
+    #[pallet::storage]
+    pub type ChildCount<T> = StorageMap<_, Blake2_128Concat, u64, u32, ValueQuery>;
+
+    /// Networks by owner, closed ones included.
+    #[pallet::storage]
+    pub type NetworksByOwner<T: Config> =
+        StorageDoubleMap<_, Blake2_128Concat, T::AccountId, Blake2_128Concat, u64, ()>;
+
+    /// The next network template ID.
+    #[pallet::storage]
+    #[pallet::getter(fn next_template_id)]
+    pub type NextTemplateId<T> = StorageValue<_, u32, ValueQuery>;
+
+    /// Network templates by ID.
+    #[pallet::storage]
+    #[pallet::getter(fn template)]
+    pub type Templates<T: Config> = StorageMap<_, Blake2_128Concat, u32, NetworkTemplate<T>>;
+
+    /// Who can join each network. Networks without an entry are open.
+    #[pallet::storage]
+    #[pallet::getter(fn admission_mode)]
+    pub type Admission<T> = StorageMap<_, Blake2_128Concat, u64, AdmissionMode, ValueQuery>;
+
+    /// Accounts allowed to join networks that are not open.
+    #[pallet::storage]
+    #[pallet::getter(fn is_allowlisted)]
+    pub type Allowlist<T: Config> =
+        StorageDoubleMap<_, Blake2_128Concat, u64, Blake2_128Concat, T::AccountId, ()>;
+
+    /// Invites by network and the BLAKE2-256 hash of their code.
+    #[pallet::storage]
+    #[pallet::getter(fn invite)]
+    pub type Invites<T: Config> =
+        StorageDoubleMap<_, Blake2_128Concat, u64, Blake2_128Concat, [u8; 32], InviteOf<T>>;
+
+    /// Pending requests to join, by network and account, with the block they were made.
+    #[pallet::storage]
+    #[pallet::getter(fn join_request)]
+    pub type JoinRequests<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        u64,
+        Blake2_128Concat,
+        T::AccountId,
+        BlockNumberFor<T>,
+    >;
+
+    /// Networks by handle.
+    #[pallet::storage]
+    #[pallet::getter(fn handle_network)]
+    pub type Handles<T> = StorageMap<_, Blake2_128Concat, Handle, u64>;
+
+    /// The handle of each network that has one.
+    #[pallet::storage]
+    #[pallet::getter(fn handle_of)]
+    pub type NetworkHandles<T> = StorageMap<_, Blake2_128Concat, u64, Handle>;
+
+    /// The bonding curve of each network that has one.
+    #[pallet::storage]
+    #[pallet::getter(fn curve)]
+    pub type Curves<T: Config> = StorageMap<_, Blake2_128Concat, u64, BondingCurve<T>>;
+
+    /// Votes by network, referendum index and voter, kept until unlocked.
+    #[pallet::storage]
+    #[pallet::getter(fn vote_of)]
+    pub type Votes<T: Config> = StorageNMap<
+        _,
+        (
+            NMapKey<Blake2_128Concat, u64>,
+            NMapKey<Blake2_128Concat, u32>,
+            NMapKey<Blake2_128Concat, T::AccountId>,
+        ),
+        VoteRecord<T>,
+    >;
+
+    /// Events for the network factory pallet.
+    #[pallet::event]
+    #[pallet::generate_deposit(pub(super) fn deposit_event)]
+    pub enum Event<T: Config> {
+        /// A network was created.
+        NetworkCreated {
+            /// The network ID.
+            network_id: u64,
+            /// The owner of the network.
+            owner: T::AccountId,
+            /// Whether the network uses NAVI (true) or a custom asset (false).
+            use_navi: bool,
+            /// The asset ID if using a custom asset (u32 for compatibility with pallet_assets).
+            asset_id: Option<u32>,
+        },
+        /// Network coins were minted.
+        Minted {
+            /// The network ID.
+            network_id: u64,
+            /// The network asset.
+            asset_id: u32,
+            /// Who received the coins.
+            beneficiary: T::AccountId,
+            /// The amount minted.
+            amount: AssetBalanceOf<T>,
+            /// Why the coins were minted.
+            reason: MintReason,
+        },
+        /// A new network owner was proposed.
+        OwnershipProposed {
+            /// The network ID.
+            network_id: u64,
+            /// The proposed owner.
+            new_owner: T::AccountId,
+        },
+        /// Network ownership was transferred.
+        OwnershipTransferred {
+            /// The network ID.
+            network_id: u64,
+            /// The previous owner.
+            from: T::AccountId,
+            /// The new owner.
+            to: T::AccountId,
+        },
+        /// A role was granted.
+        RoleGranted {
+            /// The network ID.
+            network_id: u64,
+            /// Who received the role.
+            who: T::AccountId,
+            /// The role.
+            role: NetworkRole,
+        },
+        /// A role was revoked.
+        RoleRevoked {
+            /// The network ID.
+            network_id: u64,
+            /// Who lost the role.
+            who: T::AccountId,
+            /// The role.
+            role: NetworkRole,
+        },
+        /// The metadata URI of a network changed.
+        MetadataUriUpdated {
+            /// The network ID.
+            network_id: u64,
+            /// The new metadata version.
+            version: u32,
+        },
+        /// The profile of a network changed.
+        ProfileUpdated {
+            /// The network ID.
+            network_id: u64,
+            /// The new metadata version.
+            version: u32,
+        },
+        /// The lifecycle state of a network changed.
+        NetworkStateChanged {
+            /// The network ID.
+            network_id: u64,
+            /// The previous state.
+            from: NetworkState,
+            /// The new state.
+            to: NetworkState,
+        },
+        /// The treasury of a closed network was drained.
+        TreasuryDrained {
+            /// The network ID.
+            network_id: u64,
+            /// What happened to the treasury.
+            policy: TreasuryPolicy,
+            /// The native amount drained.
+            native: BalanceOf<T>,
+            /// The network asset amount drained.
+            asset: AssetBalanceOf<T>,
+        },
+        /// All storage of a closed network was removed.
+        CleanupCompleted {
+            /// The network ID.
+            network_id: u64,
+        },
+        /// The council of a network changed.
+        CouncilSet {
+            /// The network ID.
+            network_id: u64,
+            /// The council members. Empty if the council was removed.
+            members: BoundedVec<T::AccountId, T::MaxCouncilMembers>,
+            /// How many members must approve a spend.
+            threshold: u32,
+        },
+        /// The spend limit of a network treasury changed.
+        SpendLimitSet {
+            /// The network ID.
+            network_id: u64,
+            /// The new limit, if any.
+            limit: Option<SpendLimitOf<T>>,
+        },
+        /// A treasury spend was proposed.
+        SpendProposed {
+            /// The network ID.
+            network_id: u64,
+            /// The proposal ID.
+            proposal_id: u32,
+            /// Who proposed the spend.
+            proposer: T::AccountId,
+            /// Who receives the payout.
+            beneficiary: T::AccountId,
+            /// The payout.
+            amount: SpendAmountOf<T>,
+        },
+        /// A council member approved a treasury spend.
+        SpendApproved {
+            /// The network ID.
+            network_id: u64,
+            /// The proposal ID.
+            proposal_id: u32,
+            /// Who approved.
+            who: T::AccountId,
+            /// How many council members approved so far.
+            approvals: u32,
+        },
+        /// A treasury spend was paid out.
+        SpendExecuted {
+            /// The network ID.
+            network_id: u64,
+            /// The proposal ID.
+            proposal_id: u32,
+            /// Who received the payout.
+            beneficiary: T::AccountId,
+            /// The payout.
+            amount: SpendAmountOf<T>,
+        },
+        /// A treasury spend was rejected or withdrawn.
+        SpendRejected {
+            /// The network ID.
+            network_id: u64,
+            /// The proposal ID.
+            proposal_id: u32,
+        },
+        /// The governance configuration of a network changed.
+        GovernanceSet {
+            /// The network ID.
+            network_id: u64,
+            /// The new configuration, if any.
+            config: Option<GovernanceConfigOf<T>>,
+        },
+        /// A referendum was submitted.
+        ReferendumSubmitted {
+            /// The network ID.
+            network_id: u64,
+            /// The referendum index.
+            index: u32,
+            /// Who submitted the referendum.
+            proposer: T::AccountId,
+            /// What the referendum enacts if it passes.
+            action: NetworkAction,
+            /// The block voting ends.
+            end: BlockNumberFor<T>,
+        },
+        /// A vote was cast in a referendum.
+        Voted {
+            /// The network ID.
+            network_id: u64,
+            /// The referendum index.
+            index: u32,
+            /// The voter.
+            who: T::AccountId,
+            /// Whether the vote is in favour.
+            aye: bool,
+            /// The votes cast.
+            votes: u128,
+        },
+        /// A referendum passed and its action was enacted.
+        ReferendumPassed {
+            /// The network ID.
+            network_id: u64,
+            /// The referendum index.
+            index: u32,
+            /// The result of enacting the action.
+            result: DispatchResult,
+        },
+        /// A referendum was rejected or missed its quorum.
+        ReferendumRejected {
+            /// The network ID.
+            network_id: u64,
+            /// The referendum index.
+            index: u32,
+        },
+        /// A child network was created.
+        ChildNetworkCreated {
+            /// The parent network ID.
+            parent_id: u64,
+            /// The child network ID.
+            network_id: u64,
+            /// Whether members of the parent are members of the child.
+            inherit_membership: bool,
+        },
+        /// A vote was unlocked and its balance returned.
+        VoteUnlocked {
+            /// The network ID.
+            network_id: u64,
+            /// The referendum index.
+            index: u32,
+            /// The voter.
+            who: T::AccountId,
+            /// The network asset returned.
+            balance: AssetBalanceOf<T>,
+        },
+        /// A network template was published.
+        TemplateCreated {
+            /// The template ID.
+            template_id: u32,
+            /// Who published the template.
+            creator: T::AccountId,
+        },
+        /// A network template was removed.
+        TemplateRemoved {
+            /// The template ID.
+            template_id: u32,
+        },
+        /// A network was provisioned from a template.
+        NetworkProvisioned {
+            /// The network ID.
+            network_id: u64,
+            /// The template ID.
+            template_id: u32,
+        },
+        /// Who can join a network changed.
+        AdmissionModeSet {
+            /// The network ID.
+            network_id: u64,
+            /// The new admission mode.
+            mode: AdmissionMode,
+        },
+        /// An account was added to a network's allowlist.
+        Allowlisted {
+            /// The network ID.
+            network_id: u64,
+            /// The account.
+            who: T::AccountId,
+        },
+        /// An account was removed from a network's allowlist.
+        RemovedFromAllowlist {
+            /// The network ID.
+            network_id: u64,
+            /// The account.
+            who: T::AccountId,
+        },
+        /// An invite was created.
+        InviteCreated {
+            /// The network ID.
+            network_id: u64,
+            /// The hash of the invite code.
+            code_hash: [u8; 32],
+            /// The block from which the invite can no longer be redeemed, if any.
+            expires_at: Option<BlockNumberFor<T>>,
+            /// How many times the invite can be redeemed.
+            max_uses: u32,
+        },
+        /// An invite was revoked.
+        InviteRevoked {
+            /// The network ID.
+            network_id: u64,
+            /// The hash of the invite code.
+            code_hash: [u8; 32],
+        },
+        /// An invite was redeemed.
+        InviteRedeemed {
+            /// The network ID.
+            network_id: u64,
+            /// The hash of the invite code.
+            code_hash: [u8; 32],
+            /// Who redeemed the invite.
+            who: T::AccountId,
+        },
+        /// An account asked to join a network.
+        JoinRequested {
+            /// The network ID.
+            network_id: u64,
+            /// The account.
+            who: T::AccountId,
+        },
+        /// A request to join was approved or rejected.
+        JoinRequestDecided {
+            /// The network ID.
+            network_id: u64,
+            /// The account.
+            who: T::AccountId,
+            /// Whether the account was allowlisted.
+            approved: bool,
+        },
+        /// Network coins were bought from the bonding curve.
+        CurveBought {
+            /// The network ID.
+            network_id: u64,
+            /// The buyer.
+            who: T::AccountId,
+            /// The network asset minted.
+            amount: AssetBalanceOf<T>,
+            /// The NAVI paid into the reserve.
+            cost: BalanceOf<T>,
+        },
+        /// Network coins were sold back to the bonding curve.
+        CurveSold {
+            /// The network ID.
+            network_id: u64,
+            /// The seller.
+            who: T::AccountId,
+            /// The network asset burned.
+            amount: AssetBalanceOf<T>,
+            /// The NAVI paid out of the reserve.
+            proceeds: BalanceOf<T>,
+        },
+        /// A network moved to another coin.
+        CoinMigrated {
+            /// The network ID.
+            network_id: u64,
+            /// The coin the network used.
+            from: CoinKind,
+            /// The coin the network uses now.
+            to: CoinKind,
+            /// The network asset, if the network has one.
+            asset_id: Option<u32>,
+            /// The rate prices were converted at.
+            rate: ConversionRate,
+        },
+        /// A network took a handle.
+        HandleSet {
+            /// The network ID.
+            network_id: u64,
+            /// The handle.
+            handle: Handle,
+        },
+        /// A network gave up its handle.
+        HandleReleased {
+            /// The network ID.
+            network_id: u64,
+            /// The handle.
+            handle: Handle,
+        },
+    }
+
+    /// Errors for the network factory pallet.
+    #[pallet::error]
+    pub enum Error<T> {
+        /// Network ID overflow.
+        NetworkIdOverflow,
+        /// Metadata URI too long.
+        MetadataUriTooLong,
+        /// Failed to create asset.
+        AssetCreationFailed,
+        /// No free asset ID could be allocated.
+        AssetIdUnavailable,
+        /// The allocations exceed the maximum supply.
+        SupplyCapExceeded,
+        /// Too many vesting schedules.
+        TooManyVestingSchedules,
+        /// A vesting schedule has a zero duration, a cliff longer than its duration, or a
+        /// beneficiary that already has a schedule.
+        InvalidVestingSchedule,
+        /// The network has no issuance policy.
+        NoIssuancePolicy,
+        /// The caller has no vesting schedule in the network.
+        NoVestingSchedule,
+        /// Nothing can be claimed yet.
+        NothingToClaim,
+        /// No inflation is due.
+        NothingToMint,
+        /// Failed to mint network coins.
+        MintFailed,
+        /// Network not found.
+        NetworkNotFound,
+        /// The caller lacks the network role required.
+        NotAuthorized,
+        /// No ownership transfer is pending.
+        NoPendingTransfer,
+        /// The caller is not the proposed owner.
+        NotPendingOwner,
+        /// The owner role can only be moved by ownership transfer.
+        InvalidRole,
+        /// The account holds no role in the network.
+        RoleNotFound,
+        /// The network is closed.
+        NetworkClosed,
+        /// The network is already in the requested state, or the state can only be
+        /// reached by closing.
+        InvalidStateTransition,
+        /// The account owns the maximum number of networks.
+        TooManyNetworks,
+        /// The network is not active.
+        NetworkNotActive,
+        /// The council threshold is zero or above the number of members.
+        InvalidCouncil,
+        /// Too many council members.
+        TooManyCouncilMembers,
+        /// The spend limit period is zero.
+        InvalidSpendLimit,
+        /// The spend amount is zero or in an asset the network does not have.
+        InvalidSpend,
+        /// The network has the maximum number of open spend proposals.
+        TooManySpendProposals,
+        /// Spend proposal ID overflow.
+        ProposalIdOverflow,
+        /// Spend proposal not found.
+        ProposalNotFound,
+        /// The caller is neither the owner nor a council member.
+        NotCouncilMember,
+        /// The caller already approved the spend.
+        AlreadyApproved,
+        /// The spend exceeds what the treasury can pay out this period.
+        SpendLimitExceeded,
+        /// The network does not decide by referendum.
+        NoGovernance,
+        /// The voting period is zero, the electorate holds an asset the network does not
+        /// have, or conviction voting is used without the asset holder electorate.
+        InvalidGovernance,
+        /// The network has the maximum number of ongoing referenda.
+        TooManyReferenda,
+        /// Referendum index overflow.
+        ReferendumIndexOverflow,
+        /// Referendum not found.
+        ReferendumNotFound,
+        /// The caller is not part of the network's electorate.
+        NotInElectorate,
+        /// The caller already voted in the referendum.
+        AlreadyVoted,
+        /// Asset holders must lock a non-zero balance to vote.
+        InvalidVote,
+        /// Voting in the referendum has ended.
+        VotingClosed,
+        /// Voting in the referendum has not ended yet.
+        VotingOngoing,
+        /// The account has no vote in the referendum.
+        NoVote,
+        /// The vote is still locked.
+        VoteLocked,
+        /// No pallet enacts the action.
+        UnsupportedAction,
+        /// The network has the maximum number of open child networks.
+        TooManyChildren,
+        /// The child network would be nested too deep.
+        HierarchyTooDeep,
+        /// The network has child networks that are not closed.
+        HasOpenChildren,
+        /// Network template ID overflow.
+        TemplateIdOverflow,
+        /// The template registers too many resources.
+        TooManyTemplateResources,
+        /// Network template not found.
+        TemplateNotFound,
+        /// The caller did not publish the template.
+        NotTemplateCreator,
+        /// Invites must be redeemable at least once and not expire in the past.
+        InvalidInvite,
+        /// An invite with this code already exists.
+        InviteExists,
+        /// No invite matches the code.
+        InviteNotFound,
+        /// The invite has expired.
+        InviteExpired,
+        /// The account is already allowed to join the network.
+        AlreadyAllowlisted,
+        /// The account is not on the network's allowlist.
+        NotAllowlisted,
+        /// The network does not take requests to join.
+        RequestsNotAccepted,
+        /// The account already asked to join the network.
+        AlreadyRequested,
+        /// No request to join found.
+        JoinRequestNotFound,
+        /// The bonding curve has a zero step or initial price.
+        InvalidCurve,
+        /// The network has no bonding curve.
+        NoCurve,
+        /// Trades must be at least one step.
+        InvalidTrade,
+        /// The trade is too large for the bonding curve to price.
+        CurveOverflow,
+        /// The trade costs more, or returns less, than the caller accepts.
+        SlippageExceeded,
+        /// Fewer steps are sold than the caller wants to sell back.
+        InsufficientCurveSupply,
+        /// The spend would use NAVI backing the bonding curve.
+        ReserveLocked,
+        /// Network coins bought from the bonding curve have not all been sold back.
+        CurveOutstanding,
+        /// The network already uses this coin.
+        CoinUnchanged,
+        /// Child networks use their parent's coin.
+        CoinInherited,
+        /// The conversion rate has a zero side.
+        InvalidRate,
+        /// Moving to a network coin needs an asset unless the network already has one.
+        AssetRequired,
+        /// An asset was given for a network that has one or moves to NAVI.
+        AssetExists,
+        /// Handles are 3 to 32 lowercase letters, digits and single inner hyphens.
+        InvalidHandle,
+        /// Another network has this handle.
+        HandleTaken,
+        /// The network has no handle.
+        NoHandle,
+    }
+
+    #[pallet::call]
+    impl<T: Config> Pallet<T> {
+        /// Create a new network.
+        ///
+        /// Networks that mint their own asset get one administered by the network treasury,
+        /// with no metadata and [`Config::DefaultAssetMinBalance`]. Use
+        /// `create_network_with_asset` to configure the asset.
+        ///
+        /// # Parameters
+        /// - `use_navi`: If true, use NAVI token; if false, mint a new asset.
+        /// - `metadata_uri`: Metadata URI for the network (max 256 bytes).
+        #[pallet::call_index(0)]
+        #[pallet::weight(Weight::from_parts(10_000, 0))]
+        pub fn create_network(
+            origin: OriginFor<T>,
+            use_navi: bool,
+            metadata_uri: Vec<u8>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let asset = (!use_navi).then(Self::default_asset);
+            Self::do_create_network(who, metadata_uri, asset.map(|asset| (asset, None)))?;
+            Ok(())
+        }
+
+        /// Create a new network that mints its own asset.
+        ///
+        /// The initial allocations are minted immediately. Vesting schedules start at the
+        /// current block. When the treasury administers the asset the factory is its only
+        /// issuer, so the maximum supply holds for the asset as a whole.
+        ///
+        /// # Parameters
+        /// - `metadata_uri`: Metadata URI for the network (max 256 bytes).
+        /// - `asset`: Name, symbol, decimals, minimum balance and admin of the asset.
+        /// - `issuance`: Supply cap, allocations, vesting schedules and inflation.
+        #[pallet::call_index(1)]
+        #[pallet::weight(Weight::from_parts(10_000, 0))]
+        pub fn create_network_with_asset(
+            origin: OriginFor<T>,
+            metadata_uri: Vec<u8>,
+            asset: AssetConfigOf<T>,
+            issuance: IssuanceConfigOf<T>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            Self::do_create_network(who, metadata_uri, Some((asset, Some(issuance))))?;
+            Ok(())
+        }
+
+        /// Claim the vested part of the caller's vesting schedule in a network.
+        #[pallet::call_index(2)]
+        #[pallet::weight(Weight::from_parts(10_000, 0))]
+        pub fn claim_vested(origin: OriginFor<T>, network_id: u64) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let mut schedule =
+                Vesting::<T>::get(network_id, &who).ok_or(Error::<T>::NoVestingSchedule)?;
+            let now = frame_system::Pallet::<T>::block_number();
+            let claimable = schedule.vested_at(now).saturating_sub(schedule.claimed);
+            ensure!(!claimable.is_zero(), Error::<T>::NothingToClaim);
+
+            Issuance::<T>::try_mutate(network_id, |policy| -> DispatchResult {
+                let policy = policy.as_mut().ok_or(Error::<T>::NoIssuancePolicy)?;
+                policy.unvested = policy.unvested.saturating_sub(claimable);
+                Ok(())
+            })?;
+            Self::mint(network_id, &who, claimable, MintReason::Vesting)?;
+
+            schedule.claimed = schedule.claimed.saturating_add(claimable);
+            if schedule.claimed >= schedule.total {
+                Vesting::<T>::remove(network_id, &who);
+            } else {
+                Vesting::<T>::insert(network_id, &who, schedule);
+            }
+
+            Ok(())
+        }
+
+        /// Mint the inflation accrued since the last mint to the network treasury.
+        ///
+        /// Anyone can trigger this while the network is active, and inflation only accrues
+        /// while it is. Inflation stops at the maximum supply, keeping room for unclaimed
+        /// vesting schedules.
+        #[pallet::call_index(3)]
+        #[pallet::weight(Weight::from_parts(10_000, 0))]
+        pub fn mint_inflation(origin: OriginFor<T>, network_id: u64) -> DispatchResult {
+            ensure_signed(origin)?;
+
+            let network = Networks::<T>::get(network_id).ok_or(Error::<T>::NetworkNotFound)?;
+            ensure!(
+                network.state == NetworkState::Active,
+                Error::<T>::NetworkNotActive
+            );
+            let asset_id = network.asset_id.ok_or(Error::<T>::NoIssuancePolicy)?;
+            let mut policy = Issuance::<T>::get(network_id).ok_or(Error::<T>::NoIssuancePolicy)?;
+
+            let now = frame_system::Pallet::<T>::block_number();
+            let blocks: u128 = now
+                .saturating_sub(policy.inflation_minted_at)
+                .saturated_into();
+            let mut amount: AssetBalanceOf<T> = policy
+                .inflation_per_block
+                .saturated_into::<u128>()
+                .saturating_mul(blocks)
+                .saturated_into();
+            if let Some(max_supply) = policy.max_supply {
+                let committed =
+                    <T::Assets as fungibles::Inspect<T::AccountId>>::total_issuance(asset_id)
+                        .saturating_add(policy.unvested);
+                amount = amount.min(max_supply.saturating_sub(committed));
+            }
+            ensure!(!amount.is_zero(), Error::<T>::NothingToMint);
+
+            policy.inflation_minted_at = now;
+            Issuance::<T>::insert(network_id, policy);
+            Self::mint(network_id, &network.treasury, amount, MintReason::Inflation)
+        }
+
+        /// Propose `new_owner` as the network owner. They become owner once they accept.
+        ///
+        /// Only the owner can propose. A new proposal replaces a pending one.
+        #[pallet::call_index(4)]
+        #[pallet::weight(Weight::from_parts(10_000, 0))]
+        pub fn propose_owner(
+            origin: OriginFor<T>,
+            network_id: u64,
+            new_owner: T::AccountId,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            Self::ensure_role(network_id, &who, NetworkRole::Owner)?;
+            PendingOwners::<T>::insert(network_id, &new_owner);
+
+            Self::deposit_event(Event::OwnershipProposed {
+                network_id,
+                new_owner,
+            });
+
+            Ok(())
+        }
+
+        /// Accept a proposed ownership transfer.
+        ///
+        /// Any other role the new owner held is dropped. The creation deposit stays with
+        /// whoever paid it.
+        #[pallet::call_index(5)]
+        #[pallet::weight(Weight::from_parts(10_000, 0))]
+        pub fn accept_ownership(origin: OriginFor<T>, network_id: u64) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let pending =
+                PendingOwners::<T>::get(network_id).ok_or(Error::<T>::NoPendingTransfer)?;
+            ensure!(pending == who, Error::<T>::NotPendingOwner);
+
+            let from = Networks::<T>::try_mutate(network_id, |network| {
+                let network = network.as_mut().ok_or(Error::<T>::NetworkNotFound)?;
+                Ok::<_, DispatchError>(core::mem::replace(&mut network.owner, who.clone()))
+            })?;
+            Self::inc_owned_networks(&who)?;
+            OwnedNetworks::<T>::mutate(&from, |count| *count = count.saturating_sub(1));
+            NetworksByOwner::<T>::remove(&from, network_id);
+            NetworksByOwner::<T>::insert(&who, network_id, ());
+            PendingOwners::<T>::remove(network_id);
+            Roles::<T>::remove(network_id, &who);
+
+            Self::deposit_event(Event::OwnershipTransferred {
+                network_id,
+                from,
+                to: who,
+            });
+
+            Ok(())
+        }
+
+        /// Grant `role` to `who`, replacing any role they hold.
+        ///
+        /// The owner can grant any role but owner. Admins can grant moderator and
+        /// billing manager, but cannot change the role of other admins.
+        #[pallet::call_index(6)]
+        #[pallet::weight(Weight::from_parts(10_000, 0))]
+        pub fn grant_role(
+            origin: OriginFor<T>,
+            network_id: u64,
+            who: T::AccountId,
+            role: NetworkRole,
+        ) -> DispatchResult {
+            let granter = ensure_signed(origin)?;
+
+            ensure!(role != NetworkRole::Owner, Error::<T>::InvalidRole);
+            let network = Networks::<T>::get(network_id).ok_or(Error::<T>::NetworkNotFound)?;
+            ensure!(network.owner != who, Error::<T>::InvalidRole);
+            Self::ensure_can_manage(network_id, &granter, role)?;
+            if let Some(current) = Roles::<T>::get(network_id, &who) {
+                Self::ensure_can_manage(network_id, &granter, current)?;
+            }
+
+            Roles::<T>::insert(network_id, &who, role);
+
+            Self::deposit_event(Event::RoleGranted {
+                network_id,
+                who,
+                role,
+            });
+
+            Ok(())
+        }
+
+        /// Revoke the role `who` holds.
+        ///
+        /// Accounts can always renounce their own role. Otherwise the same rules as for
+        /// `grant_role` apply.
+        #[pallet::call_index(7)]
+        #[pallet::weight(Weight::from_parts(10_000, 0))]
+        pub fn revoke_role(
+            origin: OriginFor<T>,
+            network_id: u64,
+            who: T::AccountId,
+        ) -> DispatchResult {
+            let revoker = ensure_signed(origin)?;
+
+            let role = Roles::<T>::get(network_id, &who).ok_or(Error::<T>::RoleNotFound)?;
+            if revoker != who {
+                Self::ensure_can_manage(network_id, &revoker, role)?;
+            }
+
+            Roles::<T>::remove(network_id, &who);
+
+            Self::deposit_event(Event::RoleRevoked {
+                network_id,
+                who,
+                role,
+            });
+
+            Ok(())
+        }
+
+        /// Replace the metadata URI of a network.
+        ///
+        /// Only network admins can update metadata.
+        #[pallet::call_index(8)]
+        #[pallet::weight(Weight::from_parts(10_000, 0))]
+        pub fn set_metadata_uri(
+            origin: OriginFor<T>,
+            network_id: u64,
+            metadata_uri: Vec<u8>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            Self::ensure_role(network_id, &who, NetworkRole::Admin)?;
+            let bounded_uri: BoundedVec<u8, ConstU32<256>> = metadata_uri
+                .try_into()
+                .map_err(|_| Error::<T>::MetadataUriTooLong)?;
+            Self::do_set_metadata_uri(network_id, bounded_uri);
+
+            Ok(())
+        }
+
+        /// Replace the profile of a network.
+        ///
+        /// Only network admins can update the profile.
+        #[pallet::call_index(9)]
+        #[pallet::weight(Weight::from_parts(10_000, 0))]
+        pub fn set_profile(
+            origin: OriginFor<T>,
+            network_id: u64,
+            profile: NetworkProfile,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            Self::ensure_role(network_id, &who, NetworkRole::Admin)?;
+            Self::do_set_profile(network_id, profile);
+
+            Ok(())
+        }
+
+        /// Move a network between the active, paused and archived states.
+        ///
+        /// No inflation accrues while a network is not active. The origin must be the
+        /// network owner or the governance origin.
+        #[pallet::call_index(10)]
+        #[pallet::weight(Weight::from_parts(10_000, 0))]
+        pub fn set_network_state(
+            origin: OriginFor<T>,
+            network_id: u64,
+            state: NetworkState,
+        ) -> DispatchResult {
+            Self::ensure_owner_or_governance(origin, network_id)?;
+
+            let from = Networks::<T>::try_mutate(network_id, |network| {
+                let network = network.as_mut().ok_or(Error::<T>::NetworkNotFound)?;
+                ensure!(
+                    network.state != NetworkState::Closed,
+                    Error::<T>::NetworkClosed
+                );
+                ensure!(
+                    state != NetworkState::Closed && state != network.state,
+                    Error::<T>::InvalidStateTransition
+                );
+                Ok::<_, DispatchError>(core::mem::replace(&mut network.state, state))
+            })?;
+            if state == NetworkState::Active {
+                Issuance::<T>::mutate(network_id, |policy| {
+                    if let Some(policy) = policy {
+                        policy.inflation_minted_at = frame_system::Pallet::<T>::block_number();
+                    }
+                });
+            }
+
+            Self::deposit_event(Event::NetworkStateChanged {
+                network_id,
+                from,
+                to: state,
+            });
+
+            Ok(())
+        }
+
+        /// Close a network for good.
+        ///
+        /// The creation deposit is refunded, the treasury is drained according to `policy`,
+        /// roles, pending transfers, profile, issuance policy, treasury and governance
+        /// settings are removed, the handle is freed, and the remaining storage is removed
+        /// in `on_idle`. Votes in the network's referenda can be unlocked right away.
+        /// Networks with open child networks or with coins outstanding on their bonding
+        /// curve cannot be closed. The origin must be the network owner or the governance
+        /// origin.
+        #[pallet::call_index(11)]
+        #[pallet::weight(Weight::from_parts(10_000, 0))]
+        pub fn close_network(
+            origin: OriginFor<T>,
+            network_id: u64,
+            policy: TreasuryPolicy,
+        ) -> DispatchResult {
+            Self::ensure_owner_or_governance(origin, network_id)?;
+
+            let mut network = Networks::<T>::get(network_id).ok_or(Error::<T>::NetworkNotFound)?;
+            ensure!(
+                network.state != NetworkState::Closed,
+                Error::<T>::NetworkClosed
+            );
+            ensure!(
+                ChildCount::<T>::get(network_id) == 0,
+                Error::<T>::HasOpenChildren
+            );
+            ensure!(
+                Curves::<T>::get(network_id).map_or(true, |curve| curve.supply == 0),
+                Error::<T>::CurveOutstanding
+            );
+
+            Self::drain_treasury(network_id, &network, policy)?;
+
+            if let Some((depositor, deposit)) = NetworkDeposits::<T>::take(network_id) {
+                T::Currency::release(
+                    &HoldReason::NetworkDeposit.into(),
+                    &depositor,
+                    deposit,
+                    Precision::BestEffort,
+                )?;
+            }
+            OwnedNetworks::<T>::mutate(&network.owner, |count| *count = count.saturating_sub(1));
+
+            let from = core::mem::replace(&mut network.state, NetworkState::Closed);
+            Networks::<T>::insert(network_id, network);
+            PendingOwners::<T>::remove(network_id);
+            Profiles::<T>::remove(network_id);
+            MetadataVersions::<T>::remove(network_id);
+            Issuance::<T>::remove(network_id);
+            Councils::<T>::remove(network_id);
+            SpendLimits::<T>::remove(network_id);
+            SpendPeriods::<T>::remove(network_id);
+            NextProposalId::<T>::remove(network_id);
+            OpenProposals::<T>::remove(network_id);
+            Governance::<T>::remove(network_id);
+            NextReferendumIndex::<T>::remove(network_id);
+            OngoingReferenda::<T>::remove(network_id);
+            Admission::<T>::remove(network_id);
+            Curves::<T>::remove(network_id);
+            Self::release_handle(network_id);
+            if let Some(link) = Parents::<T>::get(network_id) {
+                Children::<T>::remove(link.parent, network_id);
+                ChildCount::<T>::mutate(link.parent, |count| *count = count.saturating_sub(1));
+            }
+            PendingCleanup::<T>::insert(network_id, ());
+
+            Self::deposit_event(Event::NetworkStateChanged {
+                network_id,
+                from,
+                to: NetworkState::Closed,
+            });
+
+            Ok(())
+        }
+
+        /// Replace the council that approves treasury spends of a network.
+        ///
+        /// Only the owner can set the council. An empty `members` removes it, leaving
+        /// approval to the owner alone.
+        #[pallet::call_index(12)]
+        #[pallet::weight(Weight::from_parts(10_000, 0))]
+        pub fn set_council(
+            origin: OriginFor<T>,
+            network_id: u64,
+            members: Vec<T::AccountId>,
+            threshold: u32,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            Self::ensure_role(network_id, &who, NetworkRole::Owner)?;
+            let mut members = members;
+            members.sort();
+            members.dedup();
+            let members: BoundedVec<T::AccountId, T::MaxCouncilMembers> = members
+                .try_into()
+                .map_err(|_| Error::<T>::TooManyCouncilMembers)?;
+
+            if members.is_empty() {
+                Councils::<T>::remove(network_id);
+            } else {
+                ensure!(
+                    threshold > 0 && threshold as usize <= members.len(),
+                    Error::<T>::InvalidCouncil
+                );
+                Councils::<T>::insert(
+                    network_id,
+                    Council {
+                        members: members.clone(),
+                        threshold,
+                    },
+                );
+            }
+
+            Self::deposit_event(Event::CouncilSet {
+                network_id,
+                members,
+                threshold,
+            });
+
+            Ok(())
+        }
+
+        /// Set or remove the limit on what the treasury of a network pays out per period.
+        ///
+        /// Only the owner can set the limit. A new limit starts a new period.
+        #[pallet::call_index(13)]
+        #[pallet::weight(Weight::from_parts(10_000, 0))]
+        pub fn set_spend_limit(
+            origin: OriginFor<T>,
+            network_id: u64,
+            limit: Option<SpendLimitOf<T>>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            Self::ensure_role(network_id, &who, NetworkRole::Owner)?;
+            match &limit {
+                Some(limit) => {
+                    ensure!(!limit.period.is_zero(), Error::<T>::InvalidSpendLimit);
+                    SpendLimits::<T>::insert(network_id, limit);
+                }
+                None => SpendLimits::<T>::remove(network_id),
+            }
+            SpendPeriods::<T>::remove(network_id);
+
+            Self::deposit_event(Event::SpendLimitSet { network_id, limit });
+
+            Ok(())
+        }
+
+        /// Propose paying `amount` from the network treasury to `beneficiary`.
+        ///
+        /// Only network admins can propose spends, and only while the network is active.
+        #[pallet::call_index(14)]
+        #[pallet::weight(Weight::from_parts(10_000, 0))]
+        pub fn propose_spend(
+            origin: OriginFor<T>,
+            network_id: u64,
+            beneficiary: T::AccountId,
+            amount: SpendAmountOf<T>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            Self::ensure_role(network_id, &who, NetworkRole::Admin)?;
+            let network = Networks::<T>::get(network_id).ok_or(Error::<T>::NetworkNotFound)?;
+            ensure!(
+                network.state == NetworkState::Active,
+                Error::<T>::NetworkNotActive
+            );
+            let valid = match amount {
+                SpendAmount::Native(amount) => !amount.is_zero(),
+                SpendAmount::Asset(amount) => network.asset_id.is_some() && !amount.is_zero(),
+            };
+            ensure!(valid, Error::<T>::InvalidSpend);
+
+            OpenProposals::<T>::try_mutate(network_id, |open| -> DispatchResult {
+                ensure!(
+                    *open < T::MaxSpendProposals::get(),
+                    Error::<T>::TooManySpendProposals
+                );
+                *open += 1;
+                Ok(())
+            })?;
+            let proposal_id = NextProposalId::<T>::try_mutate(network_id, |next| {
+                let id = *next;
+                *next = id.checked_add(1).ok_or(Error::<T>::ProposalIdOverflow)?;
+                Ok::<_, DispatchError>(id)
+            })?;
+            Proposals::<T>::insert(
+                network_id,
+                proposal_id,
+                SpendProposal {
+                    proposer: who.clone(),
+                    beneficiary: beneficiary.clone(),
+                    amount,
+                    approvals: BoundedVec::new(),
+                },
+            );
+
+            Self::deposit_event(Event::SpendProposed {
+                network_id,
+                proposal_id,
+                proposer: who,
+                beneficiary,
+                amount,
+            });
+
+            Ok(())
+        }
+
+        /// Approve a spend proposal.
+        ///
+        /// The owner's approval pays the spend out at once. Otherwise the spend is paid out
+        /// once the council threshold is reached. Payouts fail while the network is not
+        /// active or when they exceed the spend limit.
+        #[pallet::call_index(15)]
+        #[pallet::weight(Weight::from_parts(10_000, 0))]
+        pub fn approve_spend(
+            origin: OriginFor<T>,
+            network_id: u64,
+            proposal_id: u32,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let network = Networks::<T>::get(network_id).ok_or(Error::<T>::NetworkNotFound)?;
+            ensure!(
+                network.state == NetworkState::Active,
+                Error::<T>::NetworkNotActive
+            );
+            let mut proposal =
+                Proposals::<T>::get(network_id, proposal_id).ok_or(Error::<T>::ProposalNotFound)?;
+
+            if network.owner == who {
+                return Self::execute_spend(network_id, proposal_id, &network, proposal);
+            }
+
+            let council = Councils::<T>::get(network_id).ok_or(Error::<T>::NotCouncilMember)?;
+            ensure!(council.members.contains(&who), Error::<T>::NotCouncilMember);
+            ensure!(
+                !proposal.approvals.contains(&who),
+                Error::<T>::AlreadyApproved
+            );
+            // Only approvals of current members count.
+            proposal
+                .approvals
+                .retain(|member| council.members.contains(member));
+            proposal
+                .approvals
+                .try_push(who.clone())
+                .map_err(|_| Error::<T>::TooManyCouncilMembers)?;
+            let approvals = proposal.approvals.len() as u32;
+
+            Self::deposit_event(Event::SpendApproved {
+                network_id,
+                proposal_id,
+                who,
+                approvals,
+            });
+
+            if approvals >= council.threshold {
+                Self::execute_spend(network_id, proposal_id, &network, proposal)
+            } else {
+                Proposals::<T>::insert(network_id, proposal_id, proposal);
+                Ok(())
+            }
+        }
+
+        /// Reject a spend proposal.
+        ///
+        /// The owner can reject any proposal and proposers can withdraw their own.
+        #[pallet::call_index(16)]
+        #[pallet::weight(Weight::from_parts(10_000, 0))]
+        pub fn reject_spend(
+            origin: OriginFor<T>,
+            network_id: u64,
+            proposal_id: u32,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let proposal =
+                Proposals::<T>::get(network_id, proposal_id).ok_or(Error::<T>::ProposalNotFound)?;
+            if proposal.proposer != who {
+                Self::ensure_role(network_id, &who, NetworkRole::Owner)?;
+            }
+
+            Self::remove_proposal(network_id, proposal_id);
+            Self::deposit_event(Event::SpendRejected {
+                network_id,
+                proposal_id,
+            });
+
+            Ok(())
+        }
+
+        /// Set or remove how a network decides by referendum.
+        ///
+        /// Only the owner can change governance. Ongoing referenda keep the configuration
+        /// they were submitted with.
+        #[pallet::call_index(17)]
+        #[pallet::weight(Weight::from_parts(10_000, 0))]
+        pub fn set_governance(
+            origin: OriginFor<T>,
+            network_id: u64,
+            config: Option<GovernanceConfigOf<T>>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            Self::ensure_role(network_id, &who, NetworkRole::Owner)?;
+            match &config {
+                Some(config) => {
+                    let network =
+                        Networks::<T>::get(network_id).ok_or(Error::<T>::NetworkNotFound)?;
+                    Self::ensure_valid_governance(config, network.asset_id.is_some())?;
+                    Governance::<T>::insert(network_id, config);
+                }
+                None => Governance::<T>::remove(network_id),
+            }
+
+            Self::deposit_event(Event::GovernanceSet { network_id, config });
+
+            Ok(())
+        }
+
+        /// Submit a referendum on `action`.
+        ///
+        /// Members of the electorate and network admins can submit referenda while the
+        /// network is active. Voting starts at once, and the size of the electorate is taken
+        /// for the quorum.
+        #[pallet::call_index(18)]
+        #[pallet::weight(Weight::from_parts(10_000, 0))]
+        pub fn submit_referendum(
+            origin: OriginFor<T>,
+            network_id: u64,
+            action: NetworkAction,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let network = Networks::<T>::get(network_id).ok_or(Error::<T>::NetworkNotFound)?;
+            ensure!(
+                network.state == NetworkState::Active,
+                Error::<T>::NetworkNotActive
+            );
+            let config = Governance::<T>::get(network_id).ok_or(Error::<T>::NoGovernance)?;
+            ensure!(
+                Self::in_electorate(network_id, &network, config.electorate, &who)
+                    || Self::role_of(network_id, &who)
+                        .is_some_and(|role| role.includes(NetworkRole::Admin)),
+                Error::<T>::NotInElectorate
+            );
+
+            OngoingReferenda::<T>::try_mutate(network_id, |ongoing| -> DispatchResult {
+                ensure!(
+                    *ongoing < T::MaxReferenda::get(),
+                    Error::<T>::TooManyReferenda
+                );
+                *ongoing += 1;
+                Ok(())
+            })?;
+            let index = NextReferendumIndex::<T>::try_mutate(network_id, |next| {
+                let index = *next;
+                *next = index
+                    .checked_add(1)
+                    .ok_or(Error::<T>::ReferendumIndexOverflow)?;
+                Ok::<_, DispatchError>(index)
+            })?;
+            let end =
+                frame_system::Pallet::<T>::block_number().saturating_add(config.voting_period);
+            let electorate = Self::electorate_size(network_id, &network, config.electorate);
+            Referenda::<T>::insert(
+                network_id,
+                index,
+                Referendum {
+                    proposer: who.clone(),
+                    action: action.clone(),
+                    config,
+                    end,
+                    tally: Tally::default(),
+                    electorate,
+                },
+            );
+
+            Self::deposit_event(Event::ReferendumSubmitted {
+                network_id,
+                index,
+                proposer: who,
+                action,
+                end,
+            });
+
+            Ok(())
+        }
+
+        /// Vote in a referendum.
+        ///
+        /// Asset holders lock `balance` of the network asset until the referendum ends,
+        /// or longer with `conviction` in conviction referenda. Members vote with a weight
+        /// of one and ignore `balance` and `conviction`. Votes cannot be changed.
+        #[pallet::call_index(19)]
+        #[pallet::weight(Weight::from_parts(10_000, 0))]
+        pub fn vote(
+            origin: OriginFor<T>,
+            network_id: u64,
+            index: u32,
+            aye: bool,
+            balance: AssetBalanceOf<T>,
+            conviction: Conviction,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let network = Networks::<T>::get(network_id).ok_or(Error::<T>::NetworkNotFound)?;
+            ensure!(
+                network.state == NetworkState::Active,
+                Error::<T>::NetworkNotActive
+            );
+            let mut referendum =
+                Referenda::<T>::get(network_id, index).ok_or(Error::<T>::ReferendumNotFound)?;
+            ensure!(
+                frame_system::Pallet::<T>::block_number() < referendum.end,
+                Error::<T>::VotingClosed
+            );
+            ensure!(
+                !Votes::<T>::contains_key((network_id, index, &who)),
+                Error::<T>::AlreadyVoted
+            );
+
+            let (record, votes, turnout) = match referendum.config.electorate {
+                Electorate::Members => {
+                    ensure!(
+                        T::Members::is_member(network_id, &who),
+                        Error::<T>::NotInElectorate
+                    );
+                    let record = VoteRecord {
+                        aye,
+                        balance: Zero::zero(),
+                        unlock_at: referendum.end,
+                    };
+                    (record, 1, 1)
+                }
+                Electorate::AssetHolders => {
+                    ensure!(!balance.is_zero(), Error::<T>::InvalidVote);
+                    let asset_id = network.asset_id.ok_or(Error::<T>::NotInElectorate)?;
+                    Self::lock_vote(asset_id, &who, balance)?;
+
+                    let locked: u128 = balance.saturated_into();
+                    let (votes, periods) = match referendum.config.scheme {
+                        VotingScheme::SimpleMajority => (locked, 0),
+                        VotingScheme::Conviction => {
+                            (conviction.votes(locked), conviction.lock_periods())
+                        }
+                    };
+                    let record = VoteRecord {
+                        aye,
+                        balance,
+                        unlock_at: referendum.end.saturating_add(
+                            T::VoteLockingPeriod::get().saturating_mul(periods.into()),
+                        ),
+                    };
+                    (record, votes, locked)
+                }
+            };
+
+            let tally = &mut referendum.tally;
+            if aye {
+                tally.ayes = tally.ayes.saturating_add(votes);
+            } else {
+                tally.nays = tally.nays.saturating_add(votes);
+            }
+            tally.turnout = tally.turnout.saturating_add(turnout);
+            Referenda::<T>::insert(network_id, index, referendum);
+            Votes::<T>::insert((network_id, index, &who), record);
+
+            Self::deposit_event(Event::Voted {
+                network_id,
+                index,
+                who,
+                aye,
+                votes,
+            });
+
+            Ok(())
+        }
+
+        /// Close a referendum whose voting has ended, enacting its action if it passed.
+        ///
+        /// Anyone can close a referendum. It passes with more ayes than nays and a turnout
+        /// that meets the quorum of the electorate at submission. Enacting fails while the
+        /// network is not active.
+        #[pallet::call_index(20)]
+        #[pallet::weight(Weight::from_parts(10_000, 0))]
+        pub fn close_referendum(
+            origin: OriginFor<T>,
+            network_id: u64,
+            index: u32,
+        ) -> DispatchResult {
+            ensure_signed(origin)?;
+
+            let referendum =
+                Referenda::<T>::get(network_id, index).ok_or(Error::<T>::ReferendumNotFound)?;
+            ensure!(
+                frame_system::Pallet::<T>::block_number() >= referendum.end,
+                Error::<T>::VotingOngoing
+            );
+            Referenda::<T>::remove(network_id, index);
+            OngoingReferenda::<T>::mutate(network_id, |ongoing| {
+                *ongoing = ongoing.saturating_sub(1)
+            });
+
+            let tally = referendum.tally;
+            let passed = tally.ayes > tally.nays
+                && tally.turnout >= referendum.config.quorum.mul_ceil(referendum.electorate);
+
+            if passed {
+                let result = if Self::state_of(network_id) == Some(NetworkState::Active) {
+                    frame_support::storage::with_storage_layer(|| {
+                        Self::enact(network_id, referendum.action)
+                    })
+                } else {
+                    Err(Error::<T>::NetworkNotActive.into())
+                };
+                Self::deposit_event(Event::ReferendumPassed {
+                    network_id,
+                    index,
+                    result,
+                });
+            } else {
+                Self::deposit_event(Event::ReferendumRejected { network_id, index });
+            }
+
+            Ok(())
+        }
+
+        /// Unlock the vote of `who` in a referendum and return the balance it locked.
+        ///
+        /// Anyone can unlock a vote once its lock expires, or at any time once the network
+        /// is closed.
+        #[pallet::call_index(21)]
+        #[pallet::weight(Weight::from_parts(10_000, 0))]
+        pub fn unlock_vote(
+            origin: OriginFor<T>,
+            network_id: u64,
+            index: u32,
+            who: T::AccountId,
+        ) -> DispatchResult {
+            use fungibles::Mutate as _;
+
+            ensure_signed(origin)?;
+
+            let record = Votes::<T>::get((network_id, index, &who)).ok_or(Error::<T>::NoVote)?;
+            let network = Networks::<T>::get(network_id).ok_or(Error::<T>::NetworkNotFound)?;
+            ensure!(
+                network.state == NetworkState::Closed
+                    || frame_system::Pallet::<T>::block_number() >= record.unlock_at,
+                Error::<T>::VoteLocked
+            );
+
+            Votes::<T>::remove((network_id, index, &who));
+            if !record.balance.is_zero() {
+                let asset_id = network.asset_id.ok_or(Error::<T>::NoVote)?;
+                T::Assets::transfer(
+                    asset_id,
+                    &Self::vote_account(),
+                    &who,
+                    record.balance,
+                    Preservation::Expendable,
+                )?;
+            }
+
+            Self::deposit_event(Event::VoteUnlocked {
+                network_id,
+                index,
+                who,
+                balance: record.balance,
+            });
+
+            Ok(())
+        }
+
+        /// Create a child network under network `parent_id`.
+        ///
+        /// Only admins of the parent can create children, and only while the parent is
+        /// active. The child uses the parent's coin and is owned by the parent's treasury
+        /// or the caller, who pays the creation deposit either way. With
+        /// `inherit_membership`, members of the parent are members of the child.
+        #[pallet::call_index(22)]
+        #[pallet::weight(Weight::from_parts(10_000, 0))]
+        pub fn create_child_network(
+            origin: OriginFor<T>,
+            parent_id: u64,
+            metadata_uri: Vec<u8>,
+            owner: ChildOwner,
+            inherit_membership: bool,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            Self::ensure_role(parent_id, &who, NetworkRole::Admin)?;
+            let parent = Networks::<T>::get(parent_id).ok_or(Error::<T>::NetworkNotFound)?;
+            ensure!(
+                parent.state == NetworkState::Active,
+                Error::<T>::NetworkNotActive
+            );
+            // The parent's ancestors, the parent and the child itself.
+            let depth = Self::ancestors_of(parent_id).len().saturating_add(2);
+            ensure!(
+                depth <= T::MaxDepth::get() as usize,
+                Error::<T>::HierarchyTooDeep
+            );
+            ChildCount::<T>::try_mutate(parent_id, |count| -> DispatchResult {
+                ensure!(*count < T::MaxChildren::get(), Error::<T>::TooManyChildren);
+                *count += 1;
+                Ok(())
+            })?;
+
+            let bounded_uri: BoundedVec<u8, ConstU32<256>> = metadata_uri
+                .try_into()
+                .map_err(|_| Error::<T>::MetadataUriTooLong)?;
+            let owner = match owner {
+                ChildOwner::ParentTreasury => parent.treasury,
+                ChildOwner::Caller => who.clone(),
+            };
+            let network_id = Self::take_network_id()?;
+            Self::inc_owned_networks(&owner)?;
+            Self::hold_deposit(network_id, &who)?;
+
+            let treasury = Self::treasury_account(network_id);
+            if parent.asset_id.is_some() {
+                // The treasury must be able to hold network coins without a native balance.
+                frame_system::Pallet::<T>::inc_providers(&treasury);
+            }
+            Networks::<T>::insert(
+                network_id,
+                NetworkInfo {
+                    owner: owner.clone(),
+                    coin_kind: parent.coin_kind,
+                    asset_id: parent.asset_id,
+                    treasury,
+                    metadata_uri: bounded_uri,
+                    state: NetworkState::Active,
+                },
+            );
+            Parents::<T>::insert(
+                network_id,
+                ParentLink {
+                    parent: parent_id,
+                    inherit_membership,
+                },
+            );
+            Children::<T>::insert(parent_id, network_id, ());
+            NetworksByOwner::<T>::insert(&owner, network_id, ());
+
+            Self::deposit_event(Event::NetworkCreated {
+                network_id,
+                owner,
+                use_navi: parent.coin_kind == CoinKind::UseNavi,
+                asset_id: parent.asset_id,
+            });
+            Self::deposit_event(Event::ChildNetworkCreated {
+                parent_id,
+                network_id,
+                inherit_membership,
+            });
+
+            Ok(())
+        }
+
+        /// Publish a network template.
+        ///
+        /// Anyone can publish templates, holding [`Config::TemplateDeposit`] until they
+        /// remove them. The governance configuration is checked against the template's
+        /// coin; resources and basket weights are checked when networks are provisioned.
+        ///
+        /// # Parameters
+        /// - `asset`: The asset networks mint, or `None` to use NAVI.
+        /// - `profile`: The profile networks start with.
+        /// - `governance`: How networks decide by referendum.
+        /// - `resources`: Access Gate resources to register, in order.
+        /// - `basket_weights`: Service basket weights, in parts per million.
+        #[pallet::call_index(23)]
+        #[pallet::weight(Weight::from_parts(10_000, 0))]
+        pub fn create_template(
+            origin: OriginFor<T>,
+            asset: Option<AssetConfigOf<T>>,
+            profile: Option<NetworkProfile>,
+            governance: Option<GovernanceConfigOf<T>>,
+            resources: Vec<ResourceSpec>,
+            basket_weights: BoundedVec<(BoundedVec<u8, ConstU32<32>>, u32), ConstU32<10>>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            if let Some(config) = &governance {
+                Self::ensure_valid_governance(config, asset.is_some())?;
+            }
+            let resources: BoundedVec<_, T::MaxTemplateResources> = resources
+                .try_into()
+                .map_err(|_| Error::<T>::TooManyTemplateResources)?;
+
+            let template_id = NextTemplateId::<T>::get();
+            NextTemplateId::<T>::put(
+                template_id
+                    .checked_add(1)
+                    .ok_or(Error::<T>::TemplateIdOverflow)?,
+            );
+            let deposit = T::TemplateDeposit::get();
+            if !deposit.is_zero() {
+                T::Currency::hold(&HoldReason::TemplateDeposit.into(), &who, deposit)?;
+            }
+            Templates::<T>::insert(
+                template_id,
+                NetworkTemplate {
+                    creator: who.clone(),
+                    deposit,
+                    asset,
+                    profile,
+                    governance,
+                    resources,
+                    basket_weights,
+                },
+            );
+
+            Self::deposit_event(Event::TemplateCreated {
+                template_id,
+                creator: who,
+            });
+
+            Ok(())
+        }
+
+        /// Remove a network template and release its deposit.
+        ///
+        /// Only the creator can remove a template. Networks created from it are not
+        /// affected.
+        #[pallet::call_index(24)]
+        #[pallet::weight(Weight::from_parts(10_000, 0))]
+        pub fn remove_template(origin: OriginFor<T>, template_id: u32) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let template = Templates::<T>::get(template_id).ok_or(Error::<T>::TemplateNotFound)?;
+            ensure!(template.creator == who, Error::<T>::NotTemplateCreator);
+            T::Currency::release(
+                &HoldReason::TemplateDeposit.into(),
+                &who,
+                template.deposit,
+                Precision::BestEffort,
+            )?;
+            Templates::<T>::remove(template_id);
+
+            Self::deposit_event(Event::TemplateRemoved { template_id });
+
+            Ok(())
+        }
+
+        /// Create a network from a template.
+        ///
+        /// The caller owns the network and pays its creation deposit. The network gets the
+        /// template's coin, profile and governance, then its resources and basket weights
+        /// are set through [`Config::ActionHandler`]. If any step fails, nothing is created.
+        #[pallet::call_index(25)]
+        #[pallet::weight(Weight::from_parts(10_000, 0))]
+        pub fn create_from_template(
+            origin: OriginFor<T>,
+            template_id: u32,
+            metadata_uri: Vec<u8>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let template = Templates::<T>::get(template_id).ok_or(Error::<T>::TemplateNotFound)?;
+            let network_id = Self::do_create_network(
+                who,
+                metadata_uri,
+                template.asset.map(|asset| (asset, None)),
+            )?;
+
+            if let Some(profile) = template.profile {
+                Self::do_set_profile(network_id, profile);
+            }
+            if let Some(config) = template.governance {
+                Governance::<T>::insert(network_id, config);
+                Self::deposit_event(Event::GovernanceSet {
+                    network_id,
+                    config: Some(config),
+                });
+            }
+            for resource in template.resources {
+                Self::enact(network_id, NetworkAction::RegisterResource(resource))?;
+            }
+            if !template.basket_weights.is_empty() {
+                Self::enact(
+                    network_id,
+                    NetworkAction::SetBasketWeights(template.basket_weights),
+                )?;
+            }
+
+            Self::deposit_event(Event::NetworkProvisioned {
+                network_id,
+                template_id,
+            });
+
+            Ok(())
+        }
+
+        /// Set who can join a network.
+        ///
+        /// Only admins can change the admission mode. Switching to open keeps the
+        /// allowlist for when the network is made private again.
+        #[pallet::call_index(26)]
+        #[pallet::weight(Weight::from_parts(10_000, 0))]
+        pub fn set_admission_mode(
+            origin: OriginFor<T>,
+            network_id: u64,
+            mode: AdmissionMode,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            Self::ensure_role(network_id, &who, NetworkRole::Admin)?;
+            Admission::<T>::insert(network_id, mode);
+
+            Self::deposit_event(Event::AdmissionModeSet { network_id, mode });
+
+            Ok(())
+        }
+
+        /// Allow `who` to join a network that is not open.
+        ///
+        /// Only admins can change the allowlist. Any request to join by `who` is approved.
+        #[pallet::call_index(27)]
+        #[pallet::weight(Weight::from_parts(10_000, 0))]
+        pub fn add_to_allowlist(
+            origin: OriginFor<T>,
+            network_id: u64,
+            who: T::AccountId,
+        ) -> DispatchResult {
+            let admin = ensure_signed(origin)?;
+
+            Self::ensure_role(network_id, &admin, NetworkRole::Admin)?;
+            if JoinRequests::<T>::take(network_id, &who).is_some() {
+                Self::deposit_event(Event::JoinRequestDecided {
+                    network_id,
+                    who: who.clone(),
+                    approved: true,
+                });
+            }
+            Self::allowlist(network_id, who)
+        }
+
+        /// Remove `who` from the allowlist of a network.
+        ///
+        /// Only admins can change the allowlist. Existing Access Gate memberships are
+        /// not affected.
+        #[pallet::call_index(28)]
+        #[pallet::weight(Weight::from_parts(10_000, 0))]
+        pub fn remove_from_allowlist(
+            origin: OriginFor<T>,
+            network_id: u64,
+            who: T::AccountId,
+        ) -> DispatchResult {
+            let admin = ensure_signed(origin)?;
+
+            Self::ensure_role(network_id, &admin, NetworkRole::Admin)?;
+            ensure!(
+                Allowlist::<T>::take(network_id, &who).is_some(),
+                Error::<T>::NotAllowlisted
+            );
+
+            Self::deposit_event(Event::RemovedFromAllowlist { network_id, who });
+
+            Ok(())
+        }
+
+        /// Create an invite whose code hashes to `code_hash` with BLAKE2-256.
+        ///
+        /// Only admins can create invites. The invite can be redeemed `max_uses` times
+        /// before `expires_at`, if given.
+        #[pallet::call_index(29)]
+        #[pallet::weight(Weight::from_parts(10_000, 0))]
+        pub fn create_invite(
+            origin: OriginFor<T>,
+            network_id: u64,
+            code_hash: [u8; 32],
+            expires_at: Option<BlockNumberFor<T>>,
+            max_uses: u32,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            Self::ensure_role(network_id, &who, NetworkRole::Admin)?;
+            let now = frame_system::Pallet::<T>::block_number();
+            ensure!(
+                max_uses > 0 && expires_at.map_or(true, |expires_at| expires_at > now),
+                Error::<T>::InvalidInvite
+            );
+            ensure!(
+                !Invites::<T>::contains_key(network_id, code_hash),
+                Error::<T>::InviteExists
+            );
+            Invites::<T>::insert(
+                network_id,
+                code_hash,
+                Invite {
+                    expires_at,
+                    uses_left: max_uses,
+                },
+            );
+
+            Self::deposit_event(Event::InviteCreated {
+                network_id,
+                code_hash,
+                expires_at,
+                max_uses,
+            });
+
+            Ok(())
+        }
+
+        /// Revoke an invite before it is used up or expires.
+        ///
+        /// Only admins can revoke invites. Expired invites can be revoked by anyone.
+        #[pallet::call_index(30)]
+        #[pallet::weight(Weight::from_parts(10_000, 0))]
+        pub fn revoke_invite(
+            origin: OriginFor<T>,
+            network_id: u64,
+            code_hash: [u8; 32],
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let invite =
+                Invites::<T>::get(network_id, code_hash).ok_or(Error::<T>::InviteNotFound)?;
+            let now = frame_system::Pallet::<T>::block_number();
+            if invite
+                .expires_at
+                .map_or(true, |expires_at| expires_at > now)
+            {
+                Self::ensure_role(network_id, &who, NetworkRole::Admin)?;
+            }
+            Invites::<T>::remove(network_id, code_hash);
+
+            Self::deposit_event(Event::InviteRevoked {
+                network_id,
+                code_hash,
+            });
+
+            Ok(())
+        }
+
+        /// Redeem an invite code to join the allowlist of a network.
+        ///
+        /// The invite is removed once it is used up.
+        #[pallet::call_index(31)]
+        #[pallet::weight(Weight::from_parts(10_000, 0))]
+        pub fn redeem_invite(
+            origin: OriginFor<T>,
+            network_id: u64,
+            code: Vec<u8>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            Self::ensure_not_closed(network_id)?;
+            ensure!(
+                !Allowlist::<T>::contains_key(network_id, &who),
+                Error::<T>::AlreadyAllowlisted
+            );
+            let code_hash: [u8; 32] = BlakeTwo256::hash(&code).into();
+            Invites::<T>::try_mutate_exists(network_id, code_hash, |maybe_invite| {
+                let invite = maybe_invite.as_mut().ok_or(Error::<T>::InviteNotFound)?;
+                let now = frame_system::Pallet::<T>::block_number();
+                ensure!(
+                    invite
+                        .expires_at
+                        .map_or(true, |expires_at| expires_at > now),
+                    Error::<T>::InviteExpired
+                );
+                invite.uses_left = invite.uses_left.saturating_sub(1);
+                if invite.uses_left == 0 {
+                    *maybe_invite = None;
+                }
+                Ok::<_, DispatchError>(())
+            })?;
+            JoinRequests::<T>::remove(network_id, &who);
+
+            Self::deposit_event(Event::InviteRedeemed {
+                network_id,
+                code_hash,
+                who: who.clone(),
+            });
+            Self::allowlist(network_id, who)
+        }
+
+        /// Ask the admins of a network to allowlist the caller.
+        ///
+        /// Only networks in [`AdmissionMode::RequestToJoin`] take requests. Admins
+        /// approve them with `add_to_allowlist` or turn them down with `reject_join`.
+        #[pallet::call_index(32)]
+        #[pallet::weight(Weight::from_parts(10_000, 0))]
+        pub fn request_to_join(origin: OriginFor<T>, network_id: u64) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            Self::ensure_not_closed(network_id)?;
+            ensure!(
+                Admission::<T>::get(network_id) == AdmissionMode::RequestToJoin,
+                Error::<T>::RequestsNotAccepted
+            );
+            ensure!(
+                !Allowlist::<T>::contains_key(network_id, &who),
+                Error::<T>::AlreadyAllowlisted
+            );
+            ensure!(
+                !JoinRequests::<T>::contains_key(network_id, &who),
+                Error::<T>::AlreadyRequested
+            );
+            JoinRequests::<T>::insert(network_id, &who, frame_system::Pallet::<T>::block_number());
+
+            Self::deposit_event(Event::JoinRequested { network_id, who });
+
+            Ok(())
+        }
+
+        /// Turn down a request to join.
+        ///
+        /// Only admins can decide requests.
+        #[pallet::call_index(33)]
+        #[pallet::weight(Weight::from_parts(10_000, 0))]
+        pub fn reject_join(
+            origin: OriginFor<T>,
+            network_id: u64,
+            who: T::AccountId,
+        ) -> DispatchResult {
+            let admin = ensure_signed(origin)?;
+
+            Self::ensure_role(network_id, &admin, NetworkRole::Admin)?;
+            ensure!(
+                JoinRequests::<T>::take(network_id, &who).is_some(),
+                Error::<T>::JoinRequestNotFound
+            );
+
+            Self::deposit_event(Event::JoinRequestDecided {
+                network_id,
+                who,
+                approved: false,
+            });
+
+            Ok(())
+        }
+
+        /// Buy `steps` steps of the network asset from its bonding curve.
+        ///
+        /// The NAVI cost goes to the curve reserve in the treasury and the asset is minted
+        /// to the caller, within the maximum supply. Fails if the cost exceeds `max_cost`.
+        #[pallet::call_index(34)]
+        #[pallet::weight(Weight::from_parts(10_000, 0))]
+        pub fn buy_from_curve(
+            origin: OriginFor<T>,
+            network_id: u64,
+            steps: u32,
+            max_cost: BalanceOf<T>,
+        ) -> DispatchResult {
+            use fungible::Mutate as _;
+
+            let who = ensure_signed(origin)?;
+
+            let (network, mut curve) = Self::curve_market(network_id, steps)?;
+            ensure!(
+                network.state == NetworkState::Active,
+                Error::<T>::NetworkNotActive
+            );
+            let asset_id = network.asset_id.ok_or(Error::<T>::NoCurve)?;
+            let cost = Self::curve_balance(curve.config.cost(curve.supply, steps))?;
+            ensure!(cost <= max_cost, Error::<T>::SlippageExceeded);
+            let amount = Self::curve_amount(&curve.config, steps)?;
+            let policy = Issuance::<T>::get(network_id).ok_or(Error::<T>::NoIssuancePolicy)?;
+            if let Some(max_supply) = policy.max_supply {
+                let committed =
+                    <T::Assets as fungibles::Inspect<T::AccountId>>::total_issuance(asset_id)
+                        .saturating_add(policy.unvested);
+                ensure!(
+                    committed
+                        .checked_add(&amount)
+                        .is_some_and(|total| total <= max_supply),
+                    Error::<T>::SupplyCapExceeded
+                );
+            }
+
+            T::Currency::transfer(&who, &network.treasury, cost, Preservation::Preserve)?;
+            Self::mint(network_id, &who, amount, MintReason::BondingCurve)?;
+            curve.supply = curve.supply.saturating_add(steps);
+            curve.reserve = curve.reserve.saturating_add(cost);
+            Curves::<T>::insert(network_id, curve);
+
+            Self::deposit_event(Event::CurveBought {
+                network_id,
+                who,
+                amount,
+                cost,
+            });
+
+            Ok(())
+        }
+
+        /// Sell `steps` steps of the network asset back to its bonding curve.
+        ///
+        /// The asset is burned and the caller is paid from the curve reserve. Fails if the
+        /// proceeds fall short of `min_return`. Selling stays possible while the network is
+        /// paused.
+        #[pallet::call_index(35)]
+        #[pallet::weight(Weight::from_parts(10_000, 0))]
+        pub fn sell_to_curve(
+            origin: OriginFor<T>,
+            network_id: u64,
+            steps: u32,
+            min_return: BalanceOf<T>,
+        ) -> DispatchResult {
+            use fungible::Mutate as _;
+            use fungibles::Mutate as _;
+
+            let who = ensure_signed(origin)?;
+
+            let (network, mut curve) = Self::curve_market(network_id, steps)?;
+            let asset_id = network.asset_id.ok_or(Error::<T>::NoCurve)?;
+            let supply = curve
+                .supply
+                .checked_sub(steps)
+                .ok_or(Error::<T>::InsufficientCurveSupply)?;
+            let proceeds = Self::curve_balance(curve.config.cost(supply, steps))?;
+            ensure!(proceeds >= min_return, Error::<T>::SlippageExceeded);
+            let amount = Self::curve_amount(&curve.config, steps)?;
+
+            T::Assets::burn_from(
+                asset_id,
+                &who,
+                amount,
+                Preservation::Expendable,
+                Precision::Exact,
+                Fortitude::Polite,
+            )?;
+            T::Currency::transfer(&network.treasury, &who, proceeds, Preservation::Expendable)?;
+            curve.supply = supply;
+            curve.reserve = curve.reserve.saturating_sub(proceeds);
+            Curves::<T>::insert(network_id, curve);
+
+            Self::deposit_event(Event::CurveSold {
+                network_id,
+                who,
+                amount,
+                proceeds,
+            });
+
+            Ok(())
+        }
+
+        /// Move a network between NAVI and its own coin.
+        ///
+        /// Moving to the network's own coin creates its asset and issuance policy from
+        /// `asset`, unless the network kept its asset when it last moved back to NAVI.
+        /// Moving back to NAVI leaves the asset and its balances in place and closes its
+        /// bonding curve, which must have no coins outstanding. Prices in other pallets,
+        /// such as Access Gate resource prices, are converted at `rate` through
+        /// [`NetworkActionHandler`], and memberships carry over. Only the owner can
+        /// migrate, and not while the network has open children or when it uses its
+        /// parent's coin.
+        #[pallet::call_index(36)]
+        #[pallet::weight(Weight::from_parts(10_000, 0).saturating_add(
+            T::ActionHandler::weight(&NetworkAction::ConvertPrices(*rate))
+        ))]
+        pub fn migrate_coin(
+            origin: OriginFor<T>,
+            network_id: u64,
+            to: CoinKind,
+            rate: ConversionRate,
+            asset: Option<(AssetConfigOf<T>, IssuanceConfigOf<T>)>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            Self::ensure_role(network_id, &who, NetworkRole::Owner)?;
+            let mut network = Networks::<T>::get(network_id).ok_or(Error::<T>::NetworkNotFound)?;
+            let from = network.coin_kind;
+            ensure!(from != to, Error::<T>::CoinUnchanged);
+            ensure!(
+                !Parents::<T>::contains_key(network_id),
+                Error::<T>::CoinInherited
+            );
+            ensure!(
+                ChildCount::<T>::get(network_id) == 0,
+                Error::<T>::HasOpenChildren
+            );
+            ensure!(rate.from > 0 && rate.to > 0, Error::<T>::InvalidRate);
+            if to == CoinKind::UseNavi {
+                ensure!(
+                    !Curves::<T>::get(network_id).is_some_and(|curve| curve.supply > 0),
+                    Error::<T>::CurveOutstanding
+                );
+            }
+
+            network.coin_kind = to;
+            match asset {
+                Some((asset, issuance)) => {
+                    ensure!(
+                        to == CoinKind::MintAsset && network.asset_id.is_none(),
+                        Error::<T>::AssetExists
+                    );
+                    let asset_id =
+                        Self::attach_asset(network_id, &network.owner, &network.treasury, asset)?;
+                    network.asset_id = Some(asset_id);
+                    Networks::<T>::insert(network_id, &network);
+                    Self::apply_issuance(network_id, &network.owner, &network.treasury, issuance)?;
+                }
+                None => {
+                    ensure!(
+                        to == CoinKind::UseNavi || network.asset_id.is_some(),
+                        Error::<T>::AssetRequired
+                    );
+                    Networks::<T>::insert(network_id, &network);
+                }
+            }
+            if to == CoinKind::UseNavi {
+                Curves::<T>::remove(network_id);
+            }
+
+            if let Some(result) =
+                T::ActionHandler::enact(network_id, &NetworkAction::ConvertPrices(rate))
+            {
+                result?;
+            }
+
+            Self::deposit_event(Event::CoinMigrated {
+                network_id,
+                from,
+                to,
+                asset_id: network.asset_id,
+                rate,
+            });
+
+            Ok(())
+        }
+
+        /// Give a network a handle, replacing its current one, or release it with `None`.
+        ///
+        /// Handles are unique and stay with the network when its ownership moves. Only the
+        /// owner can change the handle.
+        #[pallet::call_index(37)]
+        #[pallet::weight(Weight::from_parts(10_000, 0))]
+        pub fn set_handle(
+            origin: OriginFor<T>,
+            network_id: u64,
+            handle: Option<Vec<u8>>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            Self::ensure_role(network_id, &who, NetworkRole::Owner)?;
+            match handle {
+                Some(handle) => Self::do_set_handle(network_id, handle),
+                None => {
+                    ensure!(
+                        NetworkHandles::<T>::contains_key(network_id),
+                        Error::<T>::NoHandle
+                    );
+                    Self::release_handle(network_id);
+                    Ok(())
+                }
+            }
+        }
+
+        /// Create a new network with a handle.
+        ///
+        /// Works like `create_network`, and fails if the handle is invalid or taken.
+        #[pallet::call_index(38)]
+        #[pallet::weight(Weight::from_parts(10_000, 0))]
+        pub fn create_network_with_handle(
+            origin: OriginFor<T>,
+            use_navi: bool,
+            metadata_uri: Vec<u8>,
+            handle: Vec<u8>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            // Check the handle before anything is created
+            Self::free_handle(handle.clone())?;
+            let asset = (!use_navi).then(Self::default_asset);
+            let network_id =
+                Self::do_create_network(who, metadata_uri, asset.map(|asset| (asset, None)))?;
+            Self::do_set_handle(network_id, handle)
+        }
+    }
+
+    #[pallet::hooks]
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn on_idle(_n: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+            let consumed = Self::cleanup_pending(remaining_weight);
+            consumed.saturating_add(Self::skip_taken_asset_ids(
+                remaining_weight.saturating_sub(consumed),
+            ))
+        }
+    }
+
+    impl<T: Config> Pallet<T> {
+        /// Derive the treasury account for a network.
+        pub fn treasury_account(network_id: u64) -> T::AccountId {
+            PALLET_ID.into_sub_account_truncating(network_id)
+        }
+
+        /// Ensure `origin` is the governance origin or the owner of network `network_id`.
+        fn ensure_owner_or_governance(origin: OriginFor<T>, network_id: u64) -> DispatchResult {
+            match T::GovernanceOrigin::try_origin(origin) {
+                Ok(_) => Ok(()),
+                Err(origin) => {
+                    let who = ensure_signed(origin)?;
+                    Self::ensure_role(network_id, &who, NetworkRole::Owner)
+                }
+            }
+        }
+
+        /// Move everything in a network treasury to the owner, or burn it.
+        fn drain_treasury(
+            network_id: u64,
+            network: &NetworkInfo<T>,
+            policy: TreasuryPolicy,
+        ) -> DispatchResult {
+            use fungible::{Inspect as _, Mutate as _};
+            use fungibles::{Inspect as _, Mutate as _};
+
+            let treasury = &network.treasury;
+            let native = T::Currency::reducible_balance(
+                treasury,
+                Preservation::Expendable,
+                Fortitude::Polite,
+            );
+            if !native.is_zero() {
+                match policy {
+                    TreasuryPolicy::ToOwner => {
+                        T::Currency::transfer(
+                            treasury,
+                            &network.owner,
+                            native,
+                            Preservation::Expendable,
+                        )?;
+                    }
+                    TreasuryPolicy::Burn => {
+                        T::Currency::burn_from(
+                            treasury,
+                            native,
+                            Preservation::Expendable,
+                            Precision::Exact,
+                            Fortitude::Polite,
+                        )?;
+                    }
+                }
+            }
+
+            let mut asset = AssetBalanceOf::<T>::zero();
+            if let Some(asset_id) = network.asset_id {
+                asset = T::Assets::reducible_balance(
+                    asset_id,
+                    treasury,
+                    Preservation::Expendable,
+                    Fortitude::Polite,
+                );
+                if !asset.is_zero() {
+                    match policy {
+                        TreasuryPolicy::ToOwner => {
+                            T::Assets::transfer(
+                                asset_id,
+                                treasury,
+                                &network.owner,
+                                asset,
+                                Preservation::Expendable,
+                            )?;
+                        }
+                        TreasuryPolicy::Burn => {
+                            T::Assets::burn_from(
+                                asset_id,
+                                treasury,
+                                asset,
+                                Preservation::Expendable,
+                                Precision::Exact,
+                                Fortitude::Polite,
+                            )?;
+                        }
+                    }
+                }
+                // Release the provider reference taken when the asset was created.
+                let _ = frame_system::Pallet::<T>::dec_providers(treasury);
+            }
+
+            Self::deposit_event(Event::TreasuryDrained {
+                network_id,
+                policy,
+                native,
+                asset,
+            });
+            Ok(())
+        }
+
+        /// Clean up the oldest pending closed network within `remaining_weight`.
+        fn cleanup_pending(remaining_weight: Weight) -> Weight {
+            let db = T::DbWeight::get();
+            let per_item = db.reads_writes(1, 1);
+            let mut consumed = db.reads(1);
+            if remaining_weight.any_lt(consumed.saturating_add(per_item)) {
+                return Weight::zero();
+            }
+
+            let Some(network_id) = PendingCleanup::<T>::iter_keys().next() else {
+                return consumed;
+            };
+            let limit = remaining_weight
+                .saturating_sub(consumed)
+                .saturating_sub(db.writes(1))
+                .checked_div_per_component(&per_item)
+                .unwrap_or(MAX_CLEANUP_ITEMS)
+                .min(MAX_CLEANUP_ITEMS) as u32;
+            if limit == 0 {
+                return consumed;
+            }
+
+            let (removed, done) = Self::cleanup(network_id, limit);
+            consumed.saturating_accrue(per_item.saturating_mul(removed.into()));
+            if done {
+                PendingCleanup::<T>::remove(network_id);
+                consumed.saturating_accrue(db.writes(1));
+                Self::deposit_event(Event::CleanupCompleted { network_id });
+            }
+            consumed
+        }
+
+        /// Remove at most `limit` storage items of closed network `network_id`, first
+        /// from this pallet and then through [`Config::Cleanup`].
+        fn cleanup(network_id: u64, limit: u32) -> (u32, bool) {
+            let roles = Roles::<T>::clear_prefix(network_id, limit, None);
+            let mut removed = roles.unique;
+            if roles.maybe_cursor.is_some() {
+                return (removed, false);
+            }
+
+            let vesting =
+                Vesting::<T>::clear_prefix(network_id, limit.saturating_sub(removed), None);
+            removed = removed.saturating_add(vesting.unique);
+            if vesting.maybe_cursor.is_some() {
+                return (removed, false);
+            }
+
+            let proposals =
+                Proposals::<T>::clear_prefix(network_id, limit.saturating_sub(removed), None);
+            removed = removed.saturating_add(proposals.unique);
+            if proposals.maybe_cursor.is_some() {
+                return (removed, false);
+            }
+
+            let referenda =
+                Referenda::<T>::clear_prefix(network_id, limit.saturating_sub(removed), None);
+            removed = removed.saturating_add(referenda.unique);
+            if referenda.maybe_cursor.is_some() {
+                return (removed, false);
+            }
+
+            let allowlist =
+                Allowlist::<T>::clear_prefix(network_id, limit.saturating_sub(removed), None);
+            removed = removed.saturating_add(allowlist.unique);
+            if allowlist.maybe_cursor.is_some() {
+                return (removed, false);
+            }
+
+            let invites =
+                Invites::<T>::clear_prefix(network_id, limit.saturating_sub(removed), None);
+            removed = removed.saturating_add(invites.unique);
+            if invites.maybe_cursor.is_some() {
+                return (removed, false);
+            }
+
+            let requests =
+                JoinRequests::<T>::clear_prefix(network_id, limit.saturating_sub(removed), None);
+            removed = removed.saturating_add(requests.unique);
+            if requests.maybe_cursor.is_some() {
+                return (removed, false);
+            }
+
+            let (count, done) = T::Cleanup::cleanup(network_id, limit.saturating_sub(removed));
+            (removed.saturating_add(count), done)
+        }
+
+        /// Pay out an approved spend proposal within the spend limit and remove it.
+        fn execute_spend(
+            network_id: u64,
+            proposal_id: u32,
+            network: &NetworkInfo<T>,
+            proposal: SpendProposal<T>,
+        ) -> DispatchResult {
+            use fungible::Mutate as _;
+            use fungibles::Mutate as _;
+
+            Self::record_spend(network_id, proposal.amount)?;
+            match proposal.amount {
+                SpendAmount::Native(amount) => {
+                    if let Some(curve) = Curves::<T>::get(network_id) {
+                        let available =
+                            <T::Currency as fungible::Inspect<T::AccountId>>::reducible_balance(
+                                &network.treasury,
+                                Preservation::Expendable,
+                                Fortitude::Polite,
+                            )
+                            .saturating_sub(curve.reserve);
+                        ensure!(amount <= available, Error::<T>::ReserveLocked);
+                    }
+                    T::Currency::transfer(
+                        &network.treasury,
+                        &proposal.beneficiary,
+                        amount,
+                        Preservation::Expendable,
+                    )?;
+                }
+                SpendAmount::Asset(amount) => {
+                    let asset_id = network.asset_id.ok_or(Error::<T>::InvalidSpend)?;
+                    T::Assets::transfer(
+                        asset_id,
+                        &network.treasury,
+                        &proposal.beneficiary,
+                        amount,
+                        Preservation::Expendable,
+                    )?;
+                }
+            }
+            Self::remove_proposal(network_id, proposal_id);
+
+            Self::deposit_event(Event::SpendExecuted {
+                network_id,
+                proposal_id,
+                beneficiary: proposal.beneficiary,
+                amount: proposal.amount,
+            });
+            Ok(())
+        }
+
+        /// Count `amount` against the spend limit of a network, starting a new period if
+        /// the current one is over.
+        fn record_spend(network_id: u64, amount: SpendAmountOf<T>) -> DispatchResult {
+            let Some(limit) = SpendLimits::<T>::get(network_id) else {
+                return Ok(());
+            };
+            let now = frame_system::Pallet::<T>::block_number();
+            let mut period = SpendPeriods::<T>::get(network_id)
+                .filter(|period| now < period.started_at.saturating_add(limit.period))
+                .unwrap_or(SpendPeriod {
+                    started_at: now,
+                    native_spent: Zero::zero(),
+                    asset_spent: Zero::zero(),
+                });
+
+            match amount {
+                SpendAmount::Native(amount) => {
+                    period.native_spent = period
+                        .native_spent
+                        .checked_add(&amount)
+                        .filter(|spent| *spent <= limit.native)
+                        .ok_or(Error::<T>::SpendLimitExceeded)?;
+                }
+                SpendAmount::Asset(amount) => {
+                    period.asset_spent = period
+                        .asset_spent
+                        .checked_add(&amount)
+                        .filter(|spent| *spent <= limit.asset)
+                        .ok_or(Error::<T>::SpendLimitExceeded)?;
+                }
+            }
+            SpendPeriods::<T>::insert(network_id, period);
+            Ok(())
+        }
+
+        /// The account holding the network assets locked by votes.
+        pub fn vote_account() -> T::AccountId {
+            VOTING_PALLET_ID.into_account_truncating()
+        }
+
+        /// Move `balance` of asset `asset_id` from `who` to the vote account.
+        fn lock_vote(
+            asset_id: u32,
+            who: &T::AccountId,
+            balance: AssetBalanceOf<T>,
+        ) -> DispatchResult {
+            use fungibles::Mutate as _;
+
+            let vote_account = Self::vote_account();
+            // Like network treasuries, the vote account holds network assets without a
+            // native balance.
+            if frame_system::Pallet::<T>::providers(&vote_account) == 0 {
+                frame_system::Pallet::<T>::inc_providers(&vote_account);
+            }
+            T::Assets::transfer(
+                asset_id,
+                who,
+                &vote_account,
+                balance,
+                Preservation::Expendable,
+            )?;
+            Ok(())
+        }
+
+        /// Whether `who` belongs to `electorate` in network `network_id`.
+        fn in_electorate(
+            network_id: u64,
+            network: &NetworkInfo<T>,
+            electorate: Electorate,
+            who: &T::AccountId,
+        ) -> bool {
+            match electorate {
+                Electorate::Members => T::Members::is_member(network_id, who),
+                Electorate::AssetHolders => network.asset_id.is_some_and(|asset_id| {
+                    !<T::Assets as fungibles::Inspect<T::AccountId>>::balance(asset_id, who)
+                        .is_zero()
+                }),
+            }
+        }
+
+        /// The size of `electorate` in network `network_id`: its members, or the supply of
+        /// its asset.
+        fn electorate_size(
+            network_id: u64,
+            network: &NetworkInfo<T>,
+            electorate: Electorate,
+        ) -> u128 {
+            match electorate {
+                Electorate::Members => T::Members::member_count(network_id).into(),
+                Electorate::AssetHolders => network
+                    .asset_id
+                    .map(|asset_id| {
+                        <T::Assets as fungibles::Inspect<T::AccountId>>::total_issuance(asset_id)
+                            .saturated_into()
+                    })
+                    .unwrap_or_default(),
+            }
+        }
+
+        /// Ensure `config` suits a network that has an asset if `has_asset`.
+        fn ensure_valid_governance(
+            config: &GovernanceConfigOf<T>,
+            has_asset: bool,
+        ) -> DispatchResult {
+            let valid = !config.voting_period.is_zero()
+                && match config.electorate {
+                    Electorate::AssetHolders => has_asset,
+                    Electorate::Members => config.scheme == VotingScheme::SimpleMajority,
+                };
+            ensure!(valid, Error::<T>::InvalidGovernance);
+            Ok(())
+        }
+
+        /// Enact the action of a passed referendum or a network template.
+        fn enact(network_id: u64, action: NetworkAction) -> DispatchResult {
+            match action {
+                NetworkAction::SetMetadataUri(metadata_uri) => {
+                    Self::do_set_metadata_uri(network_id, metadata_uri);
+                    Ok(())
+                }
+                NetworkAction::SetProfile(profile) => {
+                    Self::do_set_profile(network_id, profile);
+                    Ok(())
+                }
+                action => T::ActionHandler::enact(network_id, &action)
+                    .unwrap_or_else(|| Err(Error::<T>::UnsupportedAction.into())),
+            }
+        }
+
+        /// Replace the metadata URI of a network and emit [`Event::MetadataUriUpdated`].
+        fn do_set_metadata_uri(network_id: u64, metadata_uri: BoundedVec<u8, ConstU32<256>>) {
+            Networks::<T>::mutate(network_id, |network| {
+                if let Some(network) = network {
+                    network.metadata_uri = metadata_uri;
+                }
+            });
+
+            let version = Self::bump_metadata_version(network_id);
+            Self::deposit_event(Event::MetadataUriUpdated {
+                network_id,
+                version,
+            });
+        }
+
+        /// Replace the profile of a network and emit [`Event::ProfileUpdated`].
+        fn do_set_profile(network_id: u64, profile: NetworkProfile) {
+            Profiles::<T>::insert(network_id, profile);
+
+            let version = Self::bump_metadata_version(network_id);
+            Self::deposit_event(Event::ProfileUpdated {
+                network_id,
+                version,
+            });
+        }
+
+        /// Remove an open spend proposal.
+        fn remove_proposal(network_id: u64, proposal_id: u32) {
+            Proposals::<T>::remove(network_id, proposal_id);
+            OpenProposals::<T>::mutate(network_id, |open| *open = open.saturating_sub(1));
+        }
+
+        /// Network `network_id` and its bonding curve, for a trade of `steps`.
+        fn curve_market(
+            network_id: u64,
+            steps: u32,
+        ) -> Result<(NetworkInfo<T>, BondingCurve<T>), DispatchError> {
+            ensure!(steps > 0, Error::<T>::InvalidTrade);
+            let network = Networks::<T>::get(network_id).ok_or(Error::<T>::NetworkNotFound)?;
+            let curve = Curves::<T>::get(network_id).ok_or(Error::<T>::NoCurve)?;
+            Ok((network, curve))
+        }
+
+        /// Convert a NAVI amount priced by a bonding curve to the native balance type.
+        fn curve_balance(amount: Option<u128>) -> Result<BalanceOf<T>, DispatchError> {
+            amount
+                .and_then(|amount| BalanceOf::<T>::try_from(amount).ok())
+                .ok_or(Error::<T>::CurveOverflow.into())
+        }
+
+        /// The amount of the network asset in `steps` steps of a bonding curve.
+        fn curve_amount(
+            config: &CurveConfigOf<T>,
+            steps: u32,
+        ) -> Result<AssetBalanceOf<T>, DispatchError> {
+            config
+                .step
+                .checked_mul(&steps.into())
+                .ok_or(Error::<T>::CurveOverflow.into())
+        }
+
+        /// Give network `network_id` the valid, free handle `handle`, releasing its old one.
+        fn do_set_handle(network_id: u64, handle: Vec<u8>) -> DispatchResult {
+            let handle = Self::free_handle(handle)?;
+
+            Self::release_handle(network_id);
+            Handles::<T>::insert(&handle, network_id);
+            NetworkHandles::<T>::insert(network_id, &handle);
+
+            Self::deposit_event(Event::HandleSet { network_id, handle });
+            Ok(())
+        }
+
+        /// Ensure `handle` is a valid handle no network has.
+        fn free_handle(handle: Vec<u8>) -> Result<Handle, DispatchError> {
+            ensure!(is_valid_handle(&handle), Error::<T>::InvalidHandle);
+            let handle = Handle::try_from(handle).map_err(|_| Error::<T>::InvalidHandle)?;
+            ensure!(
+                !Handles::<T>::contains_key(&handle),
+                Error::<T>::HandleTaken
+            );
+            Ok(handle)
+        }
+
+        /// Free the handle of network `network_id`, if it has one.
+        fn release_handle(network_id: u64) {
+            if let Some(handle) = NetworkHandles::<T>::take(network_id) {
+                Handles::<T>::remove(&handle);
+                Self::deposit_event(Event::HandleReleased { network_id, handle });
+            }
+        }
+
+        /// Ensure network `network_id` exists and is not closed.
+        fn ensure_not_closed(network_id: u64) -> DispatchResult {
+            let state = Self::state_of(network_id).ok_or(Error::<T>::NetworkNotFound)?;
+            ensure!(state != NetworkState::Closed, Error::<T>::NetworkClosed);
+            Ok(())
+        }
+
+        /// Add `who` to the allowlist of network `network_id`.
+        fn allowlist(network_id: u64, who: T::AccountId) -> DispatchResult {
+            ensure!(
+                !Allowlist::<T>::contains_key(network_id, &who),
+                Error::<T>::AlreadyAllowlisted
+            );
+            Allowlist::<T>::insert(network_id, &who, ());
+
+            Self::deposit_event(Event::Allowlisted { network_id, who });
+
+            Ok(())
+        }
+
+        /// Count one more network owned by `who`, within [`Config::MaxNetworksPerAccount`].
+        fn inc_owned_networks(who: &T::AccountId) -> DispatchResult {
+            OwnedNetworks::<T>::try_mutate(who, |count| -> DispatchResult {
+                ensure!(
+                    *count < T::MaxNetworksPerAccount::get(),
+                    Error::<T>::TooManyNetworks
+                );
+                *count += 1;
+                Ok(())
+            })
+        }
+
+        /// Increment the metadata version of a network and return the new version.
+        fn bump_metadata_version(network_id: u64) -> u32 {
+            MetadataVersions::<T>::mutate(network_id, |version| {
+                *version = version.saturating_add(1);
+                *version
+            })
+        }
+
+        /// The lifecycle state of network `network_id`, if it exists.
+        pub fn state_of(network_id: u64) -> Option<NetworkState> {
+            Networks::<T>::get(network_id).map(|network| network.state)
+        }
+
+        /// The role `who` holds in network `network_id`, if any. Nobody holds a role in a
+        /// closed network.
+        ///
+        /// In child networks owned by their parent's treasury, roles in the parent apply
+        /// too, and the stronger of the two roles counts.
+        pub fn role_of(network_id: u64, who: &T::AccountId) -> Option<NetworkRole> {
+            let network = Networks::<T>::get(network_id)?;
+            if network.state == NetworkState::Closed {
+                return None;
+            }
+            if network.owner == *who {
+                return Some(NetworkRole::Owner);
+            }
+            let held = Roles::<T>::get(network_id, who);
+            let inherited = Parents::<T>::get(network_id)
+                .filter(|link| network.owner == Self::treasury_account(link.parent))
+                .and_then(|link| Self::role_of(link.parent, who));
+            match (held, inherited) {
+                (Some(held), Some(inherited)) if !inherited.includes(held) => Some(held),
+                (held, inherited) => inherited.or(held),
+            }
+        }
+
+        /// The open child networks of network `network_id`, in ascending order.
+        pub fn children_of(network_id: u64) -> Vec<u64> {
+            let mut children: Vec<u64> = Children::<T>::iter_key_prefix(network_id).collect();
+            children.sort();
+            children
+        }
+
+        /// The ancestors of network `network_id`, from its parent up to the root.
+        pub fn ancestors_of(network_id: u64) -> Vec<u64> {
+            let mut ancestors = Vec::new();
+            let mut current = network_id;
+            while let Some(link) = Parents::<T>::get(current) {
+                ancestors.push(link.parent);
+                current = link.parent;
+            }
+            ancestors
+        }
+
+        /// The open descendants of network `network_id`, level by level.
+        ///
+        /// Meant for off-chain queries: the size is only bounded by
+        /// [`Config::MaxChildren`] and [`Config::MaxDepth`].
+        pub fn descendants_of(network_id: u64) -> Vec<u64> {
+            let mut descendants = Self::children_of(network_id);
+            let mut next = 0;
+            while let Some(&child) = descendants.get(next) {
+                descendants.extend(Self::children_of(child));
+                next += 1;
+            }
+            descendants
+        }
+
+        /// Network `network_id` as reported to clients.
+        pub fn network_details(network_id: u64) -> Option<NetworkDetails<T::AccountId>> {
+            let network = Networks::<T>::get(network_id)?;
+            Some(NetworkDetails {
+                network_id,
+                owner: network.owner,
+                coin_kind: network.coin_kind,
+                asset_id: network.asset_id,
+                treasury: network.treasury,
+                metadata_uri: network.metadata_uri.into_inner(),
+                state: network.state,
+                parent: Parents::<T>::get(network_id).map(|link| link.parent),
+                handle: NetworkHandles::<T>::get(network_id).map(BoundedVec::into_inner),
+            })
+        }
+
+        /// The network with handle `handle`, matched case-insensitively.
+        pub fn network_by_handle(handle: &[u8]) -> Option<NetworkDetails<T::AccountId>> {
+            let handle = Handle::try_from(handle.to_ascii_lowercase()).ok()?;
+            Self::network_details(Handles::<T>::get(handle)?)
+        }
+
+        /// The NAVI price of the next step of the bonding curve of network `network_id`.
+        pub fn curve_price(network_id: u64) -> Option<u128> {
+            Self::curve_buy_cost(network_id, 1)
+        }
+
+        /// The NAVI cost of buying `steps` steps from the bonding curve of a network.
+        pub fn curve_buy_cost(network_id: u64, steps: u32) -> Option<u128> {
+            let curve = Curves::<T>::get(network_id)?;
+            curve.config.cost(curve.supply, steps)
+        }
+
+        /// The NAVI returned for selling `steps` steps back to the bonding curve of a
+        /// network.
+        pub fn curve_sell_return(network_id: u64, steps: u32) -> Option<u128> {
+            let curve = Curves::<T>::get(network_id)?;
+            curve.config.cost(curve.supply.checked_sub(steps)?, steps)
+        }
+
+        /// Up to `limit` networks in ID order, starting at `start`, and at most
+        /// [`MAX_PAGE_SIZE`].
+        pub fn networks_page(start: u64, limit: u32) -> Vec<NetworkDetails<T::AccountId>> {
+            (start..NextNetworkId::<T>::get())
+                .filter_map(Self::network_details)
+                .take(limit.min(MAX_PAGE_SIZE) as usize)
+                .collect()
+        }
+
+        /// Up to `limit` networks `owner` owns, closed ones included, in ID order, starting
+        /// at `start`, and at most [`MAX_PAGE_SIZE`].
+        pub fn networks_by_owner(
+            owner: &T::AccountId,
+            start: u64,
+            limit: u32,
+        ) -> Vec<NetworkDetails<T::AccountId>> {
+            let mut network_ids = NetworksByOwner::<T>::iter_key_prefix(owner)
+                .filter(|network_id| *network_id >= start)
+                .collect::<Vec<_>>();
+            network_ids.sort();
+            network_ids
+                .into_iter()
+                .filter_map(Self::network_details)
+                .take(limit.min(MAX_PAGE_SIZE) as usize)
+                .collect()
+        }
+
+        /// Ensure `who` holds `role`, or a role including it, in network `network_id`.
+        pub fn ensure_role(
+            network_id: u64,
+            who: &T::AccountId,
+            role: NetworkRole,
+        ) -> DispatchResult {
+            let state = Self::state_of(network_id).ok_or(Error::<T>::NetworkNotFound)?;
+            ensure!(state != NetworkState::Closed, Error::<T>::NetworkClosed);
+            let held = Self::role_of(network_id, who).ok_or(Error::<T>::NotAuthorized)?;
+            ensure!(held.includes(role), Error::<T>::NotAuthorized);
+            Ok(())
+        }
+
+        /// Ensure `who` may grant or revoke `role`: owners manage every role and admins
+        /// manage moderators and billing managers.
+        fn ensure_can_manage(
+            network_id: u64,
+            who: &T::AccountId,
+            role: NetworkRole,
+        ) -> DispatchResult {
+            let required = match role {
+                NetworkRole::Owner | NetworkRole::Admin => NetworkRole::Owner,
+                NetworkRole::Moderator | NetworkRole::BillingManager => NetworkRole::Admin,
+            };
+            Self::ensure_role(network_id, who, required)
+        }
+
+        /// Create a network owned by `who`, minting an asset if `asset` is given and
+        /// enforcing its issuance policy if one is given. Returns the network ID.
+        fn do_create_network(
+            who: T::AccountId,
+            metadata_uri: Vec<u8>,
+            asset: Option<(AssetConfigOf<T>, Option<IssuanceConfigOf<T>>)>,
+        ) -> Result<u64, DispatchError> {
+            let use_navi = asset.is_none();
+
+            // Convert metadata_uri to BoundedVec
+            let bounded_uri: BoundedVec<u8, ConstU32<256>> = metadata_uri
+                .try_into()
+                .map_err(|_| Error::<T>::MetadataUriTooLong)?;
+
+            // Get next network ID
+            let network_id = Self::take_network_id()?;
+
+            // Hold the creation deposit
+            Self::inc_owned_networks(&who)?;
+            Self::hold_deposit(network_id, &who)?;
+
+            // Derive treasury account
+            let treasury = Self::treasury_account(network_id);
+
+            // Determine coin kind and asset ID
+            let (coin_kind, asset_id) = match &asset {
+                None => (CoinKind::UseNavi, None),
+                Some((asset, _)) => {
+                    let asset_id = Self::attach_asset(network_id, &who, &treasury, asset.clone())?;
+                    (CoinKind::MintAsset, Some(asset_id))
+                }
+            };
+
+            // Store network info
+            let network_info = NetworkInfo {
                 owner: who.clone(),
                 coin_kind,
                 asset_id,
-                treasury,
+                treasury: treasury.clone(),
                 metadata_uri: bounded_uri,
+                state: NetworkState::Active,
             };
             Networks::<T>::insert(network_id, network_info);
-
-            // Update next network ID
-            NextNetworkId::<T>::put(next_id);
+            NetworksByOwner::<T>::insert(&who, network_id, ());
 
             // Emit event
             Self::deposit_event(Event::NetworkCreated {
                 network_id,
-                owner: who,
+                owner: who.clone(),
                 use_navi,
                 asset_id,
             });
 
+            if let Some((_, Some(issuance))) = asset {
+                Self::apply_issuance(network_id, &who, &treasury, issuance)?;
+            }
+
+            Ok(network_id)
+        }
+
+        /// The asset of networks created without an asset configuration.
+        fn default_asset() -> AssetConfigOf<T> {
+            AssetConfig {
+                name: BoundedVec::new(),
+                symbol: BoundedVec::new(),
+                decimals: 0,
+                min_balance: T::DefaultAssetMinBalance::get(),
+                admin: AssetAdmin::Treasury,
+            }
+        }
+
+        /// Create the asset of network `network_id`, administered by its treasury or `owner`.
+        fn attach_asset(
+            network_id: u64,
+            owner: &T::AccountId,
+            treasury: &T::AccountId,
+            asset: AssetConfigOf<T>,
+        ) -> Result<u32, DispatchError> {
+            let asset_id = Self::allocate_asset_id()?;
+            let admin = match asset.admin {
+                AssetAdmin::Treasury => treasury.clone(),
+                AssetAdmin::Owner => owner.clone(),
+            };
+            Self::create_asset(asset_id, admin, asset)?;
+            AssetToNetwork::<T>::insert(asset_id, network_id);
+            // The treasury must be able to hold network coins without a native balance.
+            frame_system::Pallet::<T>::inc_providers(treasury);
+            Ok(asset_id)
+        }
+
+        /// Take the next network ID.
+        fn take_network_id() -> Result<u64, DispatchError> {
+            NextNetworkId::<T>::try_mutate(|next| -> Result<u64, DispatchError> {
+                let network_id = *next;
+                *next = network_id
+                    .checked_add(1)
+                    .ok_or(Error::<T>::NetworkIdOverflow)?;
+                Ok(network_id)
+            })
+        }
+
+        /// Hold the creation deposit of network `network_id` from `depositor`.
+        fn hold_deposit(network_id: u64, depositor: &T::AccountId) -> DispatchResult {
+            let deposit = T::NetworkDeposit::get();
+            if !deposit.is_zero() {
+                T::Currency::hold(&HoldReason::NetworkDeposit.into(), depositor, deposit)?;
+                NetworkDeposits::<T>::insert(network_id, (depositor.clone(), deposit));
+            }
+            Ok(())
+        }
+
+        /// Record the issuance policy of a new network and mint its initial allocations.
+        fn apply_issuance(
+            network_id: u64,
+            owner: &T::AccountId,
+            treasury: &T::AccountId,
+            issuance: IssuanceConfigOf<T>,
+        ) -> DispatchResult {
+            ensure!(
+                issuance.vesting.len() <= T::MaxVestingSchedules::get() as usize,
+                Error::<T>::TooManyVestingSchedules
+            );
+
+            let now = frame_system::Pallet::<T>::block_number();
+            let mut unvested = AssetBalanceOf::<T>::zero();
+            for vesting in issuance.vesting {
+                ensure!(
+                    !vesting.duration.is_zero()
+                        && vesting.cliff <= vesting.duration
+                        && !Vesting::<T>::contains_key(network_id, &vesting.beneficiary),
+                    Error::<T>::InvalidVestingSchedule
+                );
+                unvested = unvested
+                    .checked_add(&vesting.amount)
+                    .ok_or(Error::<T>::SupplyCapExceeded)?;
+                Vesting::<T>::insert(
+                    network_id,
+                    &vesting.beneficiary,
+                    VestingSchedule {
+                        total: vesting.amount,
+                        claimed: Zero::zero(),
+                        start: now,
+                        cliff: vesting.cliff,
+                        duration: vesting.duration,
+                    },
+                );
+            }
+
+            if let Some(max_supply) = issuance.max_supply {
+                let allocated = issuance
+                    .owner_allocation
+                    .checked_add(&issuance.treasury_allocation)
+                    .and_then(|allocated| allocated.checked_add(&unvested))
+                    .ok_or(Error::<T>::SupplyCapExceeded)?;
+                ensure!(allocated <= max_supply, Error::<T>::SupplyCapExceeded);
+            }
+
+            if let Some(config) = issuance.curve {
+                ensure!(
+                    !config.step.is_zero() && config.initial_price > 0,
+                    Error::<T>::InvalidCurve
+                );
+                Curves::<T>::insert(
+                    network_id,
+                    BondingCurve {
+                        config,
+                        supply: 0,
+                        reserve: Zero::zero(),
+                    },
+                );
+            }
+
+            Issuance::<T>::insert(
+                network_id,
+                IssuancePolicy {
+                    max_supply: issuance.max_supply,
+                    inflation_per_block: issuance.inflation_per_block,
+                    inflation_minted_at: now,
+                    unvested,
+                },
+            );
+
+            if !issuance.owner_allocation.is_zero() {
+                Self::mint(
+                    network_id,
+                    owner,
+                    issuance.owner_allocation,
+                    MintReason::OwnerAllocation,
+                )?;
+            }
+            if !issuance.treasury_allocation.is_zero() {
+                Self::mint(
+                    network_id,
+                    treasury,
+                    issuance.treasury_allocation,
+                    MintReason::TreasuryAllocation,
+                )?;
+            }
+
+            Ok(())
+        }
+
+        /// Mint network coins to `beneficiary` and emit [`Event::Minted`].
+        fn mint(
+            network_id: u64,
+            beneficiary: &T::AccountId,
+            amount: AssetBalanceOf<T>,
+            reason: MintReason,
+        ) -> DispatchResult {
+            let asset_id = Networks::<T>::get(network_id)
+                .and_then(|network| network.asset_id)
+                .ok_or(Error::<T>::NoIssuancePolicy)?;
+            <T::Assets as fungibles::Mutate<T::AccountId>>::mint_into(
+                asset_id,
+                beneficiary,
+                amount,
+            )
+            .map_err(|_| Error::<T>::MintFailed)?;
+
+            Self::deposit_event(Event::Minted {
+                network_id,
+                asset_id,
+                beneficiary: beneficiary.clone(),
+                amount,
+                reason,
+            });
+            Ok(())
+        }
+
+        /// Take the next free asset ID, skipping IDs of assets created outside the factory.
+        fn allocate_asset_id() -> Result<u32, DispatchError> {
+            let mut asset_id = NextAssetId::<T>::get();
+            for _ in 0..MAX_ASSET_ID_PROBES {
+                let next = asset_id
+                    .checked_add(1)
+                    .ok_or(Error::<T>::AssetIdUnavailable)?;
+                if !Self::asset_id_taken(asset_id) {
+                    NextAssetId::<T>::put(next);
+                    return Ok(asset_id);
+                }
+                asset_id = next;
+            }
+            Err(Error::<T>::AssetIdUnavailable.into())
+        }
+
+        /// Whether `asset_id` belongs to an existing asset or a network.
+        fn asset_id_taken(asset_id: u32) -> bool {
+            <T::Assets as fungibles::Inspect<T::AccountId>>::asset_exists(asset_id)
+                || AssetToNetwork::<T>::contains_key(asset_id)
+        }
+
+        /// Move [`NextAssetId`] past taken asset IDs within `remaining_weight`.
+        ///
+        /// Asset creation gives up after [`MAX_ASSET_ID_PROBES`] taken IDs and its storage
+        /// changes are reverted, so a longer run of taken IDs is skipped here over idle blocks.
+        fn skip_taken_asset_ids(remaining_weight: Weight) -> Weight {
+            let db = T::DbWeight::get();
+            let per_probe = db.reads(2);
+            let write = db.writes(1);
+            let mut consumed = db.reads(1);
+            if remaining_weight.any_lt(consumed.saturating_add(per_probe).saturating_add(write)) {
+                return Weight::zero();
+            }
+
+            let start = NextAssetId::<T>::get();
+            let mut asset_id = start;
+            for _ in 0..MAX_ASSET_ID_PROBES {
+                if remaining_weight.any_lt(consumed.saturating_add(per_probe).saturating_add(write))
+                {
+                    break;
+                }
+                consumed.saturating_accrue(per_probe);
+                if !Self::asset_id_taken(asset_id) {
+                    break;
+                }
+                let Some(next) = asset_id.checked_add(1) else {
+                    break;
+                };
+                asset_id = next;
+            }
+            if asset_id != start {
+                NextAssetId::<T>::put(asset_id);
+                consumed.saturating_accrue(write);
+            }
+            consumed
+        }
+
+        /// Create asset `asset_id` administered by `admin` and set its metadata.
+        fn create_asset(
+            asset_id: u32,
+            admin: T::AccountId,
+            asset: AssetConfigOf<T>,
+        ) -> DispatchResult {
+            <T::Assets as fungibles::Create<T::AccountId>>::create(
+                asset_id,
+                admin.clone(),
+                false,
+                asset.min_balance,
+            )
+            .map_err(|_| Error::<T>::AssetCreationFailed)?;
+
+            if !asset.name.is_empty() || !asset.symbol.is_empty() {
+                <T::Assets as fungibles::metadata::Mutate<T::AccountId>>::set(
+                    asset_id,
+                    &admin,
+                    asset.name.into_inner(),
+                    asset.symbol.into_inner(),
+                    asset.decimals,
+                )
+                .map_err(|_| Error::<T>::AssetCreationFailed)?;
+            }
+
             Ok(())
         }
     }
 
-    impl<T: Config> Pallet<T> {
-        /// Derive the treasury account for a network.
-        pub fn treasury_account(network_id: u64) -> T::AccountId {
-            PALLET_ID.into_sub_account_truncating(network_id)
+    impl<T: Config> NetworkInspect<T::AccountId> for Pallet<T> {
+        fn network_exists(network_id: u64) -> bool {
+            Networks::<T>::contains_key(network_id)
+        }
+
+        fn is_active(network_id: u64) -> bool {
+            Self::state_of(network_id) == Some(NetworkState::Active)
+        }
+
+        fn has_role(network_id: u64, who: &T::AccountId, role: NetworkRole) -> bool {
+            Self::role_of(network_id, who).is_some_and(|held| held.includes(role))
+        }
+
+        fn membership_parent(network_id: u64) -> Option<u64> {
+            Parents::<T>::get(network_id)
+                .filter(|link| link.inherit_membership)
+                .map(|link| link.parent)
+        }
+
+        fn is_admitted(network_id: u64, who: &T::AccountId) -> bool {
+            Admission::<T>::get(network_id) == AdmissionMode::Open
+                || Allowlist::<T>::contains_key(network_id, who)
+                || Self::role_of(network_id, who).is_some()
         }
     }
 }
@@ -0,0 +1,219 @@
+//! Storage migrations for the network factory pallet.
+
+use crate::{AssetToNetwork, Config, NextAssetId, Pallet};
+use frame_support::{
+    migrations::VersionedMigration,
+    traits::{Get, UncheckedOnRuntimeUpgrade},
+    weights::Weight,
+};
+
+/// Version 1: record the network of each existing asset and start the asset ID counter
+/// after the highest asset ID in use.
+pub mod v1 {
+    use super::*;
+    use crate::CoinKind;
+    use codec::{Decode, Encode, MaxEncodedLen};
+    use frame_support::{pallet_prelude::*, storage_alias};
+    use sp_runtime::RuntimeDebug;
+
+    /// Network information before lifecycle states were added in version 2.
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    #[scale_info(skip_type_params(T))]
+    pub struct OldNetworkInfo<T: Config> {
+        /// The owner of the network.
+        pub owner: T::AccountId,
+        /// The type of coin this network uses.
+        pub coin_kind: CoinKind,
+        /// The asset ID if using MintAsset.
+        pub asset_id: Option<u32>,
+        /// The treasury account for this network.
+        pub treasury: T::AccountId,
+        /// Metadata URI for the network.
+        pub metadata_uri: BoundedVec<u8, ConstU32<256>>,
+    }
+
+    /// [`crate::Networks`] with the version 0 and 1 layout.
+    #[storage_alias]
+    pub type Networks<T: Config> = StorageMap<Pallet<T>, Blake2_128Concat, u64, OldNetworkInfo<T>>;
+
+    /// Populates [`AssetToNetwork`] and [`NextAssetId`] for networks created before
+    /// asset IDs were allocated independently of network IDs.
+    pub struct InnerMigrateV0ToV1<T>(core::marker::PhantomData<T>);
+
+    impl<T: Config> UncheckedOnRuntimeUpgrade for InnerMigrateV0ToV1<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let mut reads = 0u64;
+            let mut writes = 0u64;
+            let mut next_asset_id = NextAssetId::<T>::get();
+
+            for (network_id, network) in Networks::<T>::iter() {
+                reads += 1;
+                if let Some(asset_id) = network.asset_id {
+                    AssetToNetwork::<T>::insert(asset_id, network_id);
+                    writes += 1;
+                    next_asset_id = next_asset_id.max(asset_id.saturating_add(1));
+                }
+            }
+
+            NextAssetId::<T>::put(next_asset_id);
+            T::DbWeight::get().reads_writes(reads + 1, writes + 1)
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(_state: alloc::vec::Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+            for (network_id, network) in Networks::<T>::iter() {
+                if let Some(asset_id) = network.asset_id {
+                    frame_support::ensure!(
+                        AssetToNetwork::<T>::get(asset_id) == Some(network_id),
+                        "asset is not mapped to its network"
+                    );
+                    frame_support::ensure!(
+                        asset_id < NextAssetId::<T>::get(),
+                        "next asset ID is already in use"
+                    );
+                }
+            }
+            Ok(())
+        }
+    }
+
+    /// [`InnerMigrateV0ToV1`] gated on the on-chain storage version.
+    pub type MigrateV0ToV1<T> = VersionedMigration<
+        0,
+        1,
+        InnerMigrateV0ToV1<T>,
+        Pallet<T>,
+        <T as frame_system::Config>::DbWeight,
+    >;
+}
+
+/// Version 2: add a lifecycle state to every network, starting out active.
+pub mod v2 {
+    use super::*;
+    use crate::{NetworkInfo, NetworkState};
+
+    /// Translates [`v1::OldNetworkInfo`] into [`NetworkInfo`] with an active state.
+    pub struct InnerMigrateV1ToV2<T>(core::marker::PhantomData<T>);
+
+    impl<T: Config> UncheckedOnRuntimeUpgrade for InnerMigrateV1ToV2<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let mut translated = 0u64;
+            crate::Networks::<T>::translate::<v1::OldNetworkInfo<T>, _>(|_, old| {
+                translated += 1;
+                Some(NetworkInfo {
+                    owner: old.owner,
+                    coin_kind: old.coin_kind,
+                    asset_id: old.asset_id,
+                    treasury: old.treasury,
+                    metadata_uri: old.metadata_uri,
+                    state: NetworkState::Active,
+                })
+            });
+            T::DbWeight::get().reads_writes(translated, translated)
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade() -> Result<alloc::vec::Vec<u8>, sp_runtime::TryRuntimeError> {
+            use codec::Encode;
+            Ok((v1::Networks::<T>::iter_keys().count() as u64).encode())
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(state: alloc::vec::Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+            use codec::Decode;
+            let count = u64::decode(&mut &state[..])
+                .map_err(|_| sp_runtime::TryRuntimeError::Other("invalid pre-upgrade state"))?;
+            frame_support::ensure!(
+                crate::Networks::<T>::iter().count() as u64 == count,
+                "networks were lost in translation"
+            );
+            Ok(())
+        }
+    }
+
+    /// [`InnerMigrateV1ToV2`] gated on the on-chain storage version.
+    pub type MigrateV1ToV2<T> = VersionedMigration<
+        1,
+        2,
+        InnerMigrateV1ToV2<T>,
+        Pallet<T>,
+        <T as frame_system::Config>::DbWeight,
+    >;
+}
+
+/// Version 3: count the networks each account owns, so the per-account limit covers
+/// networks created before it existed. These networks have no creation deposit.
+pub mod v3 {
+    use super::*;
+    use crate::{NetworkState, OwnedNetworks};
+
+    /// Populates [`OwnedNetworks`] from the networks that are not closed.
+    pub struct InnerMigrateV2ToV3<T>(core::marker::PhantomData<T>);
+
+    impl<T: Config> UncheckedOnRuntimeUpgrade for InnerMigrateV2ToV3<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let mut reads = 0u64;
+            let mut writes = 0u64;
+            for network in crate::Networks::<T>::iter_values() {
+                reads += 1;
+                if network.state != NetworkState::Closed {
+                    OwnedNetworks::<T>::mutate(&network.owner, |count| {
+                        *count = count.saturating_add(1)
+                    });
+                    reads += 1;
+                    writes += 1;
+                }
+            }
+            T::DbWeight::get().reads_writes(reads, writes)
+        }
+    }
+
+    /// [`InnerMigrateV2ToV3`] gated on the on-chain storage version.
+    pub type MigrateV2ToV3<T> = VersionedMigration<
+        2,
+        3,
+        InnerMigrateV2ToV3<T>,
+        Pallet<T>,
+        <T as frame_system::Config>::DbWeight,
+    >;
+}
+
+/// Version 4: index networks by owner.
+pub mod v4 {
+    use super::*;
+    use crate::NetworksByOwner;
+
+    /// Populates [`NetworksByOwner`] from every network, closed ones included.
+    pub struct InnerMigrateV3ToV4<T>(core::marker::PhantomData<T>);
+
+    impl<T: Config> UncheckedOnRuntimeUpgrade for InnerMigrateV3ToV4<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let mut count = 0u64;
+            for (network_id, network) in crate::Networks::<T>::iter() {
+                NetworksByOwner::<T>::insert(&network.owner, network_id, ());
+                count += 1;
+            }
+            T::DbWeight::get().reads_writes(count, count)
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(_state: alloc::vec::Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+            for (network_id, network) in crate::Networks::<T>::iter() {
+                frame_support::ensure!(
+                    NetworksByOwner::<T>::contains_key(&network.owner, network_id),
+                    "network is not indexed by its owner"
+                );
+            }
+            Ok(())
+        }
+    }
+
+    /// [`InnerMigrateV3ToV4`] gated on the on-chain storage version.
+    pub type MigrateV3ToV4<T> = VersionedMigration<
+        3,
+        4,
+        InnerMigrateV3ToV4<T>,
+        Pallet<T>,
+        <T as frame_system::Config>::DbWeight,
+    >;
+}
@@ -1,5 +1,10 @@
 use crate as pallet_network_factory;
-use frame_support::derive_impl;
+use frame_support::{
+    derive_impl,
+    traits::{AsEnsureOriginWithArg, ConstU32, ConstU64, NeverEnsureOrigin},
+    weights::constants::RocksDbWeight,
+};
+use frame_system::EnsureRoot;
 use sp_runtime::BuildStorage;
 
 type Block = frame_system::mocking::MockBlock<Test>;
@@ -7,6 +12,8 @@ type Block = frame_system::mocking::MockBlock<Test>;
 frame_support::construct_runtime!(
     pub enum Test {
         System: frame_system,
+        Balances: pallet_balances,
+        Assets: pallet_assets,
         NetworkFactory: pallet_network_factory,
     }
 );
@@ -14,15 +21,71 @@ frame_support::construct_runtime!(
 #[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
 impl frame_system::Config for Test {
     type Block = Block;
+    type DbWeight = RocksDbWeight;
+    type AccountData = pallet_balances::AccountData<u64>;
+}
+
+#[derive_impl(pallet_balances::config_preludes::TestDefaultConfig)]
+impl pallet_balances::Config for Test {
+    type AccountStore = System;
+}
+
+#[derive_impl(pallet_assets::config_preludes::TestDefaultConfig)]
+impl pallet_assets::Config for Test {
+    type Currency = Balances;
+    type CreateOrigin = AsEnsureOriginWithArg<NeverEnsureOrigin<u64>>;
+    type ForceOrigin = EnsureRoot<u64>;
+    type MetadataDepositBase = ConstU64<0>;
+    type MetadataDepositPerByte = ConstU64<0>;
+    type Freezer = ();
+    type Holder = ();
 }
 
 impl pallet_network_factory::Config for Test {
     type RuntimeEvent = RuntimeEvent;
+    type Assets = Assets;
+    type DefaultAssetMinBalance = ConstU64<1>;
+    type MaxVestingSchedules = ConstU32<2>;
+    type RuntimeHoldReason = RuntimeHoldReason;
+    type Currency = Balances;
+    type NetworkDeposit = ConstU64<10>;
+    type MaxNetworksPerAccount = ConstU32<3>;
+    type GovernanceOrigin = EnsureRoot<u64>;
+    type Cleanup = ();
+    type MaxCouncilMembers = ConstU32<3>;
+    type MaxSpendProposals = ConstU32<3>;
+    type Members = MockMembers;
+    type ActionHandler = ();
+    type MaxReferenda = ConstU32<2>;
+    type VoteLockingPeriod = ConstU64<10>;
+    type MaxChildren = ConstU32<2>;
+    type MaxDepth = ConstU32<3>;
+    type TemplateDeposit = ConstU64<5>;
+    type MaxTemplateResources = ConstU32<2>;
+}
+
+/// Accounts 2 and 3 are members of every network, which have four members each.
+pub struct MockMembers;
+
+impl pallet_network_factory::NetworkMembership<u64> for MockMembers {
+    fn is_member(_network_id: u64, who: &u64) -> bool {
+        *who == 2 || *who == 3
+    }
+
+    fn member_count(_network_id: u64) -> u32 {
+        4
+    }
 }
 
 pub fn new_test_ext() -> sp_io::TestExternalities {
-    frame_system::GenesisConfig::<Test>::default()
+    let mut t = frame_system::GenesisConfig::<Test>::default()
         .build_storage()
-        .unwrap()
-        .into()
+        .unwrap();
+    pallet_balances::GenesisConfig::<Test> {
+        balances: vec![(1, 1_000), (2, 1_000), (3, 1_000)],
+        ..Default::default()
+    }
+    .assimilate_storage(&mut t)
+    .unwrap();
+    t.into()
 }
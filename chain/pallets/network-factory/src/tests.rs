@@ -1,5 +1,34 @@
-use crate::{mock::*, CoinKind, Event};
-use frame_support::{assert_noop, assert_ok};
+use crate::{
+    is_valid_handle, migrations, mock::*, AdmissionMode, AssetAdmin, AssetConfig, ChildOwner,
+    CoinKind, ConversionRate, Conviction, CurveConfig, CurveKind, Electorate, Error, Event,
+    GovernanceConfig, Handle, IssuanceConfig, MintReason, NetworkAction, NetworkDetails,
+    NetworkInspect, NetworkProfile, NetworkRole, NetworkState, ResourceSpec, SpendAmount,
+    SpendLimit, Tally, TreasuryPolicy, VestingConfig, VotingScheme,
+};
+use frame_support::{
+    assert_noop, assert_ok,
+    traits::{
+        fungible::{InspectHold, Mutate as _},
+        fungibles::{
+            metadata::Inspect as MetadataInspect, roles::Inspect as RolesInspect, Inspect,
+        },
+        tokens::Preservation,
+        GetStorageVersion, Hooks, OnRuntimeUpgrade, StorageVersion,
+    },
+    weights::{constants::RocksDbWeight, Weight},
+};
+use sp_io::hashing::blake2_256;
+use sp_runtime::Perbill;
+
+fn asset_config(admin: AssetAdmin) -> AssetConfig<u64> {
+    AssetConfig {
+        name: b"Test Coin".to_vec().try_into().unwrap(),
+        symbol: b"TST".to_vec().try_into().unwrap(),
+        decimals: 12,
+        min_balance: 5,
+        admin,
+    }
+}
 
 #[test]
 fn create_network_with_navi_works() {
@@ -68,6 +97,82 @@ fn create_network_with_custom_asset_works() {
         assert_eq!(network.owner, 2);
         assert_eq!(network.coin_kind, CoinKind::MintAsset);
         assert_eq!(network.asset_id, Some(expected_asset_id));
+
+        // The asset exists and is administered by the treasury
+        let treasury = NetworkFactory::treasury_account(0);
+        assert_eq!(Assets::admin(expected_asset_id), Some(treasury));
+        assert_eq!(Assets::owner(expected_asset_id), Some(treasury));
+        assert_eq!(Assets::minimum_balance(expected_asset_id), 1);
+    });
+}
+
+#[test]
+fn create_network_with_asset_sets_metadata() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+
+        assert_ok!(NetworkFactory::create_network_with_asset(
+            RuntimeOrigin::signed(2),
+            b"ipfs://QmCustom".to_vec(),
+            asset_config(AssetAdmin::Treasury),
+            IssuanceConfig::default(),
+        ));
+
+        System::assert_last_event(
+            Event::NetworkCreated {
+                network_id: 0,
+                owner: 2,
+                use_navi: false,
+                asset_id: Some(0),
+            }
+            .into(),
+        );
+
+        let network = NetworkFactory::networks(0).unwrap();
+        assert_eq!(network.coin_kind, CoinKind::MintAsset);
+        assert_eq!(Assets::admin(0), Some(NetworkFactory::treasury_account(0)));
+        assert_eq!(Assets::minimum_balance(0), 5);
+        assert_eq!(Assets::name(0), b"Test Coin".to_vec());
+        assert_eq!(Assets::symbol(0), b"TST".to_vec());
+        assert_eq!(Assets::decimals(0), 12);
+    });
+}
+
+#[test]
+fn create_network_with_asset_can_be_administered_by_owner() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+
+        assert_ok!(NetworkFactory::create_network_with_asset(
+            RuntimeOrigin::signed(2),
+            b"owned".to_vec(),
+            asset_config(AssetAdmin::Owner),
+            IssuanceConfig::default(),
+        ));
+
+        assert_eq!(Assets::admin(0), Some(2));
+        assert_eq!(Assets::owner(0), Some(2));
+        assert_eq!(Assets::symbol(0), b"TST".to_vec());
+    });
+}
+
+#[test]
+fn asset_creation_failure_is_reported() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+
+        // Zero minimum balances are rejected
+        let mut asset = asset_config(AssetAdmin::Owner);
+        asset.min_balance = 0;
+        assert_noop!(
+            NetworkFactory::create_network_with_asset(
+                RuntimeOrigin::signed(1),
+                b"zero".to_vec(),
+                asset,
+                IssuanceConfig::default()
+            ),
+            Error::<Test>::AssetCreationFailed
+        );
     });
 }
 
@@ -132,3 +237,2879 @@ fn treasury_account_is_derived_correctly() {
         assert_eq!(network.treasury, expected_treasury);
     });
 }
+
+#[test]
+fn asset_ids_skip_existing_assets() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+
+        // Assets 0 and 1 were created outside the factory
+        assert_ok!(Assets::force_create(RuntimeOrigin::root(), 0, 3, true, 1));
+        assert_ok!(Assets::force_create(RuntimeOrigin::root(), 1, 3, true, 1));
+
+        assert_ok!(NetworkFactory::create_network(
+            RuntimeOrigin::signed(1),
+            false,
+            b"first".to_vec()
+        ));
+        assert_eq!(NetworkFactory::networks(0).unwrap().asset_id, Some(2));
+        assert_eq!(Assets::owner(2), Some(NetworkFactory::treasury_account(0)));
+
+        // NAVI networks don't consume asset IDs
+        assert_ok!(NetworkFactory::create_network(
+            RuntimeOrigin::signed(1),
+            true,
+            b"navi".to_vec()
+        ));
+        assert_ok!(NetworkFactory::create_network(
+            RuntimeOrigin::signed(2),
+            false,
+            b"second".to_vec()
+        ));
+        System::assert_last_event(
+            Event::NetworkCreated {
+                network_id: 2,
+                owner: 2,
+                use_navi: false,
+                asset_id: Some(3),
+            }
+            .into(),
+        );
+
+        assert_eq!(NetworkFactory::asset_network(2), Some(0));
+        assert_eq!(NetworkFactory::asset_network(3), Some(2));
+        assert_eq!(NetworkFactory::asset_network(0), None);
+        assert_eq!(NetworkFactory::next_asset_id(), 4);
+    });
+}
+
+#[test]
+fn asset_id_allocation_gives_up_after_too_many_taken_ids() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+
+        // Only root and the factory may create assets
+        assert_noop!(
+            Assets::create(RuntimeOrigin::signed(3), 0, 3, 1),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        for asset_id in 0..20 {
+            assert_ok!(Assets::force_create(
+                RuntimeOrigin::root(),
+                asset_id,
+                3,
+                true,
+                1
+            ));
+        }
+
+        assert_noop!(
+            NetworkFactory::create_network(RuntimeOrigin::signed(1), false, b"full".to_vec()),
+            Error::<Test>::AssetIdUnavailable
+        );
+
+        // Idle blocks move the cursor past the taken IDs
+        NetworkFactory::on_idle(2, Weight::MAX);
+        assert_eq!(NetworkFactory::next_asset_id(), 16);
+        assert_ok!(NetworkFactory::create_network(
+            RuntimeOrigin::signed(1),
+            false,
+            b"full".to_vec()
+        ));
+        assert_eq!(NetworkFactory::networks(0).unwrap().asset_id, Some(20));
+        assert_eq!(NetworkFactory::next_asset_id(), 21);
+    });
+}
+
+#[test]
+fn migrations_upgrade_v0_networks() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+
+        // Simulate v0 networks whose asset IDs were their truncated network IDs
+        let old_network = |asset_id: Option<u32>| migrations::v1::OldNetworkInfo::<Test> {
+            owner: 1,
+            coin_kind: if asset_id.is_some() {
+                CoinKind::MintAsset
+            } else {
+                CoinKind::UseNavi
+            },
+            asset_id,
+            treasury: NetworkFactory::treasury_account(0),
+            metadata_uri: b"old".to_vec().try_into().unwrap(),
+        };
+        migrations::v1::Networks::<Test>::insert(0, old_network(Some(0)));
+        migrations::v1::Networks::<Test>::insert(1, old_network(None));
+        migrations::v1::Networks::<Test>::insert(7, old_network(Some(7)));
+        StorageVersion::new(0).put::<NetworkFactory>();
+
+        migrations::v1::MigrateV0ToV1::<Test>::on_runtime_upgrade();
+
+        assert_eq!(NetworkFactory::on_chain_storage_version(), 1);
+        assert_eq!(NetworkFactory::asset_network(0), Some(0));
+        assert_eq!(NetworkFactory::asset_network(7), Some(7));
+        assert_eq!(NetworkFactory::next_asset_id(), 8);
+
+        migrations::v2::MigrateV1ToV2::<Test>::on_runtime_upgrade();
+
+        assert_eq!(NetworkFactory::on_chain_storage_version(), 2);
+        for network_id in [0, 1, 7] {
+            let network = NetworkFactory::networks(network_id).unwrap();
+            assert_eq!(network.state, NetworkState::Active);
+            assert_eq!(network.metadata_uri.to_vec(), b"old".to_vec());
+        }
+        assert_eq!(NetworkFactory::networks(7).unwrap().asset_id, Some(7));
+
+        migrations::v3::MigrateV2ToV3::<Test>::on_runtime_upgrade();
+
+        assert_eq!(NetworkFactory::on_chain_storage_version(), 3);
+        assert_eq!(NetworkFactory::owned_networks(1), 3);
+        assert_eq!(NetworkFactory::network_deposit(0), None);
+
+        migrations::v4::MigrateV3ToV4::<Test>::on_runtime_upgrade();
+
+        assert_eq!(NetworkFactory::on_chain_storage_version(), 4);
+        let ids = |networks: Vec<NetworkDetails<u64>>| {
+            networks
+                .into_iter()
+                .map(|network| network.network_id)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            ids(NetworkFactory::networks_by_owner(&1, 0, 10)),
+            vec![0, 1, 7]
+        );
+        assert_eq!(ids(NetworkFactory::networks_by_owner(&1, 1, 1)), vec![1]);
+        assert_eq!(ids(NetworkFactory::networks_by_owner(&1, 2, 10)), vec![7]);
+    });
+}
+
+fn issuance_config() -> IssuanceConfig<u64, u64, u64> {
+    IssuanceConfig {
+        max_supply: Some(1_000),
+        owner_allocation: 100,
+        treasury_allocation: 200,
+        vesting: vec![VestingConfig {
+            beneficiary: 3,
+            amount: 400,
+            cliff: 10,
+            duration: 100,
+        }],
+        inflation_per_block: 5,
+        curve: None,
+    }
+}
+
+fn create_issued_network() {
+    assert_ok!(NetworkFactory::create_network_with_asset(
+        RuntimeOrigin::signed(1),
+        b"issued".to_vec(),
+        asset_config(AssetAdmin::Treasury),
+        issuance_config(),
+    ));
+}
+
+#[test]
+fn initial_allocations_are_minted() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        create_issued_network();
+
+        let treasury = NetworkFactory::treasury_account(0);
+        System::assert_has_event(
+            Event::Minted {
+                network_id: 0,
+                asset_id: 0,
+                beneficiary: 1,
+                amount: 100,
+                reason: MintReason::OwnerAllocation,
+            }
+            .into(),
+        );
+        System::assert_last_event(
+            Event::Minted {
+                network_id: 0,
+                asset_id: 0,
+                beneficiary: treasury,
+                amount: 200,
+                reason: MintReason::TreasuryAllocation,
+            }
+            .into(),
+        );
+
+        assert_eq!(Assets::balance(0, 1), 100);
+        assert_eq!(Assets::balance(0, treasury), 200);
+        assert_eq!(Assets::total_issuance(0), 300);
+
+        let policy = NetworkFactory::issuance(0).unwrap();
+        assert_eq!(policy.max_supply, Some(1_000));
+        assert_eq!(policy.unvested, 400);
+        let schedule = NetworkFactory::vesting(0, 3).unwrap();
+        assert_eq!((schedule.total, schedule.start), (400, 1));
+    });
+}
+
+#[test]
+fn allocations_beyond_max_supply_fail() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+
+        let mut issuance = issuance_config();
+        issuance.max_supply = Some(699);
+        assert_noop!(
+            NetworkFactory::create_network_with_asset(
+                RuntimeOrigin::signed(1),
+                b"issued".to_vec(),
+                asset_config(AssetAdmin::Treasury),
+                issuance,
+            ),
+            Error::<Test>::SupplyCapExceeded
+        );
+    });
+}
+
+#[test]
+fn invalid_vesting_schedules_fail() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+
+        let create = |issuance| {
+            NetworkFactory::create_network_with_asset(
+                RuntimeOrigin::signed(1),
+                b"issued".to_vec(),
+                asset_config(AssetAdmin::Treasury),
+                issuance,
+            )
+        };
+
+        let mut issuance = issuance_config();
+        issuance.vesting[0].cliff = 101;
+        assert_noop!(create(issuance), Error::<Test>::InvalidVestingSchedule);
+
+        let mut issuance = issuance_config();
+        issuance.vesting[0].duration = 0;
+        issuance.vesting[0].cliff = 0;
+        assert_noop!(create(issuance), Error::<Test>::InvalidVestingSchedule);
+
+        let mut issuance = issuance_config();
+        issuance.vesting.push(issuance.vesting[0].clone());
+        assert_noop!(create(issuance), Error::<Test>::InvalidVestingSchedule);
+
+        let mut issuance = issuance_config();
+        issuance.max_supply = None;
+        issuance.vesting = (2..5)
+            .map(|beneficiary| VestingConfig {
+                beneficiary,
+                amount: 10,
+                cliff: 0,
+                duration: 10,
+            })
+            .collect();
+        assert_noop!(create(issuance), Error::<Test>::TooManyVestingSchedules);
+    });
+}
+
+#[test]
+fn vesting_releases_after_cliff_linearly() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        create_issued_network();
+
+        // Nothing before the cliff
+        System::set_block_number(10);
+        assert_noop!(
+            NetworkFactory::claim_vested(RuntimeOrigin::signed(3), 0),
+            Error::<Test>::NothingToClaim
+        );
+        assert_noop!(
+            NetworkFactory::claim_vested(RuntimeOrigin::signed(2), 0),
+            Error::<Test>::NoVestingSchedule
+        );
+
+        // A quarter of the duration has passed
+        System::set_block_number(26);
+        assert_ok!(NetworkFactory::claim_vested(RuntimeOrigin::signed(3), 0));
+        System::assert_last_event(
+            Event::Minted {
+                network_id: 0,
+                asset_id: 0,
+                beneficiary: 3,
+                amount: 100,
+                reason: MintReason::Vesting,
+            }
+            .into(),
+        );
+        assert_eq!(Assets::balance(0, 3), 100);
+        assert_eq!(NetworkFactory::issuance(0).unwrap().unvested, 300);
+        assert_noop!(
+            NetworkFactory::claim_vested(RuntimeOrigin::signed(3), 0),
+            Error::<Test>::NothingToClaim
+        );
+
+        // Everything after the duration
+        System::set_block_number(200);
+        assert_ok!(NetworkFactory::claim_vested(RuntimeOrigin::signed(3), 0));
+        assert_eq!(Assets::balance(0, 3), 400);
+        assert_eq!(NetworkFactory::issuance(0).unwrap().unvested, 0);
+        assert!(NetworkFactory::vesting(0, 3).is_none());
+    });
+}
+
+#[test]
+fn inflation_is_minted_to_treasury_up_to_max_supply() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        create_issued_network();
+        let treasury = NetworkFactory::treasury_account(0);
+
+        assert_noop!(
+            NetworkFactory::mint_inflation(RuntimeOrigin::signed(2), 0),
+            Error::<Test>::NothingToMint
+        );
+
+        System::set_block_number(11);
+        assert_ok!(NetworkFactory::mint_inflation(RuntimeOrigin::signed(2), 0));
+        System::assert_last_event(
+            Event::Minted {
+                network_id: 0,
+                asset_id: 0,
+                beneficiary: treasury,
+                amount: 50,
+                reason: MintReason::Inflation,
+            }
+            .into(),
+        );
+        assert_eq!(Assets::balance(0, treasury), 250);
+
+        // Nothing is minted while the network is paused
+        assert_ok!(NetworkFactory::set_network_state(
+            RuntimeOrigin::signed(1),
+            0,
+            NetworkState::Paused
+        ));
+        System::set_block_number(20);
+        assert_noop!(
+            NetworkFactory::mint_inflation(RuntimeOrigin::signed(2), 0),
+            Error::<Test>::NetworkNotActive
+        );
+        assert_ok!(NetworkFactory::set_network_state(
+            RuntimeOrigin::signed(1),
+            0,
+            NetworkState::Active
+        ));
+
+        // Inflation resumes from the reactivation, not from before the pause
+        System::set_block_number(22);
+        assert_ok!(NetworkFactory::mint_inflation(RuntimeOrigin::signed(2), 0));
+        assert_eq!(Assets::balance(0, treasury), 260);
+
+        // Inflation leaves room for the unvested 400
+        System::set_block_number(1_000);
+        assert_ok!(NetworkFactory::mint_inflation(RuntimeOrigin::signed(2), 0));
+        assert_eq!(Assets::total_issuance(0), 600);
+        System::set_block_number(2_000);
+        assert_noop!(
+            NetworkFactory::mint_inflation(RuntimeOrigin::signed(2), 0),
+            Error::<Test>::NothingToMint
+        );
+
+        // Networks without an issuance policy have no inflation
+        assert_ok!(NetworkFactory::create_network(
+            RuntimeOrigin::signed(1),
+            false,
+            b"plain".to_vec()
+        ));
+        assert_noop!(
+            NetworkFactory::mint_inflation(RuntimeOrigin::signed(2), 1),
+            Error::<Test>::NoIssuancePolicy
+        );
+        assert_noop!(
+            NetworkFactory::mint_inflation(RuntimeOrigin::signed(2), 9),
+            Error::<Test>::NetworkNotFound
+        );
+    });
+}
+
+#[test]
+fn curve_prices_rise_per_step() {
+    let linear = CurveConfig {
+        kind: CurveKind::Linear { increment: 2 },
+        initial_price: 10,
+        step: 100u64,
+    };
+    assert_eq!(linear.total_cost(0), Some(0));
+    // 10 + 12 + 14
+    assert_eq!(linear.total_cost(3), Some(36));
+    assert_eq!(linear.cost(1, 2), Some(26));
+
+    let exponential = CurveConfig {
+        kind: CurveKind::Exponential {
+            growth: Perbill::from_percent(10),
+        },
+        initial_price: 100,
+        step: 100u64,
+    };
+    // 100 + 110
+    assert_eq!(exponential.total_cost(2), Some(210));
+    assert_eq!(exponential.cost(2, 1), Some(121));
+
+    let flat = CurveConfig {
+        kind: CurveKind::Exponential {
+            growth: Perbill::from_percent(0),
+        },
+        initial_price: 7,
+        step: 100u64,
+    };
+    assert_eq!(flat.cost(3, 5), Some(35));
+
+    // Prices beyond u128 cannot be quoted
+    let steep = CurveConfig {
+        kind: CurveKind::Exponential {
+            growth: Perbill::from_percent(100),
+        },
+        initial_price: 1,
+        step: 100u64,
+    };
+    assert_eq!(steep.total_cost(200), None);
+    let linear = CurveConfig {
+        initial_price: u128::MAX,
+        ..linear
+    };
+    assert_eq!(linear.cost(1, 1), None);
+}
+
+fn curve_config() -> IssuanceConfig<u64, u64, u64> {
+    IssuanceConfig {
+        curve: Some(CurveConfig {
+            kind: CurveKind::Linear { increment: 10 },
+            initial_price: 50,
+            step: 100,
+        }),
+        ..issuance_config()
+    }
+}
+
+fn create_curve_network() {
+    assert_ok!(NetworkFactory::create_network_with_asset(
+        RuntimeOrigin::signed(1),
+        b"curved".to_vec(),
+        asset_config(AssetAdmin::Treasury),
+        curve_config(),
+    ));
+}
+
+#[test]
+fn curves_are_validated() {
+    new_test_ext().execute_with(|| {
+        let mut issuance = curve_config();
+        issuance.curve.as_mut().unwrap().step = 0;
+        assert_noop!(
+            NetworkFactory::create_network_with_asset(
+                RuntimeOrigin::signed(1),
+                b"curved".to_vec(),
+                asset_config(AssetAdmin::Treasury),
+                issuance,
+            ),
+            Error::<Test>::InvalidCurve
+        );
+
+        create_issued_network();
+        assert!(NetworkFactory::curve(0).is_none());
+        assert_eq!(NetworkFactory::curve_price(0), None);
+        assert_noop!(
+            NetworkFactory::buy_from_curve(RuntimeOrigin::signed(2), 0, 1, 100),
+            Error::<Test>::NoCurve
+        );
+    });
+}
+
+#[test]
+fn coins_are_bought_from_and_sold_to_the_curve() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        create_curve_network();
+        let treasury = NetworkFactory::treasury_account(0);
+        assert_eq!(NetworkFactory::curve_price(0), Some(50));
+
+        assert_noop!(
+            NetworkFactory::buy_from_curve(RuntimeOrigin::signed(2), 0, 0, 100),
+            Error::<Test>::InvalidTrade
+        );
+        // 50 + 60
+        assert_eq!(NetworkFactory::curve_buy_cost(0, 2), Some(110));
+        assert_noop!(
+            NetworkFactory::buy_from_curve(RuntimeOrigin::signed(2), 0, 2, 109),
+            Error::<Test>::SlippageExceeded
+        );
+        assert_ok!(NetworkFactory::buy_from_curve(
+            RuntimeOrigin::signed(2),
+            0,
+            2,
+            110
+        ));
+        System::assert_last_event(
+            Event::CurveBought {
+                network_id: 0,
+                who: 2,
+                amount: 200,
+                cost: 110,
+            }
+            .into(),
+        );
+        assert_eq!(Assets::balance(0, 2), 200);
+        assert_eq!(Balances::free_balance(2), 890);
+        assert_eq!(Balances::free_balance(treasury), 110);
+        let curve = NetworkFactory::curve(0).unwrap();
+        assert_eq!((curve.supply, curve.reserve), (2, 110));
+        assert_eq!(NetworkFactory::curve_price(0), Some(70));
+
+        // 900 of the 1_000 maximum supply are minted or vesting
+        assert_noop!(
+            NetworkFactory::buy_from_curve(RuntimeOrigin::signed(2), 0, 2, 1_000),
+            Error::<Test>::SupplyCapExceeded
+        );
+        assert_ok!(NetworkFactory::buy_from_curve(
+            RuntimeOrigin::signed(2),
+            0,
+            1,
+            70
+        ));
+        assert_eq!(Assets::balance(0, 2), 300);
+
+        assert_noop!(
+            NetworkFactory::sell_to_curve(RuntimeOrigin::signed(2), 0, 4, 0),
+            Error::<Test>::InsufficientCurveSupply
+        );
+        // 70 + 60
+        assert_eq!(NetworkFactory::curve_sell_return(0, 2), Some(130));
+        assert_noop!(
+            NetworkFactory::sell_to_curve(RuntimeOrigin::signed(2), 0, 2, 131),
+            Error::<Test>::SlippageExceeded
+        );
+
+        // Buying stops while the network is paused, selling does not
+        assert_ok!(NetworkFactory::set_network_state(
+            RuntimeOrigin::signed(1),
+            0,
+            NetworkState::Paused
+        ));
+        assert_noop!(
+            NetworkFactory::buy_from_curve(RuntimeOrigin::signed(2), 0, 1, 100),
+            Error::<Test>::NetworkNotActive
+        );
+        assert_ok!(NetworkFactory::sell_to_curve(
+            RuntimeOrigin::signed(2),
+            0,
+            2,
+            130
+        ));
+        System::assert_last_event(
+            Event::CurveSold {
+                network_id: 0,
+                who: 2,
+                amount: 200,
+                proceeds: 130,
+            }
+            .into(),
+        );
+        assert_eq!(Assets::balance(0, 2), 100);
+        assert_eq!(Balances::free_balance(2), 950);
+        assert_eq!(Balances::free_balance(treasury), 50);
+        let curve = NetworkFactory::curve(0).unwrap();
+        assert_eq!((curve.supply, curve.reserve), (1, 50));
+    });
+}
+
+#[test]
+fn curve_reserves_back_outstanding_coins() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        create_curve_network();
+        let treasury = NetworkFactory::treasury_account(0);
+        assert_ok!(NetworkFactory::buy_from_curve(
+            RuntimeOrigin::signed(2),
+            0,
+            1,
+            50
+        ));
+        assert_ok!(Balances::transfer(
+            &3,
+            &treasury,
+            100,
+            Preservation::Expendable
+        ));
+
+        // Only the 100 above the reserve can be spent
+        assert_ok!(NetworkFactory::propose_spend(
+            RuntimeOrigin::signed(1),
+            0,
+            4,
+            SpendAmount::Native(120)
+        ));
+        assert_noop!(
+            NetworkFactory::approve_spend(RuntimeOrigin::signed(1), 0, 0),
+            Error::<Test>::ReserveLocked
+        );
+        assert_ok!(NetworkFactory::propose_spend(
+            RuntimeOrigin::signed(1),
+            0,
+            4,
+            SpendAmount::Native(100)
+        ));
+        assert_ok!(NetworkFactory::approve_spend(
+            RuntimeOrigin::signed(1),
+            0,
+            1
+        ));
+        assert_eq!(Balances::free_balance(treasury), 50);
+
+        // Networks close once every coin is sold back
+        assert_noop!(
+            NetworkFactory::close_network(RuntimeOrigin::signed(1), 0, TreasuryPolicy::ToOwner),
+            Error::<Test>::CurveOutstanding
+        );
+        assert_ok!(NetworkFactory::sell_to_curve(
+            RuntimeOrigin::signed(2),
+            0,
+            1,
+            50
+        ));
+        assert_eq!(Balances::free_balance(2), 1_000);
+        assert_ok!(NetworkFactory::close_network(
+            RuntimeOrigin::signed(1),
+            0,
+            TreasuryPolicy::ToOwner
+        ));
+        assert!(NetworkFactory::curve(0).is_none());
+    });
+}
+
+#[test]
+fn curves_close_when_networks_move_back_to_navi() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        create_curve_network();
+        assert_ok!(NetworkFactory::buy_from_curve(
+            RuntimeOrigin::signed(2),
+            0,
+            1,
+            50
+        ));
+
+        let rate = ConversionRate { from: 1, to: 1 };
+        assert_noop!(
+            NetworkFactory::migrate_coin(
+                RuntimeOrigin::signed(1),
+                0,
+                CoinKind::UseNavi,
+                rate,
+                None
+            ),
+            Error::<Test>::CurveOutstanding
+        );
+        assert_ok!(NetworkFactory::sell_to_curve(
+            RuntimeOrigin::signed(2),
+            0,
+            1,
+            50
+        ));
+        assert_ok!(NetworkFactory::migrate_coin(
+            RuntimeOrigin::signed(1),
+            0,
+            CoinKind::UseNavi,
+            rate,
+            None
+        ));
+        assert!(NetworkFactory::curve(0).is_none());
+        assert_noop!(
+            NetworkFactory::buy_from_curve(RuntimeOrigin::signed(2), 0, 1, 50),
+            Error::<Test>::NoCurve
+        );
+    });
+}
+
+#[test]
+fn owners_migrate_networks_between_coins() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(NetworkFactory::create_network(
+            RuntimeOrigin::signed(1),
+            true,
+            b"navi".to_vec()
+        ));
+        let rate = ConversionRate { from: 1, to: 10 };
+        let asset = || Some((asset_config(AssetAdmin::Treasury), issuance_config()));
+
+        assert_noop!(
+            NetworkFactory::migrate_coin(
+                RuntimeOrigin::signed(2),
+                0,
+                CoinKind::MintAsset,
+                rate,
+                asset()
+            ),
+            Error::<Test>::NotAuthorized
+        );
+        assert_noop!(
+            NetworkFactory::migrate_coin(
+                RuntimeOrigin::signed(1),
+                0,
+                CoinKind::UseNavi,
+                rate,
+                None
+            ),
+            Error::<Test>::CoinUnchanged
+        );
+        assert_noop!(
+            NetworkFactory::migrate_coin(
+                RuntimeOrigin::signed(1),
+                0,
+                CoinKind::MintAsset,
+                ConversionRate { from: 0, to: 10 },
+                asset()
+            ),
+            Error::<Test>::InvalidRate
+        );
+        assert_noop!(
+            NetworkFactory::migrate_coin(
+                RuntimeOrigin::signed(1),
+                0,
+                CoinKind::MintAsset,
+                rate,
+                None
+            ),
+            Error::<Test>::AssetRequired
+        );
+
+        // Children use their parent's coin
+        assert_ok!(NetworkFactory::create_child_network(
+            RuntimeOrigin::signed(1),
+            0,
+            b"child".to_vec(),
+            ChildOwner::Caller,
+            false
+        ));
+        assert_noop!(
+            NetworkFactory::migrate_coin(
+                RuntimeOrigin::signed(1),
+                0,
+                CoinKind::MintAsset,
+                rate,
+                asset()
+            ),
+            Error::<Test>::HasOpenChildren
+        );
+        assert_noop!(
+            NetworkFactory::migrate_coin(
+                RuntimeOrigin::signed(1),
+                1,
+                CoinKind::MintAsset,
+                rate,
+                asset()
+            ),
+            Error::<Test>::CoinInherited
+        );
+        assert_ok!(NetworkFactory::close_network(
+            RuntimeOrigin::signed(1),
+            1,
+            TreasuryPolicy::ToOwner
+        ));
+
+        assert_ok!(NetworkFactory::migrate_coin(
+            RuntimeOrigin::signed(1),
+            0,
+            CoinKind::MintAsset,
+            rate,
+            asset()
+        ));
+        System::assert_last_event(
+            Event::CoinMigrated {
+                network_id: 0,
+                from: CoinKind::UseNavi,
+                to: CoinKind::MintAsset,
+                asset_id: Some(0),
+                rate,
+            }
+            .into(),
+        );
+        let network = NetworkFactory::networks(0).unwrap();
+        assert_eq!(network.coin_kind, CoinKind::MintAsset);
+        assert_eq!(network.asset_id, Some(0));
+        assert_eq!(NetworkFactory::asset_network(0), Some(0));
+        assert_eq!(Assets::balance(0, 1), 100);
+        assert_eq!(Assets::balance(0, network.treasury), 200);
+
+        // Moving back keeps the asset, and moving forward again reuses it
+        let back = ConversionRate { from: 10, to: 1 };
+        assert_noop!(
+            NetworkFactory::migrate_coin(
+                RuntimeOrigin::signed(1),
+                0,
+                CoinKind::UseNavi,
+                back,
+                asset()
+            ),
+            Error::<Test>::AssetExists
+        );
+        assert_ok!(NetworkFactory::migrate_coin(
+            RuntimeOrigin::signed(1),
+            0,
+            CoinKind::UseNavi,
+            back,
+            None
+        ));
+        let network = NetworkFactory::networks(0).unwrap();
+        assert_eq!(network.coin_kind, CoinKind::UseNavi);
+        assert_eq!(network.asset_id, Some(0));
+        assert_eq!(Assets::balance(0, 1), 100);
+        assert_noop!(
+            NetworkFactory::migrate_coin(
+                RuntimeOrigin::signed(1),
+                0,
+                CoinKind::MintAsset,
+                rate,
+                asset()
+            ),
+            Error::<Test>::AssetExists
+        );
+        assert_ok!(NetworkFactory::migrate_coin(
+            RuntimeOrigin::signed(1),
+            0,
+            CoinKind::MintAsset,
+            rate,
+            None
+        ));
+        assert_eq!(
+            NetworkFactory::networks(0).unwrap().coin_kind,
+            CoinKind::MintAsset
+        );
+    });
+}
+
+#[test]
+fn ownership_transfer_takes_two_steps() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(NetworkFactory::create_network(
+            RuntimeOrigin::signed(1),
+            true,
+            b"test".to_vec()
+        ));
+
+        assert_noop!(
+            NetworkFactory::accept_ownership(RuntimeOrigin::signed(2), 0),
+            Error::<Test>::NoPendingTransfer
+        );
+        assert_noop!(
+            NetworkFactory::propose_owner(RuntimeOrigin::signed(2), 0, 2),
+            Error::<Test>::NotAuthorized
+        );
+        assert_noop!(
+            NetworkFactory::propose_owner(RuntimeOrigin::signed(1), 9, 2),
+            Error::<Test>::NetworkNotFound
+        );
+
+        assert_ok!(NetworkFactory::grant_role(
+            RuntimeOrigin::signed(1),
+            0,
+            2,
+            NetworkRole::Moderator
+        ));
+        assert_ok!(NetworkFactory::propose_owner(
+            RuntimeOrigin::signed(1),
+            0,
+            2
+        ));
+        System::assert_last_event(
+            Event::OwnershipProposed {
+                network_id: 0,
+                new_owner: 2,
+            }
+            .into(),
+        );
+        // Still owned by 1 until accepted
+        assert_eq!(NetworkFactory::networks(0).unwrap().owner, 1);
+        assert_noop!(
+            NetworkFactory::accept_ownership(RuntimeOrigin::signed(3), 0),
+            Error::<Test>::NotPendingOwner
+        );
+
+        assert_ok!(NetworkFactory::accept_ownership(
+            RuntimeOrigin::signed(2),
+            0
+        ));
+        System::assert_last_event(
+            Event::OwnershipTransferred {
+                network_id: 0,
+                from: 1,
+                to: 2,
+            }
+            .into(),
+        );
+        assert_eq!(NetworkFactory::networks(0).unwrap().owner, 2);
+        assert_eq!(NetworkFactory::pending_owner(0), None);
+        assert_eq!(NetworkFactory::role(0, 2), None);
+        assert_eq!(NetworkFactory::role_of(0, &2), Some(NetworkRole::Owner));
+        assert_eq!(NetworkFactory::role_of(0, &1), None);
+    });
+}
+
+#[test]
+fn roles_are_granted_by_owner_and_admins() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(NetworkFactory::create_network(
+            RuntimeOrigin::signed(1),
+            true,
+            b"test".to_vec()
+        ));
+
+        assert_noop!(
+            NetworkFactory::grant_role(RuntimeOrigin::signed(1), 0, 2, NetworkRole::Owner),
+            Error::<Test>::InvalidRole
+        );
+        assert_noop!(
+            NetworkFactory::grant_role(RuntimeOrigin::signed(1), 0, 1, NetworkRole::Admin),
+            Error::<Test>::InvalidRole
+        );
+
+        assert_ok!(NetworkFactory::grant_role(
+            RuntimeOrigin::signed(1),
+            0,
+            2,
+            NetworkRole::Admin
+        ));
+        System::assert_last_event(
+            Event::RoleGranted {
+                network_id: 0,
+                who: 2,
+                role: NetworkRole::Admin,
+            }
+            .into(),
+        );
+
+        // Admins manage moderators and billing managers, but not admins
+        assert_ok!(NetworkFactory::grant_role(
+            RuntimeOrigin::signed(2),
+            0,
+            3,
+            NetworkRole::BillingManager
+        ));
+        assert_noop!(
+            NetworkFactory::grant_role(RuntimeOrigin::signed(2), 0, 4, NetworkRole::Admin),
+            Error::<Test>::NotAuthorized
+        );
+        assert_noop!(
+            NetworkFactory::grant_role(RuntimeOrigin::signed(3), 0, 4, NetworkRole::Moderator),
+            Error::<Test>::NotAuthorized
+        );
+
+        // Role checks for other pallets
+        assert!(NetworkFactory::has_role(0, &1, NetworkRole::BillingManager));
+        assert!(NetworkFactory::has_role(0, &2, NetworkRole::Moderator));
+        assert!(!NetworkFactory::has_role(0, &2, NetworkRole::Owner));
+        assert!(NetworkFactory::has_role(0, &3, NetworkRole::BillingManager));
+        assert!(!NetworkFactory::has_role(0, &3, NetworkRole::Moderator));
+        assert!(!NetworkFactory::has_role(1, &1, NetworkRole::Moderator));
+        assert!(NetworkFactory::network_exists(0));
+
+        // Admins can't revoke admins, but anyone can renounce
+        assert_ok!(NetworkFactory::grant_role(
+            RuntimeOrigin::signed(1),
+            0,
+            4,
+            NetworkRole::Admin
+        ));
+        assert_noop!(
+            NetworkFactory::revoke_role(RuntimeOrigin::signed(2), 0, 4),
+            Error::<Test>::NotAuthorized
+        );
+        assert_ok!(NetworkFactory::revoke_role(RuntimeOrigin::signed(4), 0, 4));
+        assert_ok!(NetworkFactory::revoke_role(RuntimeOrigin::signed(2), 0, 3));
+        System::assert_last_event(
+            Event::RoleRevoked {
+                network_id: 0,
+                who: 3,
+                role: NetworkRole::BillingManager,
+            }
+            .into(),
+        );
+        assert_noop!(
+            NetworkFactory::revoke_role(RuntimeOrigin::signed(1), 0, 3),
+            Error::<Test>::RoleNotFound
+        );
+    });
+}
+
+#[test]
+fn admins_update_metadata_uri() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(NetworkFactory::create_network(
+            RuntimeOrigin::signed(1),
+            true,
+            b"ipfs://old".to_vec()
+        ));
+        assert_eq!(NetworkFactory::metadata_version(0), 0);
+
+        assert_noop!(
+            NetworkFactory::set_metadata_uri(RuntimeOrigin::signed(2), 0, b"ipfs://new".to_vec()),
+            Error::<Test>::NotAuthorized
+        );
+        assert_noop!(
+            NetworkFactory::set_metadata_uri(RuntimeOrigin::signed(1), 0, vec![b'x'; 257]),
+            Error::<Test>::MetadataUriTooLong
+        );
+
+        assert_ok!(NetworkFactory::grant_role(
+            RuntimeOrigin::signed(1),
+            0,
+            2,
+            NetworkRole::Admin
+        ));
+        assert_ok!(NetworkFactory::set_metadata_uri(
+            RuntimeOrigin::signed(2),
+            0,
+            b"ipfs://new".to_vec()
+        ));
+        System::assert_last_event(
+            Event::MetadataUriUpdated {
+                network_id: 0,
+                version: 1,
+            }
+            .into(),
+        );
+        assert_eq!(
+            NetworkFactory::networks(0).unwrap().metadata_uri.to_vec(),
+            b"ipfs://new".to_vec()
+        );
+    });
+}
+
+#[test]
+fn admins_update_profile() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(NetworkFactory::create_network(
+            RuntimeOrigin::signed(1),
+            true,
+            b"test".to_vec()
+        ));
+
+        let profile = NetworkProfile {
+            name: b"Test Network".to_vec().try_into().unwrap(),
+            symbol: b"TEST".to_vec().try_into().unwrap(),
+            description: b"A network for tests".to_vec().try_into().unwrap(),
+            logo_cid: b"QmLogo".to_vec().try_into().unwrap(),
+            website: b"https://test.example".to_vec().try_into().unwrap(),
+        };
+        assert_noop!(
+            NetworkFactory::set_profile(RuntimeOrigin::signed(2), 0, profile.clone()),
+            Error::<Test>::NotAuthorized
+        );
+        assert_noop!(
+            NetworkFactory::set_profile(RuntimeOrigin::signed(1), 1, profile.clone()),
+            Error::<Test>::NetworkNotFound
+        );
+
+        assert_ok!(NetworkFactory::set_metadata_uri(
+            RuntimeOrigin::signed(1),
+            0,
+            b"ipfs://new".to_vec()
+        ));
+        assert_ok!(NetworkFactory::set_profile(
+            RuntimeOrigin::signed(1),
+            0,
+            profile.clone()
+        ));
+        System::assert_last_event(
+            Event::ProfileUpdated {
+                network_id: 0,
+                version: 2,
+            }
+            .into(),
+        );
+        assert_eq!(NetworkFactory::profile(0), Some(profile));
+        assert_eq!(NetworkFactory::metadata_version(0), 2);
+    });
+}
+
+#[test]
+fn owner_and_governance_change_network_state() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(NetworkFactory::create_network(
+            RuntimeOrigin::signed(1),
+            true,
+            b"test".to_vec()
+        ));
+        assert_eq!(NetworkFactory::state_of(0), Some(NetworkState::Active));
+
+        // Admins can't change the state
+        assert_ok!(NetworkFactory::grant_role(
+            RuntimeOrigin::signed(1),
+            0,
+            2,
+            NetworkRole::Admin
+        ));
+        assert_noop!(
+            NetworkFactory::set_network_state(RuntimeOrigin::signed(2), 0, NetworkState::Paused),
+            Error::<Test>::NotAuthorized
+        );
+
+        assert_ok!(NetworkFactory::set_network_state(
+            RuntimeOrigin::signed(1),
+            0,
+            NetworkState::Paused
+        ));
+        System::assert_last_event(
+            Event::NetworkStateChanged {
+                network_id: 0,
+                from: NetworkState::Active,
+                to: NetworkState::Paused,
+            }
+            .into(),
+        );
+        assert!(!NetworkFactory::is_active(0));
+        assert_noop!(
+            NetworkFactory::set_network_state(RuntimeOrigin::signed(1), 0, NetworkState::Paused),
+            Error::<Test>::InvalidStateTransition
+        );
+        assert_noop!(
+            NetworkFactory::set_network_state(RuntimeOrigin::signed(1), 0, NetworkState::Closed),
+            Error::<Test>::InvalidStateTransition
+        );
+
+        assert_ok!(NetworkFactory::set_network_state(
+            RuntimeOrigin::root(),
+            0,
+            NetworkState::Archived
+        ));
+        assert_ok!(NetworkFactory::set_network_state(
+            RuntimeOrigin::root(),
+            0,
+            NetworkState::Active
+        ));
+        assert!(NetworkFactory::is_active(0));
+        assert_noop!(
+            NetworkFactory::set_network_state(RuntimeOrigin::root(), 1, NetworkState::Paused),
+            Error::<Test>::NetworkNotFound
+        );
+    });
+}
+
+#[test]
+fn closing_drains_treasury_to_owner() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        create_issued_network();
+        let treasury = NetworkFactory::treasury_account(0);
+        assert_ok!(Balances::transfer(
+            &2,
+            &treasury,
+            100,
+            Preservation::Expendable
+        ));
+
+        assert_noop!(
+            NetworkFactory::close_network(RuntimeOrigin::signed(2), 0, TreasuryPolicy::ToOwner),
+            Error::<Test>::NotAuthorized
+        );
+        assert_ok!(NetworkFactory::close_network(
+            RuntimeOrigin::signed(1),
+            0,
+            TreasuryPolicy::ToOwner
+        ));
+        System::assert_has_event(
+            Event::TreasuryDrained {
+                network_id: 0,
+                policy: TreasuryPolicy::ToOwner,
+                native: 100,
+                asset: 200,
+            }
+            .into(),
+        );
+        System::assert_last_event(
+            Event::NetworkStateChanged {
+                network_id: 0,
+                from: NetworkState::Active,
+                to: NetworkState::Closed,
+            }
+            .into(),
+        );
+
+        assert_eq!(Balances::free_balance(1), 1_100);
+        assert_eq!(Balances::free_balance(treasury), 0);
+        assert_eq!(Assets::balance(0, 1), 300);
+        assert_eq!(Assets::balance(0, treasury), 0);
+        assert!(NetworkFactory::issuance(0).is_none());
+
+        // Closed networks are final
+        assert_noop!(
+            NetworkFactory::close_network(RuntimeOrigin::root(), 0, TreasuryPolicy::Burn),
+            Error::<Test>::NetworkClosed
+        );
+        assert_noop!(
+            NetworkFactory::set_network_state(RuntimeOrigin::root(), 0, NetworkState::Active),
+            Error::<Test>::NetworkClosed
+        );
+        assert_noop!(
+            NetworkFactory::set_metadata_uri(RuntimeOrigin::signed(1), 0, b"new".to_vec()),
+            Error::<Test>::NetworkClosed
+        );
+        System::set_block_number(200);
+        assert_noop!(
+            NetworkFactory::claim_vested(RuntimeOrigin::signed(3), 0),
+            Error::<Test>::NoIssuancePolicy
+        );
+        assert!(!NetworkFactory::has_role(0, &1, NetworkRole::Owner));
+    });
+}
+
+#[test]
+fn governance_can_close_and_burn_treasury() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        create_issued_network();
+        let treasury = NetworkFactory::treasury_account(0);
+        assert_ok!(Balances::transfer(
+            &2,
+            &treasury,
+            100,
+            Preservation::Expendable
+        ));
+        let native_issuance = pallet_balances::TotalIssuance::<Test>::get();
+
+        assert_ok!(NetworkFactory::close_network(
+            RuntimeOrigin::root(),
+            0,
+            TreasuryPolicy::Burn
+        ));
+
+        assert_eq!(
+            pallet_balances::TotalIssuance::<Test>::get(),
+            native_issuance - 100
+        );
+        assert_eq!(Assets::total_issuance(0), 100);
+        assert_eq!(Assets::balance(0, 1), 100);
+        assert_eq!(NetworkFactory::state_of(0), Some(NetworkState::Closed));
+    });
+}
+
+#[test]
+fn closed_network_storage_is_cleaned_up_with_bounded_weight() {
+    let mut ext = new_test_ext();
+    ext.execute_with(|| {
+        System::set_block_number(1);
+        create_issued_network();
+        for (who, role) in [
+            (2, NetworkRole::Admin),
+            (4, NetworkRole::Moderator),
+            (5, NetworkRole::BillingManager),
+        ] {
+            assert_ok!(NetworkFactory::grant_role(
+                RuntimeOrigin::signed(1),
+                0,
+                who,
+                role
+            ));
+        }
+        assert_ok!(NetworkFactory::close_network(
+            RuntimeOrigin::signed(1),
+            0,
+            TreasuryPolicy::ToOwner
+        ));
+    });
+    // Removal limits only apply to committed storage
+    ext.commit_all().unwrap();
+
+    ext.execute_with(|| {
+        // Not enough weight to do anything
+        let per_item = RocksDbWeight::get().reads_writes(1, 1);
+        assert_eq!(NetworkFactory::on_idle(2, per_item), Weight::zero());
+
+        // Room for two items: two of the three roles go
+        let budget = RocksDbWeight::get().reads_writes(1, 1) + per_item * 2;
+        let used = NetworkFactory::on_idle(2, budget);
+        assert!(used.all_lte(budget));
+        assert_eq!(crate::Roles::<Test>::iter_prefix(0).count(), 1);
+        assert!(NetworkFactory::vesting(0, 3).is_some());
+
+        // The rest goes with enough weight
+        NetworkFactory::on_idle(3, Weight::MAX);
+        System::assert_last_event(Event::CleanupCompleted { network_id: 0 }.into());
+        assert_eq!(crate::Roles::<Test>::iter_prefix(0).count(), 0);
+        assert!(NetworkFactory::vesting(0, 3).is_none());
+        assert!(!crate::PendingCleanup::<Test>::contains_key(0));
+
+        // The network record remains
+        assert_eq!(NetworkFactory::state_of(0), Some(NetworkState::Closed));
+        assert_eq!(NetworkFactory::asset_network(0), Some(0));
+    });
+}
+
+#[test]
+fn creation_deposit_is_held_until_close() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(NetworkFactory::create_network(
+            RuntimeOrigin::signed(1),
+            true,
+            b"test".to_vec()
+        ));
+
+        let reason = crate::HoldReason::NetworkDeposit.into();
+        assert_eq!(Balances::balance_on_hold(&reason, &1), 10);
+        assert_eq!(Balances::free_balance(1), 990);
+        assert_eq!(NetworkFactory::network_deposit(0), Some((1, 10)));
+
+        // The deposit stays with the creator when ownership moves
+        assert_ok!(NetworkFactory::propose_owner(
+            RuntimeOrigin::signed(1),
+            0,
+            2
+        ));
+        assert_ok!(NetworkFactory::accept_ownership(
+            RuntimeOrigin::signed(2),
+            0
+        ));
+        assert_eq!(NetworkFactory::owned_networks(1), 0);
+        assert_eq!(NetworkFactory::owned_networks(2), 1);
+
+        assert_ok!(NetworkFactory::close_network(
+            RuntimeOrigin::signed(2),
+            0,
+            TreasuryPolicy::Burn
+        ));
+        assert_eq!(Balances::balance_on_hold(&reason, &1), 0);
+        assert_eq!(Balances::free_balance(1), 1_000);
+        assert_eq!(NetworkFactory::network_deposit(0), None);
+        assert_eq!(NetworkFactory::owned_networks(2), 0);
+    });
+}
+
+#[test]
+fn creation_requires_deposit() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+
+        // Account 4 has no funds
+        assert!(
+            NetworkFactory::create_network(RuntimeOrigin::signed(4), true, b"test".to_vec())
+                .is_err()
+        );
+        assert!(NetworkFactory::networks(0).is_none());
+        assert_eq!(NetworkFactory::owned_networks(4), 0);
+    });
+}
+
+#[test]
+fn accounts_own_a_limited_number_of_networks() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        for _ in 0..3 {
+            assert_ok!(NetworkFactory::create_network(
+                RuntimeOrigin::signed(1),
+                true,
+                b"test".to_vec()
+            ));
+        }
+
+        assert_noop!(
+            NetworkFactory::create_network(RuntimeOrigin::signed(1), true, b"test".to_vec()),
+            Error::<Test>::TooManyNetworks
+        );
+
+        // Accepting ownership counts too
+        assert_ok!(NetworkFactory::create_network(
+            RuntimeOrigin::signed(2),
+            true,
+            b"test".to_vec()
+        ));
+        assert_ok!(NetworkFactory::propose_owner(
+            RuntimeOrigin::signed(2),
+            3,
+            1
+        ));
+        assert_noop!(
+            NetworkFactory::accept_ownership(RuntimeOrigin::signed(1), 3),
+            Error::<Test>::TooManyNetworks
+        );
+
+        // Closed networks don't count
+        assert_ok!(NetworkFactory::close_network(
+            RuntimeOrigin::signed(1),
+            0,
+            TreasuryPolicy::Burn
+        ));
+        assert_ok!(NetworkFactory::accept_ownership(
+            RuntimeOrigin::signed(1),
+            3
+        ));
+        assert_eq!(NetworkFactory::owned_networks(1), 3);
+    });
+}
+
+/// Create network 0 with a treasury holding 100 NAVI and 200 network coins, and make
+/// account 2 an admin.
+fn create_funded_network() {
+    create_issued_network();
+    assert_ok!(Balances::transfer(
+        &3,
+        &NetworkFactory::treasury_account(0),
+        100,
+        Preservation::Expendable
+    ));
+    assert_ok!(NetworkFactory::grant_role(
+        RuntimeOrigin::signed(1),
+        0,
+        2,
+        NetworkRole::Admin
+    ));
+}
+
+#[test]
+fn owner_approves_treasury_spends() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        create_funded_network();
+        let treasury = NetworkFactory::treasury_account(0);
+
+        assert_noop!(
+            NetworkFactory::propose_spend(RuntimeOrigin::signed(3), 0, 4, SpendAmount::Native(40)),
+            Error::<Test>::NotAuthorized
+        );
+        assert_noop!(
+            NetworkFactory::propose_spend(RuntimeOrigin::signed(2), 0, 4, SpendAmount::Native(0)),
+            Error::<Test>::InvalidSpend
+        );
+
+        assert_ok!(NetworkFactory::propose_spend(
+            RuntimeOrigin::signed(2),
+            0,
+            4,
+            SpendAmount::Native(40)
+        ));
+        System::assert_last_event(
+            Event::SpendProposed {
+                network_id: 0,
+                proposal_id: 0,
+                proposer: 2,
+                beneficiary: 4,
+                amount: SpendAmount::Native(40),
+            }
+            .into(),
+        );
+        assert_noop!(
+            NetworkFactory::approve_spend(RuntimeOrigin::signed(2), 0, 0),
+            Error::<Test>::NotCouncilMember
+        );
+
+        assert_ok!(NetworkFactory::approve_spend(
+            RuntimeOrigin::signed(1),
+            0,
+            0
+        ));
+        System::assert_last_event(
+            Event::SpendExecuted {
+                network_id: 0,
+                proposal_id: 0,
+                beneficiary: 4,
+                amount: SpendAmount::Native(40),
+            }
+            .into(),
+        );
+        assert_eq!(Balances::free_balance(4), 40);
+        assert_eq!(Balances::free_balance(treasury), 60);
+        assert!(NetworkFactory::proposal(0, 0).is_none());
+
+        // Payouts in the network asset
+        assert_ok!(NetworkFactory::propose_spend(
+            RuntimeOrigin::signed(2),
+            0,
+            4,
+            SpendAmount::Asset(50)
+        ));
+        assert_ok!(NetworkFactory::approve_spend(
+            RuntimeOrigin::signed(1),
+            0,
+            1
+        ));
+        assert_eq!(Assets::balance(0, 4), 50);
+        assert_eq!(Assets::balance(0, treasury), 150);
+        assert_noop!(
+            NetworkFactory::approve_spend(RuntimeOrigin::signed(1), 0, 1),
+            Error::<Test>::ProposalNotFound
+        );
+    });
+}
+
+#[test]
+fn council_threshold_approves_treasury_spends() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        create_funded_network();
+
+        assert_noop!(
+            NetworkFactory::set_council(RuntimeOrigin::signed(2), 0, vec![2, 3], 2),
+            Error::<Test>::NotAuthorized
+        );
+        assert_noop!(
+            NetworkFactory::set_council(RuntimeOrigin::signed(1), 0, vec![2, 3, 3], 3),
+            Error::<Test>::InvalidCouncil
+        );
+        assert_noop!(
+            NetworkFactory::set_council(RuntimeOrigin::signed(1), 0, vec![2, 3, 4, 5], 2),
+            Error::<Test>::TooManyCouncilMembers
+        );
+        assert_ok!(NetworkFactory::set_council(
+            RuntimeOrigin::signed(1),
+            0,
+            vec![3, 2],
+            2
+        ));
+        assert_eq!(
+            NetworkFactory::council(0).unwrap().members.into_inner(),
+            vec![2, 3]
+        );
+
+        assert_ok!(NetworkFactory::propose_spend(
+            RuntimeOrigin::signed(2),
+            0,
+            4,
+            SpendAmount::Native(40)
+        ));
+        assert_noop!(
+            NetworkFactory::approve_spend(RuntimeOrigin::signed(4), 0, 0),
+            Error::<Test>::NotCouncilMember
+        );
+
+        assert_ok!(NetworkFactory::approve_spend(
+            RuntimeOrigin::signed(2),
+            0,
+            0
+        ));
+        System::assert_last_event(
+            Event::SpendApproved {
+                network_id: 0,
+                proposal_id: 0,
+                who: 2,
+                approvals: 1,
+            }
+            .into(),
+        );
+        assert_eq!(Balances::free_balance(4), 0);
+        assert_noop!(
+            NetworkFactory::approve_spend(RuntimeOrigin::signed(2), 0, 0),
+            Error::<Test>::AlreadyApproved
+        );
+
+        assert_ok!(NetworkFactory::approve_spend(
+            RuntimeOrigin::signed(3),
+            0,
+            0
+        ));
+        assert_eq!(Balances::free_balance(4), 40);
+        assert!(NetworkFactory::proposal(0, 0).is_none());
+
+        // Removing the council leaves approval to the owner
+        assert_ok!(NetworkFactory::set_council(
+            RuntimeOrigin::signed(1),
+            0,
+            vec![],
+            0
+        ));
+        assert!(NetworkFactory::council(0).is_none());
+    });
+}
+
+#[test]
+fn spend_limits_reset_each_period() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        create_funded_network();
+
+        assert_noop!(
+            NetworkFactory::set_spend_limit(
+                RuntimeOrigin::signed(1),
+                0,
+                Some(SpendLimit {
+                    period: 0,
+                    native: 50,
+                    asset: 0
+                })
+            ),
+            Error::<Test>::InvalidSpendLimit
+        );
+        assert_ok!(NetworkFactory::set_spend_limit(
+            RuntimeOrigin::signed(1),
+            0,
+            Some(SpendLimit {
+                period: 10,
+                native: 50,
+                asset: 0
+            })
+        ));
+
+        for amount in [
+            SpendAmount::Native(30),
+            SpendAmount::Native(30),
+            SpendAmount::Asset(10),
+        ] {
+            assert_ok!(NetworkFactory::propose_spend(
+                RuntimeOrigin::signed(2),
+                0,
+                4,
+                amount
+            ));
+        }
+        // Proposals are capped per network
+        assert_noop!(
+            NetworkFactory::propose_spend(RuntimeOrigin::signed(2), 0, 4, SpendAmount::Native(1)),
+            Error::<Test>::TooManySpendProposals
+        );
+
+        assert_ok!(NetworkFactory::approve_spend(
+            RuntimeOrigin::signed(1),
+            0,
+            0
+        ));
+        assert_noop!(
+            NetworkFactory::approve_spend(RuntimeOrigin::signed(1), 0, 1),
+            Error::<Test>::SpendLimitExceeded
+        );
+        assert_noop!(
+            NetworkFactory::approve_spend(RuntimeOrigin::signed(1), 0, 2),
+            Error::<Test>::SpendLimitExceeded
+        );
+        assert_eq!(NetworkFactory::spend_period(0).unwrap().native_spent, 30);
+
+        System::set_block_number(11);
+        assert_ok!(NetworkFactory::approve_spend(
+            RuntimeOrigin::signed(1),
+            0,
+            1
+        ));
+        assert_eq!(Balances::free_balance(4), 60);
+        assert_eq!(NetworkFactory::spend_period(0).unwrap().started_at, 11);
+
+        // Lifting the limit allows any payout
+        assert_ok!(NetworkFactory::set_spend_limit(
+            RuntimeOrigin::signed(1),
+            0,
+            None
+        ));
+        assert_ok!(NetworkFactory::approve_spend(
+            RuntimeOrigin::signed(1),
+            0,
+            2
+        ));
+        assert_eq!(Assets::balance(0, 4), 10);
+    });
+}
+
+#[test]
+fn spend_proposals_can_be_rejected() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        create_funded_network();
+        for _ in 0..2 {
+            assert_ok!(NetworkFactory::propose_spend(
+                RuntimeOrigin::signed(2),
+                0,
+                4,
+                SpendAmount::Native(10)
+            ));
+        }
+
+        assert_noop!(
+            NetworkFactory::reject_spend(RuntimeOrigin::signed(3), 0, 0),
+            Error::<Test>::NotAuthorized
+        );
+        // Proposers withdraw their own proposals and owners reject any
+        assert_ok!(NetworkFactory::reject_spend(RuntimeOrigin::signed(2), 0, 0));
+        assert_ok!(NetworkFactory::reject_spend(RuntimeOrigin::signed(1), 0, 1));
+        System::assert_last_event(
+            Event::SpendRejected {
+                network_id: 0,
+                proposal_id: 1,
+            }
+            .into(),
+        );
+        assert!(NetworkFactory::proposal(0, 0).is_none());
+
+        // Proposal IDs are not reused
+        assert_ok!(NetworkFactory::propose_spend(
+            RuntimeOrigin::signed(2),
+            0,
+            4,
+            SpendAmount::Native(10)
+        ));
+        assert_ok!(NetworkFactory::set_network_state(
+            RuntimeOrigin::signed(1),
+            0,
+            NetworkState::Paused
+        ));
+        assert_noop!(
+            NetworkFactory::approve_spend(RuntimeOrigin::signed(1), 0, 2),
+            Error::<Test>::NetworkNotActive
+        );
+    });
+}
+
+#[test]
+fn navi_networks_cannot_spend_assets() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(NetworkFactory::create_network(
+            RuntimeOrigin::signed(1),
+            true,
+            b"navi".to_vec()
+        ));
+
+        assert_noop!(
+            NetworkFactory::propose_spend(RuntimeOrigin::signed(1), 0, 4, SpendAmount::Asset(1)),
+            Error::<Test>::InvalidSpend
+        );
+    });
+}
+
+fn governance(
+    electorate: Electorate,
+    scheme: VotingScheme,
+    quorum: u32,
+) -> Option<GovernanceConfig<u64>> {
+    Some(GovernanceConfig {
+        electorate,
+        scheme,
+        quorum: Perbill::from_percent(quorum),
+        voting_period: 10,
+    })
+}
+
+fn metadata_action() -> NetworkAction {
+    NetworkAction::SetMetadataUri(b"ipfs://new".to_vec().try_into().unwrap())
+}
+
+#[test]
+fn governance_config_is_validated() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        create_issued_network();
+        assert_ok!(NetworkFactory::create_network(
+            RuntimeOrigin::signed(1),
+            true,
+            b"navi".to_vec()
+        ));
+
+        let asset_holders = governance(Electorate::AssetHolders, VotingScheme::Conviction, 10);
+        assert_noop!(
+            NetworkFactory::set_governance(RuntimeOrigin::signed(2), 0, asset_holders),
+            Error::<Test>::NotAuthorized
+        );
+        let mut instant = asset_holders.unwrap();
+        instant.voting_period = 0;
+        assert_noop!(
+            NetworkFactory::set_governance(RuntimeOrigin::signed(1), 0, Some(instant)),
+            Error::<Test>::InvalidGovernance
+        );
+        assert_noop!(
+            NetworkFactory::set_governance(RuntimeOrigin::signed(1), 1, asset_holders),
+            Error::<Test>::InvalidGovernance
+        );
+        assert_noop!(
+            NetworkFactory::set_governance(
+                RuntimeOrigin::signed(1),
+                1,
+                governance(Electorate::Members, VotingScheme::Conviction, 10)
+            ),
+            Error::<Test>::InvalidGovernance
+        );
+
+        assert_ok!(NetworkFactory::set_governance(
+            RuntimeOrigin::signed(1),
+            0,
+            asset_holders
+        ));
+        System::assert_last_event(
+            Event::GovernanceSet {
+                network_id: 0,
+                config: asset_holders,
+            }
+            .into(),
+        );
+        assert_noop!(
+            NetworkFactory::submit_referendum(RuntimeOrigin::signed(1), 1, metadata_action()),
+            Error::<Test>::NoGovernance
+        );
+    });
+}
+
+#[test]
+fn asset_holder_referendum_enacts_metadata() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        create_issued_network();
+        assert_ok!(NetworkFactory::set_governance(
+            RuntimeOrigin::signed(1),
+            0,
+            governance(Electorate::AssetHolders, VotingScheme::SimpleMajority, 10)
+        ));
+
+        assert_noop!(
+            NetworkFactory::submit_referendum(RuntimeOrigin::signed(2), 0, metadata_action()),
+            Error::<Test>::NotInElectorate
+        );
+        assert_ok!(NetworkFactory::submit_referendum(
+            RuntimeOrigin::signed(1),
+            0,
+            metadata_action()
+        ));
+        System::assert_last_event(
+            Event::ReferendumSubmitted {
+                network_id: 0,
+                index: 0,
+                proposer: 1,
+                action: metadata_action(),
+                end: 11,
+            }
+            .into(),
+        );
+
+        assert_ok!(NetworkFactory::vote(
+            RuntimeOrigin::signed(1),
+            0,
+            0,
+            true,
+            50,
+            Conviction::Locked6x
+        ));
+        // Simple majority ignores conviction
+        System::assert_last_event(
+            Event::Voted {
+                network_id: 0,
+                index: 0,
+                who: 1,
+                aye: true,
+                votes: 50,
+            }
+            .into(),
+        );
+        assert_eq!(Assets::balance(0, 1), 50);
+        assert_eq!(Assets::balance(0, NetworkFactory::vote_account()), 50);
+        assert_noop!(
+            NetworkFactory::vote(RuntimeOrigin::signed(1), 0, 0, false, 10, Conviction::None),
+            Error::<Test>::AlreadyVoted
+        );
+        assert_noop!(
+            NetworkFactory::vote(RuntimeOrigin::signed(3), 0, 0, false, 0, Conviction::None),
+            Error::<Test>::InvalidVote
+        );
+        assert_noop!(
+            NetworkFactory::close_referendum(RuntimeOrigin::signed(2), 0, 0),
+            Error::<Test>::VotingOngoing
+        );
+        assert_noop!(
+            NetworkFactory::unlock_vote(RuntimeOrigin::signed(2), 0, 0, 1),
+            Error::<Test>::VoteLocked
+        );
+
+        System::set_block_number(11);
+        assert_noop!(
+            NetworkFactory::vote(RuntimeOrigin::signed(1), 0, 0, true, 10, Conviction::None),
+            Error::<Test>::VotingClosed
+        );
+        assert_ok!(NetworkFactory::close_referendum(
+            RuntimeOrigin::signed(2),
+            0,
+            0
+        ));
+        System::assert_last_event(
+            Event::ReferendumPassed {
+                network_id: 0,
+                index: 0,
+                result: Ok(()),
+            }
+            .into(),
+        );
+        assert_eq!(
+            NetworkFactory::networks(0)
+                .unwrap()
+                .metadata_uri
+                .into_inner(),
+            b"ipfs://new".to_vec()
+        );
+        assert_eq!(NetworkFactory::metadata_version(0), 1);
+        assert!(NetworkFactory::referendum(0, 0).is_none());
+
+        assert_ok!(NetworkFactory::unlock_vote(
+            RuntimeOrigin::signed(2),
+            0,
+            0,
+            1
+        ));
+        System::assert_last_event(
+            Event::VoteUnlocked {
+                network_id: 0,
+                index: 0,
+                who: 1,
+                balance: 50,
+            }
+            .into(),
+        );
+        assert_eq!(Assets::balance(0, 1), 100);
+        assert!(NetworkFactory::vote_of((0, 0, 1)).is_none());
+    });
+}
+
+#[test]
+fn conviction_votes_lock_longer_and_need_quorum() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        create_issued_network();
+        assert_ok!(NetworkFactory::set_governance(
+            RuntimeOrigin::signed(1),
+            0,
+            governance(Electorate::AssetHolders, VotingScheme::Conviction, 50)
+        ));
+        assert_ok!(NetworkFactory::submit_referendum(
+            RuntimeOrigin::signed(1),
+            0,
+            metadata_action()
+        ));
+
+        assert_ok!(NetworkFactory::vote(
+            RuntimeOrigin::signed(1),
+            0,
+            0,
+            true,
+            100,
+            Conviction::Locked2x
+        ));
+        System::assert_last_event(
+            Event::Voted {
+                network_id: 0,
+                index: 0,
+                who: 1,
+                aye: true,
+                votes: 200,
+            }
+            .into(),
+        );
+
+        // 100 of 300 coins voted, short of the 50% quorum
+        System::set_block_number(11);
+        assert_ok!(NetworkFactory::close_referendum(
+            RuntimeOrigin::signed(2),
+            0,
+            0
+        ));
+        System::assert_last_event(
+            Event::ReferendumRejected {
+                network_id: 0,
+                index: 0,
+            }
+            .into(),
+        );
+        assert_eq!(NetworkFactory::metadata_version(0), 0);
+
+        // Two locking periods after the end
+        assert_noop!(
+            NetworkFactory::unlock_vote(RuntimeOrigin::signed(1), 0, 0, 1),
+            Error::<Test>::VoteLocked
+        );
+        System::set_block_number(31);
+        assert_ok!(NetworkFactory::unlock_vote(
+            RuntimeOrigin::signed(1),
+            0,
+            0,
+            1
+        ));
+        assert_eq!(Assets::balance(0, 1), 100);
+    });
+}
+
+#[test]
+fn member_referenda_are_decided_by_majority() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(NetworkFactory::create_network(
+            RuntimeOrigin::signed(1),
+            true,
+            b"navi".to_vec()
+        ));
+        assert_ok!(NetworkFactory::set_governance(
+            RuntimeOrigin::signed(1),
+            0,
+            governance(Electorate::Members, VotingScheme::SimpleMajority, 50)
+        ));
+
+        let weights = NetworkAction::SetBasketWeights(
+            vec![(b"ID".to_vec().try_into().unwrap(), 1)]
+                .try_into()
+                .unwrap(),
+        );
+        assert_noop!(
+            NetworkFactory::submit_referendum(RuntimeOrigin::signed(4), 0, weights.clone()),
+            Error::<Test>::NotInElectorate
+        );
+        assert_ok!(NetworkFactory::submit_referendum(
+            RuntimeOrigin::signed(2),
+            0,
+            weights.clone()
+        ));
+        // Admins submit without being members
+        assert_ok!(NetworkFactory::submit_referendum(
+            RuntimeOrigin::signed(1),
+            0,
+            metadata_action()
+        ));
+        assert_noop!(
+            NetworkFactory::submit_referendum(RuntimeOrigin::signed(2), 0, weights),
+            Error::<Test>::TooManyReferenda
+        );
+
+        for (who, aye) in [(2, true), (3, true)] {
+            assert_ok!(NetworkFactory::vote(
+                RuntimeOrigin::signed(who),
+                0,
+                0,
+                aye,
+                0,
+                Conviction::None
+            ));
+        }
+        for (who, aye) in [(2, true), (3, false)] {
+            assert_ok!(NetworkFactory::vote(
+                RuntimeOrigin::signed(who),
+                0,
+                1,
+                aye,
+                0,
+                Conviction::None
+            ));
+        }
+        assert_noop!(
+            NetworkFactory::vote(RuntimeOrigin::signed(4), 0, 1, true, 0, Conviction::None),
+            Error::<Test>::NotInElectorate
+        );
+        assert_eq!(
+            NetworkFactory::referendum(0, 1).unwrap().tally,
+            Tally {
+                ayes: 1,
+                nays: 1,
+                turnout: 2
+            }
+        );
+
+        System::set_block_number(11);
+        // Basket weights are enacted by another pallet, which the mock lacks
+        assert_ok!(NetworkFactory::close_referendum(
+            RuntimeOrigin::signed(1),
+            0,
+            0
+        ));
+        System::assert_last_event(
+            Event::ReferendumPassed {
+                network_id: 0,
+                index: 0,
+                result: Err(Error::<Test>::UnsupportedAction.into()),
+            }
+            .into(),
+        );
+        assert_ok!(NetworkFactory::close_referendum(
+            RuntimeOrigin::signed(1),
+            0,
+            1
+        ));
+        System::assert_last_event(
+            Event::ReferendumRejected {
+                network_id: 0,
+                index: 1,
+            }
+            .into(),
+        );
+    });
+}
+
+#[test]
+fn closing_network_unlocks_votes() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        create_issued_network();
+        assert_ok!(NetworkFactory::set_governance(
+            RuntimeOrigin::signed(1),
+            0,
+            governance(Electorate::AssetHolders, VotingScheme::Conviction, 10)
+        ));
+        assert_ok!(NetworkFactory::submit_referendum(
+            RuntimeOrigin::signed(1),
+            0,
+            metadata_action()
+        ));
+        assert_ok!(NetworkFactory::vote(
+            RuntimeOrigin::signed(1),
+            0,
+            0,
+            true,
+            50,
+            Conviction::Locked6x
+        ));
+
+        assert_ok!(NetworkFactory::close_network(
+            RuntimeOrigin::signed(1),
+            0,
+            TreasuryPolicy::ToOwner
+        ));
+        assert!(NetworkFactory::governance(0).is_none());
+        NetworkFactory::on_idle(2, Weight::MAX);
+        assert!(NetworkFactory::referendum(0, 0).is_none());
+
+        assert_ok!(NetworkFactory::unlock_vote(
+            RuntimeOrigin::signed(1),
+            0,
+            0,
+            1
+        ));
+        assert_eq!(Assets::balance(0, 1), 300);
+    });
+}
+
+#[test]
+fn admins_create_child_networks() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        create_issued_network();
+        assert_ok!(NetworkFactory::grant_role(
+            RuntimeOrigin::signed(1),
+            0,
+            2,
+            NetworkRole::Admin
+        ));
+
+        assert_noop!(
+            NetworkFactory::create_child_network(
+                RuntimeOrigin::signed(3),
+                0,
+                b"child".to_vec(),
+                ChildOwner::Caller,
+                false
+            ),
+            Error::<Test>::NotAuthorized
+        );
+        assert_ok!(NetworkFactory::create_child_network(
+            RuntimeOrigin::signed(2),
+            0,
+            b"child".to_vec(),
+            ChildOwner::ParentTreasury,
+            true
+        ));
+        System::assert_last_event(
+            Event::ChildNetworkCreated {
+                parent_id: 0,
+                network_id: 1,
+                inherit_membership: true,
+            }
+            .into(),
+        );
+
+        // Children use the parent's coin and the caller pays the deposit
+        let child = NetworkFactory::networks(1).unwrap();
+        assert_eq!(child.owner, NetworkFactory::treasury_account(0));
+        assert_eq!(child.coin_kind, CoinKind::MintAsset);
+        assert_eq!(child.asset_id, Some(0));
+        assert_eq!(
+            Balances::balance_on_hold(&crate::HoldReason::NetworkDeposit.into(), &2),
+            10
+        );
+        assert_eq!(NetworkFactory::membership_parent(1), Some(0));
+
+        // The parent's roles apply to children owned by its treasury
+        assert_eq!(NetworkFactory::role_of(1, &1), Some(NetworkRole::Owner));
+        assert_eq!(NetworkFactory::role_of(1, &2), Some(NetworkRole::Admin));
+        assert_ok!(NetworkFactory::set_metadata_uri(
+            RuntimeOrigin::signed(2),
+            1,
+            b"renamed".to_vec()
+        ));
+
+        assert_ok!(NetworkFactory::create_child_network(
+            RuntimeOrigin::signed(2),
+            0,
+            b"own".to_vec(),
+            ChildOwner::Caller,
+            false
+        ));
+        assert_eq!(NetworkFactory::networks(2).unwrap().owner, 2);
+        assert_eq!(NetworkFactory::role_of(2, &1), None);
+        assert_eq!(NetworkFactory::membership_parent(2), None);
+        assert_noop!(
+            NetworkFactory::create_child_network(
+                RuntimeOrigin::signed(1),
+                0,
+                b"third".to_vec(),
+                ChildOwner::Caller,
+                false
+            ),
+            Error::<Test>::TooManyChildren
+        );
+    });
+}
+
+#[test]
+fn hierarchies_are_bounded_and_queryable() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(NetworkFactory::create_network(
+            RuntimeOrigin::signed(1),
+            true,
+            b"root".to_vec()
+        ));
+        for (parent, owner) in [
+            (0, ChildOwner::ParentTreasury),
+            (0, ChildOwner::Caller),
+            (1, ChildOwner::ParentTreasury),
+        ] {
+            assert_ok!(NetworkFactory::create_child_network(
+                RuntimeOrigin::signed(1),
+                parent,
+                b"child".to_vec(),
+                owner,
+                false
+            ));
+        }
+        assert_noop!(
+            NetworkFactory::create_child_network(
+                RuntimeOrigin::signed(1),
+                3,
+                b"too deep".to_vec(),
+                ChildOwner::Caller,
+                false
+            ),
+            Error::<Test>::HierarchyTooDeep
+        );
+
+        assert_eq!(NetworkFactory::children_of(0), vec![1, 2]);
+        assert_eq!(NetworkFactory::children_of(1), vec![3]);
+        assert_eq!(NetworkFactory::ancestors_of(3), vec![1, 0]);
+        assert_eq!(NetworkFactory::descendants_of(0), vec![1, 2, 3]);
+        assert_eq!(
+            NetworkFactory::networks(3).unwrap().coin_kind,
+            CoinKind::UseNavi
+        );
+    });
+}
+
+#[test]
+fn networks_close_after_their_children() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(NetworkFactory::create_network(
+            RuntimeOrigin::signed(1),
+            true,
+            b"root".to_vec()
+        ));
+        assert_ok!(NetworkFactory::create_child_network(
+            RuntimeOrigin::signed(1),
+            0,
+            b"child".to_vec(),
+            ChildOwner::ParentTreasury,
+            false
+        ));
+
+        assert_noop!(
+            NetworkFactory::close_network(RuntimeOrigin::signed(1), 0, TreasuryPolicy::ToOwner),
+            Error::<Test>::HasOpenChildren
+        );
+        // The parent owner closes the child on the treasury's behalf
+        assert_ok!(NetworkFactory::close_network(
+            RuntimeOrigin::signed(1),
+            1,
+            TreasuryPolicy::ToOwner
+        ));
+        assert!(NetworkFactory::children_of(0).is_empty());
+        assert_ok!(NetworkFactory::close_network(
+            RuntimeOrigin::signed(1),
+            0,
+            TreasuryPolicy::ToOwner
+        ));
+        assert_eq!(NetworkFactory::state_of(0), Some(NetworkState::Closed));
+    });
+}
+
+fn resource_spec() -> ResourceSpec {
+    ResourceSpec {
+        cid: b"QmResource".to_vec().try_into().unwrap(),
+        kind: 1,
+        base_stake: 100,
+        duration_blocks: 50,
+        ppu: None,
+        tiers: Default::default(),
+    }
+}
+
+#[test]
+fn templates_hold_a_deposit_until_removed() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+
+        // Asset holders cannot govern NAVI networks
+        assert_noop!(
+            NetworkFactory::create_template(
+                RuntimeOrigin::signed(1),
+                None,
+                None,
+                governance(Electorate::AssetHolders, VotingScheme::SimpleMajority, 10),
+                vec![],
+                Default::default()
+            ),
+            Error::<Test>::InvalidGovernance
+        );
+        assert_noop!(
+            NetworkFactory::create_template(
+                RuntimeOrigin::signed(1),
+                None,
+                None,
+                None,
+                vec![resource_spec(); 3],
+                Default::default()
+            ),
+            Error::<Test>::TooManyTemplateResources
+        );
+
+        assert_ok!(NetworkFactory::create_template(
+            RuntimeOrigin::signed(1),
+            None,
+            None,
+            None,
+            vec![resource_spec()],
+            Default::default()
+        ));
+        System::assert_last_event(
+            Event::TemplateCreated {
+                template_id: 0,
+                creator: 1,
+            }
+            .into(),
+        );
+        let reason = crate::HoldReason::TemplateDeposit.into();
+        assert_eq!(Balances::balance_on_hold(&reason, &1), 5);
+        assert_eq!(NetworkFactory::template(0).unwrap().resources.len(), 1);
+        assert_eq!(NetworkFactory::next_template_id(), 1);
+
+        assert_noop!(
+            NetworkFactory::remove_template(RuntimeOrigin::signed(2), 0),
+            Error::<Test>::NotTemplateCreator
+        );
+        assert_ok!(NetworkFactory::remove_template(RuntimeOrigin::signed(1), 0));
+        System::assert_last_event(Event::TemplateRemoved { template_id: 0 }.into());
+        assert_eq!(Balances::balance_on_hold(&reason, &1), 0);
+        assert!(NetworkFactory::template(0).is_none());
+        assert_noop!(
+            NetworkFactory::create_from_template(RuntimeOrigin::signed(2), 0, vec![]),
+            Error::<Test>::TemplateNotFound
+        );
+    });
+}
+
+#[test]
+fn networks_are_provisioned_from_templates() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+
+        let profile = NetworkProfile {
+            name: b"Template Network".to_vec().try_into().unwrap(),
+            ..Default::default()
+        };
+        let config = governance(Electorate::AssetHolders, VotingScheme::Conviction, 10);
+        assert_ok!(NetworkFactory::create_template(
+            RuntimeOrigin::signed(1),
+            Some(asset_config(AssetAdmin::Treasury)),
+            Some(profile.clone()),
+            config,
+            vec![],
+            Default::default()
+        ));
+
+        assert_ok!(NetworkFactory::create_from_template(
+            RuntimeOrigin::signed(2),
+            0,
+            b"from template".to_vec()
+        ));
+        System::assert_last_event(
+            Event::NetworkProvisioned {
+                network_id: 0,
+                template_id: 0,
+            }
+            .into(),
+        );
+        let network = NetworkFactory::networks(0).unwrap();
+        assert_eq!(network.owner, 2);
+        assert_eq!(network.coin_kind, CoinKind::MintAsset);
+        assert_eq!(network.metadata_uri.to_vec(), b"from template".to_vec());
+        assert_eq!(NetworkFactory::profile(0), Some(profile));
+        assert_eq!(NetworkFactory::governance(0), config);
+        assert_eq!(
+            Balances::balance_on_hold(&crate::HoldReason::NetworkDeposit.into(), &2),
+            10
+        );
+    });
+}
+
+#[test]
+fn templates_need_handlers_for_resources() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+
+        assert_ok!(NetworkFactory::create_template(
+            RuntimeOrigin::signed(1),
+            None,
+            None,
+            None,
+            vec![resource_spec()],
+            Default::default()
+        ));
+        // The mock has no Access Gate to register the resource
+        assert_eq!(
+            NetworkFactory::create_from_template(RuntimeOrigin::signed(1), 0, vec![]),
+            Err(Error::<Test>::UnsupportedAction.into())
+        );
+    });
+}
+
+#[test]
+fn networks_are_indexed_by_owner() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        for _ in 0..2 {
+            assert_ok!(NetworkFactory::create_network(
+                RuntimeOrigin::signed(1),
+                true,
+                b"test".to_vec()
+            ));
+        }
+        assert_ok!(NetworkFactory::create_child_network(
+            RuntimeOrigin::signed(1),
+            0,
+            b"child".to_vec(),
+            ChildOwner::ParentTreasury,
+            false
+        ));
+        assert_ok!(NetworkFactory::propose_owner(
+            RuntimeOrigin::signed(1),
+            1,
+            2
+        ));
+        assert_ok!(NetworkFactory::accept_ownership(
+            RuntimeOrigin::signed(2),
+            1
+        ));
+
+        let ids = |networks: Vec<NetworkDetails<u64>>| {
+            networks
+                .into_iter()
+                .map(|network| network.network_id)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(ids(NetworkFactory::networks_by_owner(&1, 0, 10)), vec![0]);
+        assert_eq!(ids(NetworkFactory::networks_by_owner(&2, 0, 10)), vec![1]);
+        assert!(NetworkFactory::networks_by_owner(&2, 2, 10).is_empty());
+        assert!(NetworkFactory::networks_by_owner(&2, 0, 0).is_empty());
+        let treasury = NetworkFactory::treasury_account(0);
+        assert_eq!(
+            ids(NetworkFactory::networks_by_owner(&treasury, 0, 10)),
+            vec![2]
+        );
+
+        // Closed networks stay listed
+        assert_ok!(NetworkFactory::close_network(
+            RuntimeOrigin::signed(2),
+            1,
+            TreasuryPolicy::ToOwner
+        ));
+        let details = NetworkFactory::network_details(1).unwrap();
+        assert_eq!(details.owner, 2);
+        assert_eq!(details.state, NetworkState::Closed);
+        assert_eq!(details.metadata_uri, b"test".to_vec());
+        assert_eq!(NetworkFactory::network_details(2).unwrap().parent, Some(0));
+        assert_eq!(NetworkFactory::network_details(3), None);
+
+        assert_eq!(ids(NetworkFactory::networks_page(0, 2)), vec![0, 1]);
+        assert_eq!(ids(NetworkFactory::networks_page(1, 10)), vec![1, 2]);
+        assert!(NetworkFactory::networks_page(3, 10).is_empty());
+    });
+}
+
+#[test]
+fn handles_follow_slug_rules() {
+    let valid: [&[u8]; 4] = [b"abc", b"my-guild", b"a1-b2-c3", &[b'a'; 32]];
+    for handle in valid {
+        assert!(is_valid_handle(handle));
+    }
+    let invalid: [&[u8]; 8] = [
+        b"ab",
+        b"My-Guild",
+        b"my_guild",
+        b"my guild",
+        b"-guild",
+        b"guild-",
+        b"my--guild",
+        &[b'a'; 33],
+    ];
+    for handle in invalid {
+        assert!(!is_valid_handle(handle));
+    }
+}
+
+#[test]
+fn networks_take_unique_handles() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_noop!(
+            NetworkFactory::create_network_with_handle(
+                RuntimeOrigin::signed(1),
+                true,
+                vec![],
+                b"My-Guild".to_vec()
+            ),
+            Error::<Test>::InvalidHandle
+        );
+        assert_ok!(NetworkFactory::create_network_with_handle(
+            RuntimeOrigin::signed(1),
+            true,
+            vec![],
+            b"my-guild".to_vec()
+        ));
+        System::assert_last_event(
+            Event::HandleSet {
+                network_id: 0,
+                handle: b"my-guild".to_vec().try_into().unwrap(),
+            }
+            .into(),
+        );
+        assert_eq!(
+            NetworkFactory::handle_network(Handle::truncate_from(b"my-guild".to_vec())),
+            Some(0)
+        );
+        // Lookups ignore case
+        let details = NetworkFactory::network_by_handle(b"My-Guild").unwrap();
+        assert_eq!(details.network_id, 0);
+        assert_eq!(details.handle, Some(b"my-guild".to_vec()));
+        assert!(NetworkFactory::network_by_handle(b"other").is_none());
+
+        assert_noop!(
+            NetworkFactory::create_network_with_handle(
+                RuntimeOrigin::signed(2),
+                true,
+                vec![],
+                b"my-guild".to_vec()
+            ),
+            Error::<Test>::HandleTaken
+        );
+        assert_ok!(NetworkFactory::create_network(
+            RuntimeOrigin::signed(2),
+            true,
+            vec![]
+        ));
+        assert_noop!(
+            NetworkFactory::set_handle(RuntimeOrigin::signed(2), 1, Some(b"my-guild".to_vec())),
+            Error::<Test>::HandleTaken
+        );
+        assert_noop!(
+            NetworkFactory::set_handle(RuntimeOrigin::signed(2), 0, Some(b"mine".to_vec())),
+            Error::<Test>::NotAuthorized
+        );
+
+        // Replacing a handle frees the old one
+        assert_ok!(NetworkFactory::set_handle(
+            RuntimeOrigin::signed(1),
+            0,
+            Some(b"guild".to_vec())
+        ));
+        System::assert_has_event(
+            Event::HandleReleased {
+                network_id: 0,
+                handle: b"my-guild".to_vec().try_into().unwrap(),
+            }
+            .into(),
+        );
+        assert_ok!(NetworkFactory::set_handle(
+            RuntimeOrigin::signed(2),
+            1,
+            Some(b"my-guild".to_vec())
+        ));
+        assert_eq!(
+            NetworkFactory::network_by_handle(b"guild")
+                .unwrap()
+                .network_id,
+            0
+        );
+
+        // Handles move with the network
+        assert_ok!(NetworkFactory::propose_owner(
+            RuntimeOrigin::signed(1),
+            0,
+            3
+        ));
+        assert_ok!(NetworkFactory::accept_ownership(
+            RuntimeOrigin::signed(3),
+            0
+        ));
+        assert_eq!(
+            NetworkFactory::network_by_handle(b"guild").unwrap().owner,
+            3
+        );
+        assert_ok!(NetworkFactory::set_handle(
+            RuntimeOrigin::signed(3),
+            0,
+            None
+        ));
+        assert!(NetworkFactory::handle_of(0).is_none());
+        assert_noop!(
+            NetworkFactory::set_handle(RuntimeOrigin::signed(3), 0, None),
+            Error::<Test>::NoHandle
+        );
+
+        // Closing frees the handle
+        assert_ok!(NetworkFactory::close_network(
+            RuntimeOrigin::signed(2),
+            1,
+            TreasuryPolicy::ToOwner
+        ));
+        assert!(NetworkFactory::network_by_handle(b"my-guild").is_none());
+        assert_ok!(NetworkFactory::set_handle(
+            RuntimeOrigin::signed(3),
+            0,
+            Some(b"my-guild".to_vec())
+        ));
+    });
+}
+
+#[test]
+fn admins_manage_admission() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(NetworkFactory::create_network(
+            RuntimeOrigin::signed(1),
+            true,
+            b"test".to_vec()
+        ));
+        assert!(NetworkFactory::is_admitted(0, &2));
+
+        assert_noop!(
+            NetworkFactory::set_admission_mode(
+                RuntimeOrigin::signed(2),
+                0,
+                AdmissionMode::InviteOnly
+            ),
+            Error::<Test>::NotAuthorized
+        );
+        assert_ok!(NetworkFactory::set_admission_mode(
+            RuntimeOrigin::signed(1),
+            0,
+            AdmissionMode::InviteOnly
+        ));
+        System::assert_last_event(
+            Event::AdmissionModeSet {
+                network_id: 0,
+                mode: AdmissionMode::InviteOnly,
+            }
+            .into(),
+        );
+        // Role holders are always admitted
+        assert!(NetworkFactory::is_admitted(0, &1));
+        assert!(!NetworkFactory::is_admitted(0, &2));
+
+        assert_ok!(NetworkFactory::add_to_allowlist(
+            RuntimeOrigin::signed(1),
+            0,
+            2
+        ));
+        assert!(NetworkFactory::is_admitted(0, &2));
+        assert_noop!(
+            NetworkFactory::add_to_allowlist(RuntimeOrigin::signed(1), 0, 2),
+            Error::<Test>::AlreadyAllowlisted
+        );
+        assert_ok!(NetworkFactory::remove_from_allowlist(
+            RuntimeOrigin::signed(1),
+            0,
+            2
+        ));
+        assert!(!NetworkFactory::is_admitted(0, &2));
+        assert_noop!(
+            NetworkFactory::remove_from_allowlist(RuntimeOrigin::signed(1), 0, 2),
+            Error::<Test>::NotAllowlisted
+        );
+
+        assert_noop!(
+            NetworkFactory::request_to_join(RuntimeOrigin::signed(3), 0),
+            Error::<Test>::RequestsNotAccepted
+        );
+        assert_ok!(NetworkFactory::set_admission_mode(
+            RuntimeOrigin::signed(1),
+            0,
+            AdmissionMode::RequestToJoin
+        ));
+        assert_ok!(NetworkFactory::request_to_join(RuntimeOrigin::signed(3), 0));
+        assert_eq!(NetworkFactory::join_request(0, 3), Some(1));
+        assert_noop!(
+            NetworkFactory::request_to_join(RuntimeOrigin::signed(3), 0),
+            Error::<Test>::AlreadyRequested
+        );
+        assert_ok!(NetworkFactory::reject_join(RuntimeOrigin::signed(1), 0, 3));
+        System::assert_last_event(
+            Event::JoinRequestDecided {
+                network_id: 0,
+                who: 3,
+                approved: false,
+            }
+            .into(),
+        );
+        assert_noop!(
+            NetworkFactory::reject_join(RuntimeOrigin::signed(1), 0, 3),
+            Error::<Test>::JoinRequestNotFound
+        );
+
+        // Allowlisting approves a pending request
+        assert_ok!(NetworkFactory::request_to_join(RuntimeOrigin::signed(3), 0));
+        assert_ok!(NetworkFactory::add_to_allowlist(
+            RuntimeOrigin::signed(1),
+            0,
+            3
+        ));
+        System::assert_has_event(
+            Event::JoinRequestDecided {
+                network_id: 0,
+                who: 3,
+                approved: true,
+            }
+            .into(),
+        );
+        assert_eq!(NetworkFactory::join_request(0, 3), None);
+        assert!(NetworkFactory::is_admitted(0, &3));
+        assert_noop!(
+            NetworkFactory::request_to_join(RuntimeOrigin::signed(3), 0),
+            Error::<Test>::AlreadyAllowlisted
+        );
+    });
+}
+
+#[test]
+fn invites_expire_and_run_out() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(NetworkFactory::create_network(
+            RuntimeOrigin::signed(1),
+            true,
+            b"test".to_vec()
+        ));
+        assert_ok!(NetworkFactory::set_admission_mode(
+            RuntimeOrigin::signed(1),
+            0,
+            AdmissionMode::InviteOnly
+        ));
+
+        let code_hash = blake2_256(b"welcome");
+        assert_noop!(
+            NetworkFactory::create_invite(RuntimeOrigin::signed(1), 0, code_hash, None, 0),
+            Error::<Test>::InvalidInvite
+        );
+        assert_noop!(
+            NetworkFactory::create_invite(RuntimeOrigin::signed(1), 0, code_hash, Some(1), 2),
+            Error::<Test>::InvalidInvite
+        );
+        assert_noop!(
+            NetworkFactory::create_invite(RuntimeOrigin::signed(2), 0, code_hash, None, 2),
+            Error::<Test>::NotAuthorized
+        );
+        assert_ok!(NetworkFactory::create_invite(
+            RuntimeOrigin::signed(1),
+            0,
+            code_hash,
+            Some(10),
+            2
+        ));
+        assert_noop!(
+            NetworkFactory::create_invite(RuntimeOrigin::signed(1), 0, code_hash, None, 1),
+            Error::<Test>::InviteExists
+        );
+
+        assert_noop!(
+            NetworkFactory::redeem_invite(RuntimeOrigin::signed(2), 0, b"wrong".to_vec()),
+            Error::<Test>::InviteNotFound
+        );
+        assert_ok!(NetworkFactory::redeem_invite(
+            RuntimeOrigin::signed(2),
+            0,
+            b"welcome".to_vec()
+        ));
+        System::assert_has_event(
+            Event::InviteRedeemed {
+                network_id: 0,
+                code_hash,
+                who: 2,
+            }
+            .into(),
+        );
+        assert!(NetworkFactory::is_admitted(0, &2));
+        assert_eq!(NetworkFactory::invite(0, code_hash).unwrap().uses_left, 1);
+        assert_noop!(
+            NetworkFactory::redeem_invite(RuntimeOrigin::signed(2), 0, b"welcome".to_vec()),
+            Error::<Test>::AlreadyAllowlisted
+        );
+        assert_ok!(NetworkFactory::redeem_invite(
+            RuntimeOrigin::signed(3),
+            0,
+            b"welcome".to_vec()
+        ));
+        assert!(NetworkFactory::invite(0, code_hash).is_none());
+
+        let later = blake2_256(b"later");
+        let soon = blake2_256(b"soon");
+        assert_ok!(NetworkFactory::create_invite(
+            RuntimeOrigin::signed(1),
+            0,
+            later,
+            None,
+            1
+        ));
+        assert_ok!(NetworkFactory::create_invite(
+            RuntimeOrigin::signed(1),
+            0,
+            soon,
+            Some(5),
+            1
+        ));
+        System::set_block_number(5);
+        assert_noop!(
+            NetworkFactory::redeem_invite(RuntimeOrigin::signed(4), 0, b"soon".to_vec()),
+            Error::<Test>::InviteExpired
+        );
+
+        // Anyone can remove expired invites, but only admins revoke live ones
+        assert_noop!(
+            NetworkFactory::revoke_invite(RuntimeOrigin::signed(4), 0, later),
+            Error::<Test>::NotAuthorized
+        );
+        assert_ok!(NetworkFactory::revoke_invite(
+            RuntimeOrigin::signed(4),
+            0,
+            soon
+        ));
+        System::assert_last_event(
+            Event::InviteRevoked {
+                network_id: 0,
+                code_hash: soon,
+            }
+            .into(),
+        );
+        assert_ok!(NetworkFactory::revoke_invite(
+            RuntimeOrigin::signed(1),
+            0,
+            later
+        ));
+        assert_noop!(
+            NetworkFactory::redeem_invite(RuntimeOrigin::signed(4), 0, b"later".to_vec()),
+            Error::<Test>::InviteNotFound
+        );
+    });
+}
@@ -5,10 +5,16 @@
 //! ## Overview
 //!
 //! This pallet allows networks to configure weighted baskets of external
-//! services (ID, storage, compute) with proofs and indices.
+//! services (ID, storage, compute) with proofs and indices. Baskets are
+//! managed by each network's billing managers, checked through the network
+//! factory's `NetworkInspect`, and only change while the network is active.
+//! Baskets of closed networks are removed through `NetworkCleanup`, and passed
+//! network referenda set basket weights through `NetworkActionHandler`.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
+extern crate alloc;
+
 pub use pallet::*;
 
 #[cfg(test)]
@@ -19,9 +25,13 @@ mod tests;
 
 #[frame_support::pallet]
 pub mod pallet {
+    use alloc::vec::Vec;
     use codec::{Decode, Encode, MaxEncodedLen};
     use frame_support::pallet_prelude::*;
     use frame_system::pallet_prelude::*;
+    use pallet_network_factory::{
+        NetworkAction, NetworkActionHandler, NetworkCleanup, NetworkInspect, NetworkRole,
+    };
     use scale_info::TypeInfo;
     use sp_runtime::{FixedU128, Permill, RuntimeDebug};
 
@@ -49,6 +59,9 @@ pub mod pallet {
     pub trait Config: frame_system::Config {
         /// The overarching runtime event type.
         type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+        /// Network roles, checked before baskets are changed.
+        type Networks: NetworkInspect<Self::AccountId>;
     }
 
     /// Storage for baskets per network
@@ -93,13 +106,18 @@ pub mod pallet {
         ServiceKeyTooLong,
         /// Proof CID is too long.
         ProofCidTooLong,
+        /// Caller is not a billing manager of the network.
+        NotBillingManager,
+        /// The network is paused, archived or closed.
+        NetworkNotActive,
     }
 
     #[pallet::call]
     impl<T: Config> Pallet<T> {
         /// Set service weights for a network.
         ///
-        /// The sum of all weights must be <= 1.0 (100%).
+        /// The sum of all weights must be <= 1.0 (100%). Only billing managers of the
+        /// network may set weights.
         #[pallet::call_index(0)]
         #[pallet::weight(Weight::from_parts(10_000, 0))]
         pub fn set_weights(
@@ -107,45 +125,30 @@ pub mod pallet {
             network_id: u64,
             weights: Vec<(Vec<u8>, u32)>,
         ) -> DispatchResult {
-            let _who = ensure_signed(origin)?;
-
-            // Convert Vec<(Vec<u8>, u32)> to BoundedVec<(ServiceKey, Weight), _>
-            let mut bounded_weights = BoundedVec::new();
-            let mut sum = 0u32;
-
-            for (key, weight_parts) in weights {
-                let service_key =
-                    BoundedVec::try_from(key).map_err(|_| Error::<T>::ServiceKeyTooLong)?;
-                let weight = Permill::from_parts(weight_parts);
-                bounded_weights
-                    .try_push((service_key, weight))
-                    .map_err(|_| Error::<T>::TooManyWeights)?;
-                sum = sum.saturating_add(weight_parts);
-            }
-
-            // Ensure sum <= 1.0 (1_000_000 parts)
-            ensure!(sum <= 1_000_000, Error::<T>::WeightSumExceedsOne);
-
-            // Get or create basket
-            let mut basket = Baskets::<T>::get(network_id).unwrap_or(Basket {
-                weights: BoundedVec::new(),
-                proofs: BoundedVec::new(),
-                index: FixedU128::from(0),
-            });
-
-            basket.weights = bounded_weights;
-            Baskets::<T>::insert(network_id, basket);
-
-            Self::deposit_event(Event::WeightsUpdated { network_id });
-
-            Ok(())
+            let who = ensure_signed(origin)?;
+
+            Self::ensure_billing_manager(&who, network_id)?;
+
+            let weights = weights
+                .into_iter()
+                .map(|(key, weight_parts)| {
+                    let service_key =
+                        BoundedVec::try_from(key).map_err(|_| Error::<T>::ServiceKeyTooLong)?;
+                    Ok((service_key, weight_parts))
+                })
+                .collect::<Result<Vec<_>, Error<T>>>()?;
+            Self::do_set_weights(network_id, weights)
         }
 
         /// Add a proof CID to the basket.
+        ///
+        /// Only billing managers of the network may add proofs.
         #[pallet::call_index(1)]
         #[pallet::weight(Weight::from_parts(10_000, 0))]
         pub fn add_proof(origin: OriginFor<T>, network_id: u64, cid: Vec<u8>) -> DispatchResult {
-            let _who = ensure_signed(origin)?;
+            let who = ensure_signed(origin)?;
+
+            Self::ensure_billing_manager(&who, network_id)?;
 
             let bounded_cid = BoundedVec::try_from(cid).map_err(|_| Error::<T>::ProofCidTooLong)?;
 
@@ -191,4 +194,73 @@ pub mod pallet {
             Ok(())
         }
     }
+
+    impl<T: Config> Pallet<T> {
+        /// Replace the service weights of a network, given in parts per million.
+        fn do_set_weights(network_id: u64, weights: Vec<(ServiceKey, u32)>) -> DispatchResult {
+            let mut bounded_weights = BoundedVec::new();
+            let mut sum = 0u32;
+
+            for (service_key, weight_parts) in weights {
+                let weight = Permill::from_parts(weight_parts);
+                bounded_weights
+                    .try_push((service_key, weight))
+                    .map_err(|_| Error::<T>::TooManyWeights)?;
+                sum = sum.saturating_add(weight_parts);
+            }
+
+            // Ensure sum <= 1.0 (1_000_000 parts)
+            ensure!(sum <= 1_000_000, Error::<T>::WeightSumExceedsOne);
+
+            // Get or create basket
+            let mut basket = Baskets::<T>::get(network_id).unwrap_or(Basket {
+                weights: BoundedVec::new(),
+                proofs: BoundedVec::new(),
+                index: FixedU128::from(0),
+            });
+
+            basket.weights = bounded_weights;
+            Baskets::<T>::insert(network_id, basket);
+
+            Self::deposit_event(Event::WeightsUpdated { network_id });
+
+            Ok(())
+        }
+
+        /// Check that `who` manages billing for network `network_id` and that the network
+        /// is active.
+        fn ensure_billing_manager(who: &T::AccountId, network_id: u64) -> DispatchResult {
+            ensure!(
+                T::Networks::is_active(network_id),
+                Error::<T>::NetworkNotActive
+            );
+            ensure!(
+                T::Networks::has_role(network_id, who, NetworkRole::BillingManager),
+                Error::<T>::NotBillingManager
+            );
+            Ok(())
+        }
+    }
+
+    impl<T: Config> NetworkCleanup for Pallet<T> {
+        fn cleanup(network_id: u64, limit: u32) -> (u32, bool) {
+            if limit == 0 {
+                return (0, !Baskets::<T>::contains_key(network_id));
+            }
+            Baskets::<T>::remove(network_id);
+            (1, true)
+        }
+    }
+
+    impl<T: Config> NetworkActionHandler for Pallet<T> {
+        fn enact(network_id: u64, action: &NetworkAction) -> Option<DispatchResult> {
+            match action {
+                NetworkAction::SetBasketWeights(weights) => Some(Self::do_set_weights(
+                    network_id,
+                    weights.clone().into_inner(),
+                )),
+                _ => None,
+            }
+        }
+    }
 }
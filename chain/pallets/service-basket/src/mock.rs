@@ -1,5 +1,6 @@
 use crate as pallet_service_basket;
 use frame_support::derive_impl;
+use pallet_network_factory::{NetworkInspect, NetworkRole};
 use sp_runtime::BuildStorage;
 
 type Block = frame_system::mocking::MockBlock<Test>;
@@ -16,8 +17,30 @@ impl frame_system::Config for Test {
     type Block = Block;
 }
 
+/// A network that exists but is paused.
+pub const PAUSED_NETWORK: u64 = 7;
+
+/// Account 1 holds every role in every network; other accounts hold none. Every network
+/// but [`PAUSED_NETWORK`] is active.
+pub struct MockNetworks;
+
+impl NetworkInspect<u64> for MockNetworks {
+    fn network_exists(_network_id: u64) -> bool {
+        true
+    }
+
+    fn is_active(network_id: u64) -> bool {
+        network_id != PAUSED_NETWORK
+    }
+
+    fn has_role(_network_id: u64, who: &u64, _role: NetworkRole) -> bool {
+        *who == 1
+    }
+}
+
 impl pallet_service_basket::Config for Test {
     type RuntimeEvent = RuntimeEvent;
+    type Networks = MockNetworks;
 }
 
 pub fn new_test_ext() -> sp_io::TestExternalities {
@@ -1,5 +1,6 @@
 use crate::{mock::*, Error, Event};
 use frame_support::{assert_noop, assert_ok};
+use pallet_network_factory::{NetworkAction, NetworkActionHandler, NetworkCleanup};
 use sp_runtime::FixedU128;
 
 #[test]
@@ -123,3 +124,97 @@ fn recompute_index_fails_when_basket_not_found() {
         );
     });
 }
+
+#[test]
+fn only_billing_managers_change_baskets() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+
+        assert_noop!(
+            ServiceBasket::set_weights(RuntimeOrigin::signed(2), 1, vec![(b"ID".to_vec(), 1)]),
+            Error::<Test>::NotBillingManager
+        );
+
+        assert_ok!(ServiceBasket::set_weights(
+            RuntimeOrigin::signed(1),
+            1,
+            vec![(b"ID".to_vec(), 1)]
+        ));
+        assert_noop!(
+            ServiceBasket::add_proof(RuntimeOrigin::signed(2), 1, b"QmTest".to_vec()),
+            Error::<Test>::NotBillingManager
+        );
+
+        // Anyone can recompute the index
+        assert_ok!(ServiceBasket::recompute_index(RuntimeOrigin::signed(2), 1));
+    });
+}
+
+#[test]
+fn baskets_only_change_while_network_is_active() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+
+        assert_noop!(
+            ServiceBasket::set_weights(
+                RuntimeOrigin::signed(1),
+                PAUSED_NETWORK,
+                vec![(b"ID".to_vec(), 1)]
+            ),
+            Error::<Test>::NetworkNotActive
+        );
+        assert_noop!(
+            ServiceBasket::add_proof(RuntimeOrigin::signed(1), PAUSED_NETWORK, b"QmTest".to_vec()),
+            Error::<Test>::NetworkNotActive
+        );
+    });
+}
+
+#[test]
+fn cleanup_removes_basket() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(ServiceBasket::set_weights(
+            RuntimeOrigin::signed(1),
+            1,
+            vec![(b"ID".to_vec(), 1)]
+        ));
+
+        assert_eq!(ServiceBasket::cleanup(1, 0), (0, false));
+        assert_eq!(ServiceBasket::cleanup(1, 10), (1, true));
+        assert!(crate::Baskets::<Test>::get(1).is_none());
+    });
+}
+
+#[test]
+fn referenda_set_weights() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+
+        let weights = vec![(b"ID".to_vec().try_into().unwrap(), 400_000)]
+            .try_into()
+            .unwrap();
+        assert_eq!(
+            ServiceBasket::enact(1, &NetworkAction::SetBasketWeights(weights)),
+            Some(Ok(()))
+        );
+        assert_eq!(crate::Baskets::<Test>::get(1).unwrap().weights.len(), 1);
+        System::assert_last_event(Event::WeightsUpdated { network_id: 1 }.into());
+
+        let weights = vec![
+            (b"ID".to_vec().try_into().unwrap(), 600_000),
+            (b"FILE".to_vec().try_into().unwrap(), 500_000),
+        ]
+        .try_into()
+        .unwrap();
+        assert_eq!(
+            ServiceBasket::enact(1, &NetworkAction::SetBasketWeights(weights)),
+            Some(Err(Error::<Test>::WeightSumExceedsOne.into()))
+        );
+
+        // Other actions are left to other pallets
+        assert_eq!(
+            ServiceBasket::enact(1, &NetworkAction::SetMetadataUri(Default::default())),
+            None
+        );
+    });
+}
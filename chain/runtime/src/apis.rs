@@ -42,8 +42,9 @@ use sp_version::RuntimeVersion;
 
 // Local module imports
 use super::{
-    AccountId, Aura, Balance, Block, Executive, Grandpa, InherentDataExt, Nonce, Runtime,
-    RuntimeCall, RuntimeGenesisConfig, SessionKeys, System, TransactionPayment, VERSION,
+    AccountId, Aura, Balance, Block, Executive, Grandpa, Identity, InherentDataExt, NetworkFactory,
+    Nonce, Runtime, RuntimeCall, RuntimeGenesisConfig, SessionKeys, System, TransactionPayment,
+    VERSION,
 };
 
 impl_runtime_apis! {
@@ -220,6 +221,93 @@ impl_runtime_apis! {
         }
     }
 
+    impl pallet_identity_runtime_api::IdentityApi<Block, AccountId> for Runtime {
+        fn identity_of(who: AccountId) -> Option<AccountId> {
+            Identity::identity_of(&who)
+        }
+
+        fn auth_keys(who: AccountId) -> Vec<pallet_identity::AuthKey> {
+            Identity::auth_keys(&who)
+        }
+
+        fn personhood_score(who: AccountId) -> u32 {
+            Identity::personhood_score(&who)
+        }
+
+        fn commitments(who: AccountId) -> Vec<(Vec<u8>, [u8; 32])> {
+            Identity::commitments(&who)
+        }
+    }
+
+    impl pallet_identity_runtime_api::ReputationApi<Block, AccountId> for Runtime {
+        fn reputation(who: AccountId, network_id: u64) -> Option<pallet_identity::ReputationView> {
+            Identity::reputation(&who, network_id)
+        }
+
+        fn reputations(who: AccountId) -> Vec<(u64, pallet_identity::ReputationView)> {
+            Identity::reputations(&who)
+        }
+    }
+
+    impl pallet_identity_runtime_api::NameResolverApi<Block, AccountId> for Runtime {
+        fn resolve(name: Vec<u8>) -> Option<AccountId> {
+            Identity::resolve_name(&name)
+        }
+
+        fn primary_name(who: AccountId) -> Option<Vec<u8>> {
+            Identity::primary_name(&who)
+        }
+    }
+
+    impl pallet_network_factory_runtime_api::NetworkFactoryApi<Block, AccountId> for Runtime {
+        fn networks(
+            start: u64,
+            limit: u32,
+        ) -> Vec<pallet_network_factory::NetworkDetails<AccountId>> {
+            NetworkFactory::networks_page(start, limit)
+        }
+
+        fn networks_by_owner(
+            owner: AccountId,
+            start: u64,
+            limit: u32,
+        ) -> Vec<pallet_network_factory::NetworkDetails<AccountId>> {
+            NetworkFactory::networks_by_owner(&owner, start, limit)
+        }
+
+        fn network_info(
+            network_id: u64,
+        ) -> Option<pallet_network_factory::NetworkDetails<AccountId>> {
+            NetworkFactory::network_details(network_id)
+        }
+
+        fn network_by_handle(
+            handle: Vec<u8>,
+        ) -> Option<pallet_network_factory::NetworkDetails<AccountId>> {
+            NetworkFactory::network_by_handle(&handle)
+        }
+
+        fn curve_price(network_id: u64) -> Option<u128> {
+            NetworkFactory::curve_price(network_id)
+        }
+
+        fn curve_buy_cost(network_id: u64, steps: u32) -> Option<u128> {
+            NetworkFactory::curve_buy_cost(network_id, steps)
+        }
+
+        fn curve_sell_return(network_id: u64, steps: u32) -> Option<u128> {
+            NetworkFactory::curve_sell_return(network_id, steps)
+        }
+
+        fn children(network_id: u64) -> Vec<u64> {
+            NetworkFactory::children_of(network_id)
+        }
+
+        fn ancestors(network_id: u64) -> Vec<u64> {
+            NetworkFactory::ancestors_of(network_id)
+        }
+    }
+
     #[cfg(feature = "runtime-benchmarks")]
     impl frame_benchmarking::Benchmark<Block> for Runtime {
         fn benchmark_metadata(extra: bool) -> (
@@ -26,13 +26,19 @@
 // Substrate and Polkadot dependencies
 use frame_support::{
     derive_impl, parameter_types,
-    traits::{ConstBool, ConstU128, ConstU32, ConstU64, ConstU8, VariantCountOf},
+    traits::{
+        AsEnsureOriginWithArg, ConstBool, ConstU128, ConstU32, ConstU64, ConstU8,
+        NeverEnsureOrigin, VariantCountOf,
+    },
     weights::{
         constants::{RocksDbWeight, WEIGHT_REF_TIME_PER_SECOND},
         IdentityFee, Weight,
     },
 };
-use frame_system::limits::{BlockLength, BlockWeights};
+use frame_system::{
+    limits::{BlockLength, BlockWeights},
+    EnsureRoot,
+};
 use pallet_transaction_payment::{ConstFeeMultiplier, FungibleAdapter, Multiplier};
 use sp_consensus_aura::sr25519::AuthorityId as AuraId;
 use sp_runtime::{traits::One, Perbill};
@@ -40,9 +46,10 @@ use sp_version::RuntimeVersion;
 
 // Local module imports
 use super::{
-    AccountId, Aura, Balance, Balances, Block, BlockNumber, Hash, Nonce, PalletInfo, Runtime,
-    RuntimeCall, RuntimeEvent, RuntimeFreezeReason, RuntimeHoldReason, RuntimeOrigin, RuntimeTask,
-    System, EXISTENTIAL_DEPOSIT, SLOT_DURATION, VERSION,
+    AccessGate, AccountId, Assets, Aura, Balance, Balances, Block, BlockNumber, Hash, Identity,
+    NetworkFactory, Nonce, PalletInfo, Runtime, RuntimeCall, RuntimeEvent, RuntimeFreezeReason,
+    RuntimeHoldReason, RuntimeOrigin, RuntimeTask, ServiceBasket, System, DAYS,
+    EXISTENTIAL_DEPOSIT, MILLI_UNIT, SLOT_DURATION, UNIT, VERSION,
 };
 
 const NORMAL_DISPATCH_RATIO: Perbill = Perbill::from_percent(75);
@@ -163,22 +170,119 @@ impl pallet_template::Config for Runtime {
     type WeightInfo = pallet_template::weights::SubstrateWeight<Runtime>;
 }
 
+parameter_types! {
+    pub const NameFee: Balance = UNIT;
+    pub const NameRenewalPeriod: BlockNumber = 365 * DAYS;
+    pub const SubIdentityDeposit: Balance = 100 * MILLI_UNIT;
+    pub const VouchStake: Balance = 10 * UNIT;
+    pub const VouchDuration: BlockNumber = 180 * DAYS;
+    pub const ReputationHalfLife: BlockNumber = 90 * DAYS;
+    pub const ReputationVolumeUnit: u128 = UNIT;
+}
+
 /// Configure the identity pallet.
 impl pallet_identity::Config for Runtime {
     type RuntimeEvent = RuntimeEvent;
+    type RuntimeHoldReason = RuntimeHoldReason;
+    type Currency = Balances;
+    // Name fees are burned.
+    type NameFeeHandler = ();
+    type NameFee = NameFee;
+    type NameRenewalPeriod = NameRenewalPeriod;
+    type MaxRenewalPeriods = ConstU32<10>;
+    type MaxSubnames = ConstU32<100>;
+    type RegistrarOrigin = EnsureRoot<AccountId>;
+    type MaxRegistrars = ConstU32<20>;
+    type SubIdentityDeposit = SubIdentityDeposit;
+    type MaxSubIdentities = ConstU32<100>;
+    type MaxAuthKeys = ConstU32<8>;
+    type VouchStake = VouchStake;
+    type VouchDuration = VouchDuration;
+    type MaxVouches = ConstU32<16>;
+    type PersonhoodThreshold = ConstU32<3>;
+    type DuplicateOrigin = EnsureRoot<AccountId>;
+    // Stakes slashed from vouchers of duplicates are burned.
+    type VouchSlashHandler = ();
+    type ReputationHalfLife = ReputationHalfLife;
+    type ReputationVolumeUnit = ReputationVolumeUnit;
+    type MaxCommitments = ConstU32<16>;
+}
+
+parameter_types! {
+    pub const AssetDeposit: Balance = 10 * UNIT;
+    pub const AssetAccountDeposit: Balance = 10 * MILLI_UNIT;
+    pub const ApprovalDeposit: Balance = MILLI_UNIT;
+}
+
+/// Configure the assets pallet that holds network coins.
+impl pallet_assets::Config for Runtime {
+    type RuntimeEvent = RuntimeEvent;
+    type Balance = Balance;
+    type AssetId = u32;
+    type AssetIdParameter = codec::Compact<u32>;
+    type Currency = Balances;
+    // Only the network factory and root, through `force_create`, create assets, so asset
+    // IDs cannot be squatted.
+    type CreateOrigin = AsEnsureOriginWithArg<NeverEnsureOrigin<AccountId>>;
+    type ForceOrigin = EnsureRoot<AccountId>;
+    type AssetDeposit = AssetDeposit;
+    type AssetAccountDeposit = AssetAccountDeposit;
+    // Network treasuries administer their coins and cannot pay metadata deposits.
+    type MetadataDepositBase = ConstU128<0>;
+    type MetadataDepositPerByte = ConstU128<0>;
+    type ApprovalDeposit = ApprovalDeposit;
+    type StringLimit = ConstU32<50>;
+    type Freezer = ();
+    type Holder = ();
+    type Extra = ();
+    type CallbackHandle = ();
+    type WeightInfo = pallet_assets::weights::SubstrateWeight<Runtime>;
+    type RemoveItemsLimit = ConstU32<1000>;
+    #[cfg(feature = "runtime-benchmarks")]
+    type BenchmarkHelper = ();
+}
+
+parameter_types! {
+    pub const DefaultAssetMinBalance: Balance = MILLI_UNIT;
+    pub const NetworkDeposit: Balance = 100 * UNIT;
+    pub const VoteLockingPeriod: BlockNumber = 7 * DAYS;
+    pub const TemplateDeposit: Balance = 10 * UNIT;
 }
 
 /// Configure the network factory pallet.
 impl pallet_network_factory::Config for Runtime {
     type RuntimeEvent = RuntimeEvent;
+    type Assets = Assets;
+    type DefaultAssetMinBalance = DefaultAssetMinBalance;
+    type MaxVestingSchedules = ConstU32<16>;
+    type RuntimeHoldReason = RuntimeHoldReason;
+    type Currency = Balances;
+    type NetworkDeposit = NetworkDeposit;
+    type MaxNetworksPerAccount = ConstU32<10>;
+    type GovernanceOrigin = EnsureRoot<AccountId>;
+    type Cleanup = (AccessGate, ServiceBasket);
+    type MaxCouncilMembers = ConstU32<16>;
+    type MaxSpendProposals = ConstU32<32>;
+    type Members = AccessGate;
+    type ActionHandler = (AccessGate, ServiceBasket);
+    type MaxReferenda = ConstU32<16>;
+    type VoteLockingPeriod = VoteLockingPeriod;
+    type MaxChildren = ConstU32<32>;
+    type MaxDepth = ConstU32<4>;
+    type TemplateDeposit = TemplateDeposit;
+    type MaxTemplateResources = ConstU32<16>;
 }
 
 /// Configure the access gate pallet.
 impl pallet_access_gate::Config for Runtime {
     type RuntimeEvent = RuntimeEvent;
+    type Identity = Identity;
+    type Reputation = Identity;
+    type MaxResources = ConstU32<256>;
 }
 
 /// Configure the service basket pallet.
 impl pallet_service_basket::Config for Runtime {
     type RuntimeEvent = RuntimeEvent;
+    type Networks = NetworkFactory;
 }
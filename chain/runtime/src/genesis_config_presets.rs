@@ -15,9 +15,10 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::{AccountId, BalancesConfig, RuntimeGenesisConfig, SudoConfig};
+use crate::{AccountId, BalancesConfig, IdentityConfig, RuntimeGenesisConfig, SudoConfig};
 use alloc::{vec, vec::Vec};
 use frame_support::build_struct_json_patch;
+use pallet_identity::AuthKey;
 use serde_json::Value;
 use sp_consensus_aura::sr25519::AuthorityId as AuraId;
 use sp_consensus_grandpa::AuthorityId as GrandpaId;
@@ -29,6 +30,7 @@ fn testnet_genesis(
     initial_authorities: Vec<(AuraId, GrandpaId)>,
     endowed_accounts: Vec<AccountId>,
     root: AccountId,
+    identities: Vec<(AccountId, Option<Vec<u8>>, Vec<AuthKey>)>,
 ) -> Value {
     build_struct_json_patch!(RuntimeGenesisConfig {
         balances: BalancesConfig {
@@ -51,9 +53,27 @@ fn testnet_genesis(
                 .collect::<Vec<_>>(),
         },
         sudo: SudoConfig { key: Some(root) },
+        identity: IdentityConfig { identities },
     })
 }
 
+// NaviIDs for Alice and Bob, named `alice.navi` and `bob.navi`, signing in with their account keys.
+fn dev_identities() -> Vec<(AccountId, Option<Vec<u8>>, Vec<AuthKey>)> {
+    [
+        (Sr25519Keyring::Alice, &b"alice.navi"[..]),
+        (Sr25519Keyring::Bob, &b"bob.navi"[..]),
+    ]
+    .into_iter()
+    .map(|(keyring, name)| {
+        (
+            keyring.to_account_id(),
+            Some(name.to_vec()),
+            vec![AuthKey::Sr25519(keyring.to_raw_public())],
+        )
+    })
+    .collect()
+}
+
 /// Return the development genesis config.
 pub fn development_config_genesis() -> Value {
     testnet_genesis(
@@ -68,6 +88,7 @@ pub fn development_config_genesis() -> Value {
             Sr25519Keyring::BobStash.to_account_id(),
         ],
         sp_keyring::Sr25519Keyring::Alice.to_account_id(),
+        dev_identities(),
     )
 }
 
@@ -89,6 +110,7 @@ pub fn local_config_genesis() -> Value {
             .map(|v| v.to_account_id())
             .collect::<Vec<_>>(),
         Sr25519Keyring::Alice.to_account_id(),
+        dev_identities(),
     )
 }
 
@@ -174,7 +174,14 @@ pub type SignedPayload = generic::SignedPayload<RuntimeCall, TxExtension>;
 ///
 /// This can be a tuple of types, each implementing `OnRuntimeUpgrade`.
 #[allow(unused_parens)]
-type Migrations = ();
+type Migrations = (
+    pallet_network_factory::migrations::v1::MigrateV0ToV1<Runtime>,
+    pallet_network_factory::migrations::v2::MigrateV1ToV2<Runtime>,
+    pallet_network_factory::migrations::v3::MigrateV2ToV3<Runtime>,
+    pallet_network_factory::migrations::v4::MigrateV3ToV4<Runtime>,
+    pallet_access_gate::migrations::v1::MigrateV0ToV1<Runtime>,
+    pallet_access_gate::migrations::v2::MigrateV1ToV2<Runtime>,
+);
 
 /// Executive: handles dispatch to the various modules.
 pub type Executive = frame_executive::Executive<
@@ -241,4 +248,8 @@ mod runtime {
 
     #[runtime::pallet_index(11)]
     pub type ServiceBasket = pallet_service_basket;
+
+    // Custom network coins minted by the network factory.
+    #[runtime::pallet_index(12)]
+    pub type Assets = pallet_assets;
 }